@@ -0,0 +1,251 @@
+//! Manifest auto-fix
+//!
+//! Applies the [`Fix`]es attached to validation diagnostics as minimal text
+//! edits to the original manifest: only the affected key or value is
+//! rewritten and missing members are appended to their object, so member
+//! order, formatting and comments are preserved. The fixed text is validated
+//! again, so the result reports whatever problems remain.
+
+use crate::json_source::{self, ParsedJson};
+use crate::manifest::{self, Fix, ManifestFormat, ValidationReport};
+use serde::Serialize;
+use std::ops::Range;
+
+/// A fix that was applied to the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AppliedFix {
+    /// JSON pointer of the fixed value
+    pub pointer: String,
+    /// What was changed
+    pub description: String,
+}
+
+/// Result of auto-fixing a manifest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixResult {
+    /// The corrected manifest text
+    pub fixed_manifest: String,
+    /// Fixes applied, in source order
+    pub fixes: Vec<AppliedFix>,
+    /// Validation report of the corrected manifest
+    pub report: ValidationReport,
+}
+
+impl FixResult {
+    /// Human-readable summary of the applied fixes and remaining problems
+    pub fn summary(&self) -> String {
+        let mut summary = if self.fixes.is_empty() {
+            "No automatic fixes available.".to_string()
+        } else {
+            let mut applied = format!("Applied {} fix(es):", self.fixes.len());
+            for fix in &self.fixes {
+                let pointer = if fix.pointer.is_empty() {
+                    "/"
+                } else {
+                    &fix.pointer
+                };
+                applied.push_str(&format!("\n- {}: {}", pointer, fix.description));
+            }
+            applied
+        };
+
+        summary.push_str(&format!("\n\n{}", self.report.summary()));
+        if !self.fixes.is_empty() {
+            summary.push_str(&format!("\n\nFixed manifest:\n{}", self.fixed_manifest));
+        }
+        summary
+    }
+}
+
+/// A pending text edit
+struct Edit {
+    range: Range<usize>,
+    replacement: String,
+    fix: AppliedFix,
+}
+
+/// Validate manifest text and apply every available automatic fix
+///
+/// When `format` is `None` it is detected from the original text, and the
+/// fixed text is validated as the same format.
+pub fn fix_source(text: &str, format: Option<ManifestFormat>) -> FixResult {
+    let report = manifest::validate_source(text, format);
+    let parsed = json_source::parse(text);
+
+    let mut edits: Vec<Edit> = report
+        .diagnostics
+        .iter()
+        .filter_map(|diagnostic| {
+            let fix = diagnostic.fix.as_ref()?;
+            edit_for(text, &parsed, &diagnostic.pointer, fix)
+        })
+        .collect();
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+
+    // Keep the first of any overlapping edits; the rest are reported again
+    // by the final validation pass
+    let mut accepted: Vec<Edit> = Vec::new();
+    for edit in edits {
+        let overlaps = accepted.last().is_some_and(|previous| {
+            edit.range.start < previous.range.end || edit.range.start == previous.range.start
+        });
+        if !overlaps {
+            accepted.push(edit);
+        }
+    }
+
+    let mut fixed = text.to_string();
+    for edit in accepted.iter().rev() {
+        fixed.replace_range(edit.range.clone(), &edit.replacement);
+    }
+
+    FixResult {
+        report: manifest::validate_source(&fixed, Some(report.format)),
+        fixed_manifest: fixed,
+        fixes: accepted.into_iter().map(|edit| edit.fix).collect(),
+    }
+}
+
+/// Turn a fix into a text edit of the original source
+fn edit_for(text: &str, parsed: &ParsedJson, pointer: &str, fix: &Fix) -> Option<Edit> {
+    let (range, replacement, description) = match fix {
+        Fix::RenameKey { to } => {
+            let range = parsed.key_span(pointer)?;
+            let description = format!("renamed {} to \"{}\"", &text[range.clone()], to);
+            (range, serde_json::to_string(to).ok()?, description)
+        }
+        Fix::Replace { value } => {
+            let range = parsed.value_span(pointer)?;
+            let replacement = serde_json::to_string(value).ok()?;
+            let description = format!("replaced {} with {}", &text[range.clone()], replacement);
+            (range, replacement, description)
+        }
+        Fix::Insert { value } => {
+            let (parent, key) = pointer.rsplit_once('/')?;
+            let key = key.replace("~1", "/").replace("~0", "~");
+            let member = format!(
+                "{}: {}",
+                serde_json::to_string(&key).ok()?,
+                serde_json::to_string(value).ok()?
+            );
+            let (range, replacement) = insertion(text, parsed, parent, &member)?;
+            (range, replacement, format!("added {}", member))
+        }
+    };
+
+    Some(Edit {
+        range,
+        replacement,
+        fix: AppliedFix {
+            pointer: pointer.to_string(),
+            description,
+        },
+    })
+}
+
+/// Where and how to add `member` to the object at `parent`
+///
+/// The member is appended after the last existing member, on its own line
+/// with the same indentation when the object spans several lines.
+fn insertion(
+    text: &str,
+    parsed: &ParsedJson,
+    parent: &str,
+    member: &str,
+) -> Option<(Range<usize>, String)> {
+    let object = parsed.value_span(parent)?;
+    if text.as_bytes().get(object.start) != Some(&b'{') {
+        return None;
+    }
+
+    let Some(last) = parsed.children(parent).last().copied() else {
+        let at = object.start + 1;
+        return Some((at..at, member.to_string()));
+    };
+
+    let end = parsed.value_span(last)?.end;
+    let key_start = parsed.key_span(last)?.start;
+    let line_start = text[..key_start].rfind('\n').map_or(0, |i| i + 1);
+    if line_start <= object.start {
+        return Some((end..end, format!(", {}", member)));
+    }
+
+    let indent: String = text[line_start..key_start]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    Some((end..end, format!(",\n{}{}", indent, member)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fix_desktop_config_preserves_layout() {
+        let text = r#"{
+  "mcpServers": {
+    "forge": {
+      "comand": "mcp-forge",
+      "args": "serve"
+    }
+  }
+}"#;
+        let result = fix_source(text, None);
+        assert_eq!(
+            result.fixed_manifest,
+            r#"{
+  "mcpServers": {
+    "forge": {
+      "command": "mcp-forge",
+      "args": ["serve"]
+    }
+  }
+}"#
+        );
+        assert_eq!(
+            result.fixes,
+            vec![
+                AppliedFix {
+                    pointer: "/mcpServers/forge/comand".to_string(),
+                    description: "renamed \"comand\" to \"command\"".to_string(),
+                },
+                AppliedFix {
+                    pointer: "/mcpServers/forge/args".to_string(),
+                    description: "replaced \"serve\" with [\"serve\"]".to_string(),
+                },
+            ]
+        );
+        assert!(result.report.valid);
+    }
+
+    #[test]
+    fn test_fix_inserts_missing_version() {
+        let text = "{\n  \"name\": \"demo\",\n  \"description\": \"Demo\"\n}";
+        let result = fix_source(text, None);
+        assert_eq!(
+            result.fixed_manifest,
+            "{\n  \"name\": \"demo\",\n  \"description\": \"Demo\",\n  \"version\": \"0.1.0\"\n}"
+        );
+        assert!(result.report.valid);
+
+        let result = fix_source(r#"{"name": "demo", "description": "Demo"}"#, None);
+        assert_eq!(
+            result.fixed_manifest,
+            r#"{"name": "demo", "description": "Demo", "version": "0.1.0"}"#
+        );
+    }
+
+    #[test]
+    fn test_fix_normalizes_semver_and_keeps_unfixable_problems() {
+        let result = fix_source(r#"{"name": "demo", "version": "v1.2"}"#, None);
+        assert_eq!(
+            result.fixed_manifest,
+            r#"{"name": "demo", "version": "1.2.0"}"#
+        );
+        assert!(!result.report.valid);
+        assert_eq!(result.report.diagnostics[0].pointer, "/description");
+        assert!(result.summary().contains("Applied 1 fix(es)"));
+    }
+}
@@ -0,0 +1,407 @@
+//! Compile checks for generated projects
+//!
+//! A scaffold that doesn't build is worse than none. [`check_project`] runs
+//! `cargo check --offline --all-targets --message-format=json` in a project
+//! directory and turns the compiler's JSON messages into
+//! [`CompilerDiagnostic`]s. The check runs offline, so every dependency must
+//! already be in the local cargo registry cache or a vendored source.
+//!
+//! Failures that happen before compilation (an unresolvable dependency, a
+//! broken `Cargo.toml`) produce no JSON diagnostics; the tail of cargo's
+//! stderr is reported instead.
+
+use crate::tool_executor::ExecutionContext;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+
+/// Number of trailing stderr lines kept when cargo fails without diagnostics
+const STDERR_TAIL_LINES: usize = 20;
+
+/// One message from the compiler
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompilerDiagnostic {
+    /// `error`, `warning`, `note` or `help`
+    pub level: String,
+    /// The main message
+    pub message: String,
+    /// Lint or error code, such as `E0425` or `unused_imports`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// File of the primary span, relative to the project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// 1-based line of the primary span
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based column of the primary span
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// The message as rustc prints it, with source snippets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered: Option<String>,
+}
+
+impl fmt::Display for CompilerDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.level)?;
+        if let Some(code) = &self.code {
+            write!(f, "[{}]", code)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let (Some(file), Some(line), Some(column)) = (&self.file, self.line, self.column) {
+            write!(f, " ({}:{}:{})", file, line, column)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of checking a project
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    /// Whether the project compiled
+    pub success: bool,
+    /// Number of errors
+    pub errors: usize,
+    /// Number of warnings
+    pub warnings: usize,
+    /// Compiler messages in the order they were emitted
+    pub diagnostics: Vec<CompilerDiagnostic>,
+    /// Tail of cargo's stderr, when it failed without compiler diagnostics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cargo_output: Option<String>,
+}
+
+impl CheckReport {
+    /// Human-readable summary of the check
+    pub fn summary(&self) -> String {
+        let mut summary = if self.success {
+            format!("cargo check passed with {} warning(s).", self.warnings)
+        } else {
+            format!(
+                "cargo check failed with {} error(s) and {} warning(s).",
+                self.errors, self.warnings
+            )
+        };
+        for diagnostic in &self.diagnostics {
+            summary.push_str(&format!("\n- {}", diagnostic));
+        }
+        if let Some(output) = &self.cargo_output {
+            summary.push_str(&format!("\n{}", output));
+        }
+        summary
+    }
+}
+
+/// A line of `cargo --message-format=json` output
+#[derive(Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum CargoMessage {
+    CompilerMessage {
+        message: RustcMessage,
+    },
+    CompilerArtifact {
+        target: CargoTarget,
+    },
+    BuildFinished {
+        success: bool,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct CargoTarget {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RustcMessage {
+    message: String,
+    level: String,
+    code: Option<RustcCode>,
+    #[serde(default)]
+    spans: Vec<RustcSpan>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RustcCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct RustcSpan {
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+}
+
+impl RustcMessage {
+    /// Totals rustc prints at the end ("aborting due to 2 previous errors",
+    /// "3 warnings emitted") rather than problems in the code
+    fn is_summary(&self) -> bool {
+        self.spans.is_empty()
+            && (self.message.starts_with("aborting due to") || self.message.ends_with("emitted"))
+    }
+
+    fn into_diagnostic(self) -> CompilerDiagnostic {
+        let primary = self.spans.iter().find(|span| span.is_primary);
+        CompilerDiagnostic {
+            file: primary.map(|span| span.file_name.clone()),
+            line: primary.map(|span| span.line_start),
+            column: primary.map(|span| span.column_start),
+            level: self.level,
+            message: self.message,
+            code: self.code.map(|code| code.code),
+            rendered: self.rendered,
+        }
+    }
+}
+
+/// Collects the parts of cargo's JSON output a report needs
+#[derive(Default)]
+struct CheckOutput {
+    diagnostics: Vec<CompilerDiagnostic>,
+    compiled: u32,
+    finished: Option<bool>,
+}
+
+impl CheckOutput {
+    /// Record one line of output; returns the name of a finished artifact
+    fn record(&mut self, line: &str) -> Option<String> {
+        match serde_json::from_str(line).ok()? {
+            CargoMessage::CompilerMessage { message } if !message.is_summary() => {
+                let diagnostic = message.into_diagnostic();
+                if !self.diagnostics.contains(&diagnostic) {
+                    self.diagnostics.push(diagnostic);
+                }
+                None
+            }
+            CargoMessage::CompilerArtifact { target } => {
+                self.compiled += 1;
+                Some(target.name)
+            }
+            CargoMessage::BuildFinished { success } => {
+                self.finished = Some(success);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn into_report(self, exit_success: bool, stderr: Vec<String>) -> CheckReport {
+        let count = |level: &str| {
+            self.diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.level == level)
+                .count()
+        };
+        let errors = count("error");
+        let warnings = count("warning");
+        let success = exit_success && self.finished != Some(false);
+        let cargo_output =
+            (!success && errors == 0 && !stderr.is_empty()).then(|| stderr.join("\n"));
+
+        CheckReport {
+            success,
+            errors,
+            warnings,
+            diagnostics: self.diagnostics,
+            cargo_output,
+        }
+    }
+}
+
+/// Run `cargo check` in `project_dir`
+///
+/// Build artifacts go to `target_dir` if given (sharing one between checks
+/// avoids recompiling dependencies), otherwise to the project's `target`.
+/// Reports one progress step per compiled crate and stops cargo when the
+/// call is cancelled.
+///
+/// # Errors
+///
+/// Returns an error if cargo cannot be started or the call is cancelled; a
+/// project that fails to compile yields a report with `success: false`.
+pub async fn check_project(
+    project_dir: &Path,
+    target_dir: Option<&Path>,
+    execution: &ExecutionContext,
+) -> Result<CheckReport, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command
+        .args([
+            "check",
+            "--offline",
+            "--all-targets",
+            "--message-format=json",
+        ])
+        .current_dir(project_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    if let Some(target_dir) = target_dir {
+        command.env("CARGO_TARGET_DIR", target_dir);
+    }
+
+    tracing::info!("Running cargo check in {}", project_dir.display());
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run cargo: {}", e))?;
+    let stdout = child.stdout.take().ok_or("cargo stdout not captured")?;
+    let stderr = tokio::spawn(tail_lines(
+        child.stderr.take().ok_or("cargo stderr not captured")?,
+    ));
+
+    let mut output = CheckOutput::default();
+    let mut lines = BufReader::new(stdout).lines();
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => line,
+            () = execution.cancelled() => {
+                tracing::info!("cargo check cancelled");
+                return Err("Cancelled by the client".to_string());
+            }
+        };
+        let Some(line) = line.map_err(|e| format!("Failed to read cargo output: {}", e))? else {
+            break;
+        };
+        if let Some(artifact) = output.record(&line) {
+            execution.report(output.compiled, None, format!("Checked {}", artifact));
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for cargo: {}", e))?;
+    let stderr = stderr.await.unwrap_or_default();
+    Ok(output.into_report(status.success(), stderr))
+}
+
+/// Manifest of the scratch crate [`check_module`] compiles code in
+const SCRATCH_MANIFEST: &str = r#"[package]
+name = "mcp-forge-scratch"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[dependencies]
+anyhow = "1"
+serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+tokio = { version = "1", features = ["full"] }
+
+[workspace]
+"#;
+
+/// Run `cargo check` on a standalone module, such as generated tool code
+///
+/// The module becomes the library of a scratch crate that depends on
+/// anyhow, serde, serde_json and tokio. Scratch crates share a target
+/// directory in the system temp directory, so dependencies compile once.
+///
+/// # Errors
+///
+/// Returns an error if the scratch crate cannot be written or cargo cannot
+/// be run; see [`check_project`].
+pub async fn check_module(code: &str, execution: &ExecutionContext) -> Result<CheckReport, String> {
+    static NEXT_SCRATCH: AtomicU32 = AtomicU32::new(0);
+
+    let root = std::env::temp_dir().join("mcp-forge-scratch");
+    let crate_dir = root.join(format!(
+        "{}-{}",
+        std::process::id(),
+        NEXT_SCRATCH.fetch_add(1, Ordering::Relaxed)
+    ));
+    let write = || -> std::io::Result<()> {
+        fs::create_dir_all(crate_dir.join("src"))?;
+        fs::write(crate_dir.join("Cargo.toml"), SCRATCH_MANIFEST)?;
+        fs::write(crate_dir.join("src/lib.rs"), code)
+    };
+    let report = match write() {
+        Ok(()) => check_project(&crate_dir, Some(&root.join("target")), execution).await,
+        Err(e) => Err(format!("Failed to write scratch crate: {}", e)),
+    };
+    let _ = fs::remove_dir_all(&crate_dir);
+    report
+}
+
+/// The last lines of a stream, skipping cargo's progress lines
+async fn tail_lines(stream: impl AsyncRead + Unpin) -> Vec<String> {
+    let mut tail = VecDeque::with_capacity(STDERR_TAIL_LINES);
+    let mut lines = BufReader::new(stream).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("Compiling") || trimmed.starts_with("Checking") {
+            continue;
+        }
+        if tail.len() == STDERR_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+    tail.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_messages_become_diagnostics() {
+        let lines = [
+            r#"{"reason":"compiler-artifact","target":{"name":"serde"},"fresh":true}"#,
+            r#"{"reason":"compiler-message","message":{"message":"cannot find value `_ctx` in this scope","level":"error","code":{"code":"E0425","explanation":null},"spans":[{"file_name":"src/prompts.rs","line_start":146,"column_start":56,"is_primary":true}],"rendered":"error[E0425]: cannot find value `_ctx`"}}"#,
+            r#"{"reason":"compiler-message","message":{"message":"unused import: `tool`","level":"warning","code":{"code":"unused_imports","explanation":null},"spans":[{"file_name":"src/tools.rs","line_start":14,"column_start":15,"is_primary":true}],"rendered":null}}"#,
+            r#"{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","level":"error","code":null,"spans":[],"rendered":"error: aborting"}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+            "not json",
+        ];
+        let mut output = CheckOutput::default();
+        let artifacts: Vec<_> = lines
+            .iter()
+            .filter_map(|line| output.record(line))
+            .collect();
+        assert_eq!(artifacts, ["serde"]);
+
+        let report = output.into_report(false, vec!["error: could not compile".to_string()]);
+        assert!(!report.success);
+        assert_eq!((report.errors, report.warnings), (1, 1));
+        assert_eq!(report.cargo_output, None);
+        assert_eq!(
+            report.diagnostics[0].to_string(),
+            "error[E0425]: cannot find value `_ctx` in this scope (src/prompts.rs:146:56)"
+        );
+        assert!(
+            report
+                .summary()
+                .starts_with("cargo check failed with 1 error(s)")
+        );
+    }
+
+    #[test]
+    fn test_cargo_failure_without_diagnostics() {
+        let report = CheckOutput::default().into_report(
+            false,
+            vec!["error: no matching package named `rmcp` found".to_string()],
+        );
+        assert!(!report.success);
+        assert_eq!(report.errors, 0);
+        assert!(
+            report
+                .summary()
+                .ends_with("error: no matching package named `rmcp` found")
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Layered configuration
+//!
+//! Server defaults come from, in increasing order of precedence:
+//! 1. Built-in defaults ([`ForgeConfig::default`])
+//! 2. The user config file, `~/.config/mcp-forge/config.toml` (or
+//!    `$XDG_CONFIG_HOME/mcp-forge/config.toml`)
+//! 3. The project config file, the nearest `.mcp-forge.toml` in the current
+//!    directory or one of its parents
+//! 4. `MCP_FORGE_*` environment variables
+//! 5. Command-line flags
+//!
+//! Each layer is a [`ConfigLayer`] that only sets the values it mentions.
+//! Relative paths in a config file are resolved against the file's directory.
+//!
+//! ```toml
+//! author = "Jane Doe <jane@example.com>"
+//! license = "Apache-2.0"
+//! rmcp_version = "0.8"
+//! workspace_root = "projects"
+//! template_dir = "templates"
+//! log_level = "debug"
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::level_filters::LevelFilter;
+
+/// File name of the project-local config file
+pub const PROJECT_CONFIG_FILE: &str = ".mcp-forge.toml";
+
+/// Server defaults after all layers are applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ForgeConfig {
+    /// Author written into generated `Cargo.toml` files
+    pub author: Option<String>,
+    /// License of generated projects
+    pub license: String,
+    /// rmcp version requirement of generated projects
+    pub rmcp_version: String,
+    /// Directory projects are generated into
+    pub workspace_root: PathBuf,
+    /// Directory with `<name>.template` files overriding built-in templates
+    pub template_dir: Option<PathBuf>,
+    /// Log level filter (`error`, `warn`, `info`, `debug`, `trace` or `off`)
+    pub log_level: Option<String>,
+}
+
+impl Default for ForgeConfig {
+    fn default() -> Self {
+        Self {
+            author: None,
+            license: "MIT".to_string(),
+            rmcp_version: "0.8".to_string(),
+            workspace_root: PathBuf::from("."),
+            template_dir: None,
+            log_level: None,
+        }
+    }
+}
+
+impl ForgeConfig {
+    /// Load the user and project config files and the environment
+    ///
+    /// The project config file is searched from `dir` upwards. Command-line
+    /// flags are applied afterwards with [`ForgeConfig::apply`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config file exists but cannot be read or parsed,
+    /// or a value is invalid.
+    pub fn load(dir: &Path) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        if let Some(path) = user_config_path() {
+            config.apply(ConfigLayer::from_file(&path)?.unwrap_or_default())?;
+        }
+        if let Some(path) = find_project_config(dir) {
+            config.apply(ConfigLayer::from_file(&path)?.unwrap_or_default())?;
+        }
+        config.apply(ConfigLayer::from_env(|name| std::env::var(name).ok()))?;
+        Ok(config)
+    }
+
+    /// Override the values set in `layer`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layer sets an unknown log level.
+    pub fn apply(&mut self, layer: ConfigLayer) -> Result<(), ConfigError> {
+        if let Some(level) = &layer.log_level {
+            level
+                .parse::<LevelFilter>()
+                .map_err(|_| ConfigError::Invalid {
+                    key: "log_level",
+                    message: format!(
+                        "unknown level '{}': expected error, warn, info, debug, trace or off",
+                        level
+                    ),
+                })?;
+        }
+
+        let ConfigLayer {
+            author,
+            license,
+            rmcp_version,
+            workspace_root,
+            template_dir,
+            log_level,
+        } = layer;
+        self.author = author.or(self.author.take());
+        self.license = license.unwrap_or(std::mem::take(&mut self.license));
+        self.rmcp_version = rmcp_version.unwrap_or(std::mem::take(&mut self.rmcp_version));
+        self.workspace_root = workspace_root.unwrap_or(std::mem::take(&mut self.workspace_root));
+        self.template_dir = template_dir.or(self.template_dir.take());
+        self.log_level = log_level.or(self.log_level.take());
+        Ok(())
+    }
+}
+
+/// One configuration layer; unset values keep the lower layer's value
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigLayer {
+    /// Author of generated projects
+    pub author: Option<String>,
+    /// License of generated projects
+    pub license: Option<String>,
+    /// rmcp version requirement of generated projects
+    pub rmcp_version: Option<String>,
+    /// Directory projects are generated into
+    pub workspace_root: Option<PathBuf>,
+    /// Template override directory
+    pub template_dir: Option<PathBuf>,
+    /// Log level filter
+    pub log_level: Option<String>,
+}
+
+impl ConfigLayer {
+    /// Read a TOML config file, or `None` if it does not exist
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid config.
+    pub fn from_file(path: &Path) -> Result<Option<Self>, ConfigError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(ConfigError::Read {
+                    path: path.to_path_buf(),
+                    message: e.to_string(),
+                });
+            }
+        };
+        let mut layer = Self::parse(&text).map_err(|message| ConfigError::Read {
+            path: path.to_path_buf(),
+            message,
+        })?;
+
+        let base = path.parent().unwrap_or(Path::new("."));
+        layer.workspace_root = layer.workspace_root.map(|p| base.join(p));
+        layer.template_dir = layer.template_dir.map(|p| base.join(p));
+        tracing::debug!("Loaded config from {}", path.display());
+        Ok(Some(layer))
+    }
+
+    /// Parse a TOML config document
+    ///
+    /// # Errors
+    ///
+    /// Returns the TOML error, including unknown keys.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml_edit::de::from_str(text).map_err(|e| e.to_string().trim().to_string())
+    }
+
+    /// Read the `MCP_FORGE_*` environment variables through `var`
+    ///
+    /// `MCP_FORGE_AUTHOR`, `MCP_FORGE_LICENSE`, `MCP_FORGE_RMCP_VERSION`,
+    /// `MCP_FORGE_WORKSPACE`, `MCP_FORGE_TEMPLATE_DIR` and
+    /// `MCP_FORGE_LOG_LEVEL`; empty values are ignored.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |name: &str| var(name).filter(|value| !value.is_empty());
+        Self {
+            author: var("MCP_FORGE_AUTHOR"),
+            license: var("MCP_FORGE_LICENSE"),
+            rmcp_version: var("MCP_FORGE_RMCP_VERSION"),
+            workspace_root: var("MCP_FORGE_WORKSPACE").map(PathBuf::from),
+            template_dir: var("MCP_FORGE_TEMPLATE_DIR").map(PathBuf::from),
+            log_level: var("MCP_FORGE_LOG_LEVEL"),
+        }
+    }
+}
+
+/// Path of the user config file, if a home directory is known
+pub fn user_config_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(config_home.join("mcp-forge").join("config.toml"))
+}
+
+/// Find the nearest project config file in `dir` or its parents
+pub fn find_project_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Error loading the configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// A config file could not be read or parsed
+    Read { path: PathBuf, message: String },
+    /// A value is invalid
+    Invalid { key: &'static str, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read { path, message } => {
+                write!(f, "Invalid config file {}: {}", path.display(), message)
+            }
+            Self::Invalid { key, message } => write!(f, "Invalid {}: {}", key, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let mut config = ForgeConfig::default();
+        config
+            .apply(
+                ConfigLayer::parse(
+                    "author = \"Jane\"\nlicense = \"Apache-2.0\"\nlog_level = \"debug\"",
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        config
+            .apply(ConfigLayer::from_env(|name| match name {
+                "MCP_FORGE_LICENSE" => Some("MIT OR Apache-2.0".to_string()),
+                "MCP_FORGE_AUTHOR" => Some(String::new()),
+                _ => None,
+            }))
+            .unwrap();
+        config
+            .apply(ConfigLayer {
+                rmcp_version: Some("0.9".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(config.author.as_deref(), Some("Jane"));
+        assert_eq!(config.license, "MIT OR Apache-2.0");
+        assert_eq!(config.rmcp_version, "0.9");
+        assert_eq!(config.log_level.as_deref(), Some("debug"));
+        assert_eq!(config.workspace_root, PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let error = ConfigLayer::parse("autor = \"Jane\"").unwrap_err();
+        assert!(error.contains("unknown field `autor`"));
+
+        let mut config = ForgeConfig::default();
+        let error = config
+            .apply(ConfigLayer {
+                log_level: Some("loud".to_string()),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid log_level: unknown level 'loud'")
+        );
+    }
+
+    #[test]
+    fn test_project_config_file() {
+        let root = std::env::temp_dir().join("mcp_forge_project_config");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("crates/server")).unwrap();
+        fs::write(
+            root.join(PROJECT_CONFIG_FILE),
+            "workspace_root = \"generated\"\ntemplate_dir = \"templates\"\n",
+        )
+        .unwrap();
+
+        let path = find_project_config(&root.join("crates/server")).unwrap();
+        assert_eq!(path, root.join(PROJECT_CONFIG_FILE));
+        let layer = ConfigLayer::from_file(&path).unwrap().unwrap();
+        assert_eq!(layer.workspace_root, Some(root.join("generated")));
+        assert_eq!(layer.template_dir, Some(root.join("templates")));
+        assert_eq!(
+            ConfigLayer::from_file(&root.join("missing.toml")).unwrap(),
+            None
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
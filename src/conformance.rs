@@ -0,0 +1,794 @@
+//! Conformance checks for MCP servers
+//!
+//! [`test_server`] launches any MCP server over stdio and runs a suite of
+//! protocol checks against it:
+//!
+//! | Check | Passes when |
+//! |-------|-------------|
+//! | `initialize` | the handshake completes with a supported protocol version and server info |
+//! | `capabilities` | every advertised list method (and `logging/setLevel`) answers |
+//! | `tool_schemas` | every input and output schema is a valid JSON Schema of type `object` |
+//! | `tool_names` | tool names are unique, 1-128 characters of `A-Z a-z 0-9 _ - .` |
+//! | `unknown_tool` | calling an unknown tool fails with -32602 (invalid params) |
+//! | `unknown_resource` | reading an unknown resource fails with -32002 (resource not found) |
+//! | `unknown_prompt` | getting an unknown prompt fails with -32602 (invalid params) |
+//! | `pagination` | list cursors end, never repeat and never repeat items |
+//! | `shutdown` | the server exits successfully once its stdin is closed |
+//!
+//! Checks that don't apply, such as `unknown_prompt` for a server without
+//! prompts, are skipped. The [`ConformanceReport`] renders as text or as
+//! JUnit XML for CI systems.
+
+use crate::protocol;
+use crate::verify;
+use rmcp::model::{
+    CallToolRequestParam, ErrorCode, GetPromptRequestParam, Implementation, JsonObject,
+    LoggingLevel, PaginatedRequestParam, ReadResourceRequestParam, ServerCapabilities,
+    SetLevelRequestParam,
+};
+use rmcp::service::{Peer, ServiceError};
+use rmcp::transport::IntoTransport;
+use rmcp::{RoleClient, ServiceExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+/// Default time a server gets for each check
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pages a list may take before pagination is considered endless
+const MAX_PAGES: usize = 100;
+
+/// Longest tool name the naming rules allow
+const MAX_TOOL_NAME_LENGTH: usize = 128;
+
+/// Names used to probe error handling; no real server should define them
+const UNKNOWN_TOOL: &str = "mcp_forge_conformance_unknown_tool";
+const UNKNOWN_RESOURCE: &str = "mcp-forge-conformance://unknown";
+const UNKNOWN_PROMPT: &str = "mcp_forge_conformance_unknown_prompt";
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Passed => "PASS",
+            Outcome::Failed => "FAIL",
+            Outcome::Skipped => "SKIP",
+        })
+    }
+}
+
+/// Result of one check
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    /// Check name, such as `unknown_tool`
+    pub name: String,
+    /// Whether the server passed
+    pub outcome: Outcome,
+    /// What was found, or why the check was skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Time the check took
+    pub duration_ms: u64,
+}
+
+/// Result of running the conformance suite against a server
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConformanceReport {
+    /// The tested server's command line
+    pub server: String,
+    /// Name and version the server reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_info: Option<Implementation>,
+    /// Protocol version the server negotiated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+    /// Number of passed checks
+    pub passed: usize,
+    /// Number of failed checks
+    pub failed: usize,
+    /// Number of skipped checks
+    pub skipped: usize,
+    /// Every check in the order it ran
+    pub checks: Vec<CheckResult>,
+}
+
+impl ConformanceReport {
+    fn new(server: impl Into<String>) -> Self {
+        Self {
+            server: server.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether no check failed
+    pub fn success(&self) -> bool {
+        self.failed == 0
+    }
+
+    fn record(&mut self, name: &str, finding: Finding, duration: Duration) {
+        let (outcome, message) = match finding {
+            Finding::Pass(message) => (Outcome::Passed, message),
+            Finding::Skip(reason) => (Outcome::Skipped, Some(reason)),
+            Finding::Fail(problem) => (Outcome::Failed, Some(problem)),
+        };
+        match outcome {
+            Outcome::Passed => self.passed += 1,
+            Outcome::Failed => self.failed += 1,
+            Outcome::Skipped => self.skipped += 1,
+        }
+        self.checks.push(CheckResult {
+            name: name.to_string(),
+            outcome,
+            message,
+            duration_ms: duration.as_millis() as u64,
+        });
+    }
+
+    /// Human-readable report, one line per check
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Conformance of `{}`", self.server);
+        if let (Some(info), Some(version)) = (&self.server_info, &self.protocol_version) {
+            summary.push_str(&format!(
+                " ({} {}, protocol {})",
+                info.name, info.version, version
+            ));
+        }
+        summary.push_str(&format!(
+            ": {} passed, {} failed, {} skipped.",
+            self.passed, self.failed, self.skipped
+        ));
+        for check in &self.checks {
+            summary.push_str(&format!("\n{} {}", check.outcome, check.name));
+            if let Some(message) = &check.message {
+                summary.push_str(&format!(": {}", message));
+            }
+        }
+        summary
+    }
+
+    /// The report as a JUnit XML document with one test suite
+    pub fn junit_xml(&self) -> String {
+        let seconds = |ms: u64| format!("{:.3}", ms as f64 / 1000.0);
+        let total_ms: u64 = self.checks.iter().map(|check| check.duration_ms).sum();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites name=\"mcp-conformance\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+            self.checks.len(),
+            self.failed,
+            self.skipped,
+            seconds(total_ms)
+        ));
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+            xml_escape(&self.server),
+            self.checks.len(),
+            self.failed,
+            self.skipped,
+            seconds(total_ms)
+        ));
+        for check in &self.checks {
+            let testcase = format!(
+                "    <testcase classname=\"mcp.conformance\" name=\"{}\" time=\"{}\"",
+                check.name,
+                seconds(check.duration_ms)
+            );
+            let message = xml_escape(check.message.as_deref().unwrap_or_default());
+            match check.outcome {
+                Outcome::Passed => xml.push_str(&format!("{}/>\n", testcase)),
+                Outcome::Skipped => xml.push_str(&format!(
+                    "{}>\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                    testcase, message
+                )),
+                Outcome::Failed => xml.push_str(&format!(
+                    "{}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                    testcase, message, message
+                )),
+            }
+        }
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+/// Escape text for XML attributes and content
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() && !matches!(c, '\n' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// What a check found
+enum Finding {
+    /// Passed, optionally with details
+    Pass(Option<String>),
+    /// Did not apply, with the reason
+    Skip(String),
+    /// Failed, with the problem
+    Fail(String),
+}
+
+/// Launch `command` over stdio and run the conformance suite against it
+///
+/// The server runs in `cwd`; each check gets `timeout`. After the suite the
+/// server's stdin is closed and it gets `timeout` to exit.
+pub async fn test_server(
+    command: &str,
+    args: &[String],
+    cwd: &Path,
+    timeout: Duration,
+) -> ConformanceReport {
+    let server = std::iter::once(command)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let spawned = Command::new(command)
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            let mut report = ConformanceReport::new(server);
+            let problem = format!("failed to start `{}`: {}", command, e);
+            report.record("initialize", Finding::Fail(problem), Duration::ZERO);
+            return report;
+        }
+    };
+    let stderr_tail = Arc::new(Mutex::new(VecDeque::new()));
+    let stderr_task = child
+        .stderr
+        .take()
+        .map(|stderr| tokio::spawn(verify::capture_stderr(stderr, stderr_tail.clone())));
+    let (Some(stdout), Some(stdin)) = (child.stdout.take(), child.stdin.take()) else {
+        let mut report = ConformanceReport::new(server);
+        let problem = "server stdio not captured".to_string();
+        report.record("initialize", Finding::Fail(problem), Duration::ZERO);
+        return report;
+    };
+
+    tracing::info!("Running conformance checks against `{}`", server);
+    let mut report = run_suite((stdout, stdin), server, timeout).await;
+
+    // The suite closed the transport, and with it the server's stdin
+    let start = Instant::now();
+    let finding = if report.server_info.is_none() {
+        let _ = child.kill().await;
+        Finding::Skip("server did not initialize".to_string())
+    } else {
+        match tokio::time::timeout(timeout, child.wait()).await {
+            Ok(Ok(status)) if status.success() => Finding::Pass(None),
+            Ok(Ok(status)) => Finding::Fail(format!("server exited with {}", status)),
+            Ok(Err(e)) => Finding::Fail(format!("failed to wait for the server: {}", e)),
+            Err(_) => {
+                let _ = child.kill().await;
+                Finding::Fail(format!(
+                    "server did not exit within {}s after stdin was closed",
+                    timeout.as_secs_f32()
+                ))
+            }
+        }
+    };
+    report.record("shutdown", finding, start.elapsed());
+
+    if let Some(check) = report.checks.first_mut()
+        && check.outcome == Outcome::Failed
+    {
+        // The server is gone, but its last lines may not have been read yet
+        if let Some(task) = stderr_task {
+            let _ = tokio::time::timeout(verify::STDERR_GRACE, task).await;
+        }
+        let tail: Vec<String> = stderr_tail
+            .lock()
+            .map(|tail| {
+                tail.iter()
+                    .filter(|line| !line.is_empty())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !tail.is_empty() {
+            let message = check.message.get_or_insert_default();
+            message.push_str(&format!("; stderr: {}", tail.join(" | ")));
+        }
+    }
+    report
+}
+
+/// Run every check except `shutdown` against the server behind `transport`
+///
+/// `server` names the server in the report. The transport is closed when
+/// the suite ends.
+pub async fn run_suite<T, E, A>(
+    transport: T,
+    server: impl Into<String>,
+    timeout: Duration,
+) -> ConformanceReport
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut report = ConformanceReport::new(server);
+
+    let start = Instant::now();
+    let client = match tokio::time::timeout(timeout, ().serve(transport)).await {
+        Ok(Ok(client)) => client,
+        Ok(Err(e)) => {
+            let problem = format!("initialize failed: {}", e);
+            report.record("initialize", Finding::Fail(problem), start.elapsed());
+            return report;
+        }
+        Err(_) => {
+            let problem = format!(
+                "server did not complete initialize within {}s",
+                timeout.as_secs_f32()
+            );
+            report.record("initialize", Finding::Fail(problem), start.elapsed());
+            return report;
+        }
+    };
+    let Some(info) = client.peer_info().cloned() else {
+        report.record(
+            "initialize",
+            Finding::Fail("server sent no initialize result".to_string()),
+            start.elapsed(),
+        );
+        return report;
+    };
+    report.server_info = Some(info.server_info.clone());
+    report.protocol_version = Some(info.protocol_version.to_string());
+    let finding = if !protocol::SUPPORTED.contains(&info.protocol_version) {
+        Finding::Fail(format!(
+            "server answered with unsupported protocol version {}",
+            info.protocol_version
+        ))
+    } else if info.server_info.name.is_empty() {
+        Finding::Fail("server info has an empty name".to_string())
+    } else {
+        Finding::Pass(None)
+    };
+    report.record("initialize", finding, start.elapsed());
+
+    let peer = client.peer().clone();
+    let capabilities = info.capabilities;
+    let checks: [(&str, Check<'_>); 7] = [
+        (
+            "capabilities",
+            Box::pin(check_capabilities(&peer, &capabilities)),
+        ),
+        (
+            "tool_schemas",
+            Box::pin(check_tool_schemas(&peer, &capabilities)),
+        ),
+        (
+            "tool_names",
+            Box::pin(check_tool_names(&peer, &capabilities)),
+        ),
+        (
+            "unknown_tool",
+            Box::pin(check_unknown_tool(&peer, &capabilities)),
+        ),
+        (
+            "unknown_resource",
+            Box::pin(check_unknown_resource(&peer, &capabilities)),
+        ),
+        (
+            "unknown_prompt",
+            Box::pin(check_unknown_prompt(&peer, &capabilities)),
+        ),
+        (
+            "pagination",
+            Box::pin(check_pagination(&peer, &capabilities)),
+        ),
+    ];
+    for (name, check) in checks {
+        let start = Instant::now();
+        let finding = tokio::time::timeout(timeout, check)
+            .await
+            .unwrap_or_else(|_| {
+                Finding::Fail(format!("no answer within {}s", timeout.as_secs_f32()))
+            });
+        report.record(name, finding, start.elapsed());
+    }
+
+    if let Err(e) = client.cancel().await {
+        tracing::warn!("Failed to close the connection: {}", e);
+    }
+    report
+}
+
+/// A check in progress
+type Check<'a> = std::pin::Pin<Box<dyn Future<Output = Finding> + Send + 'a>>;
+
+/// Every advertised list method, and `logging/setLevel`, must answer
+async fn check_capabilities(peer: &Peer<RoleClient>, capabilities: &ServerCapabilities) -> Finding {
+    let mut advertised = Vec::new();
+    let mut problems = Vec::new();
+    if capabilities.tools.is_some() {
+        advertised.push("tools");
+        if let Err(e) = peer.list_tools(None).await {
+            problems.push(format!("tools/list failed: {}", e));
+        }
+    }
+    if capabilities.resources.is_some() {
+        advertised.push("resources");
+        if let Err(e) = peer.list_resources(None).await {
+            problems.push(format!("resources/list failed: {}", e));
+        }
+    }
+    if capabilities.prompts.is_some() {
+        advertised.push("prompts");
+        if let Err(e) = peer.list_prompts(None).await {
+            problems.push(format!("prompts/list failed: {}", e));
+        }
+    }
+    if capabilities.logging.is_some() {
+        advertised.push("logging");
+        let params = SetLevelRequestParam {
+            level: LoggingLevel::Info,
+        };
+        if let Err(e) = peer.set_level(params).await {
+            problems.push(format!("logging/setLevel failed: {}", e));
+        }
+    }
+
+    if !problems.is_empty() {
+        Finding::Fail(problems.join("; "))
+    } else if advertised.is_empty() {
+        Finding::Pass(Some("no capabilities advertised".to_string()))
+    } else {
+        Finding::Pass(Some(format!("advertised {}", advertised.join(", "))))
+    }
+}
+
+/// Input and output schemas must be JSON Schemas of type `object`
+async fn check_tool_schemas(peer: &Peer<RoleClient>, capabilities: &ServerCapabilities) -> Finding {
+    if capabilities.tools.is_none() {
+        return Finding::Skip("server does not advertise tools".to_string());
+    }
+    let tools = match peer.list_all_tools().await {
+        Ok(tools) => tools,
+        Err(e) => return Finding::Fail(format!("tools/list failed: {}", e)),
+    };
+
+    let mut problems = Vec::new();
+    for tool in &tools {
+        if let Err(e) = validate_tool_schema(&tool.input_schema) {
+            problems.push(format!("{} inputSchema {}", tool.name, e));
+        }
+        if let Some(output_schema) = &tool.output_schema
+            && let Err(e) = validate_tool_schema(output_schema)
+        {
+            problems.push(format!("{} outputSchema {}", tool.name, e));
+        }
+    }
+    if problems.is_empty() {
+        Finding::Pass(Some(format!("{} tool schema(s) valid", tools.len())))
+    } else {
+        Finding::Fail(problems.join("; "))
+    }
+}
+
+/// Check that a tool schema is a usable JSON Schema for an object
+fn validate_tool_schema(schema: &JsonObject) -> Result<(), String> {
+    if schema.get("type").and_then(Value::as_str) != Some("object") {
+        return Err("must have \"type\": \"object\"".to_string());
+    }
+    let schema = Value::Object(schema.clone());
+    jsonschema::meta::validate(&schema)
+        .map_err(|e| format!("is not a valid JSON Schema: {}", e))?;
+    jsonschema::validator_for(&schema)
+        .map(|_| ())
+        .map_err(|e| format!("cannot be compiled: {}", e))
+}
+
+/// Tool names must be unique and follow the naming rules
+async fn check_tool_names(peer: &Peer<RoleClient>, capabilities: &ServerCapabilities) -> Finding {
+    if capabilities.tools.is_none() {
+        return Finding::Skip("server does not advertise tools".to_string());
+    }
+    let tools = match peer.list_all_tools().await {
+        Ok(tools) => tools,
+        Err(e) => return Finding::Fail(format!("tools/list failed: {}", e)),
+    };
+
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+    for tool in &tools {
+        if let Err(e) = validate_tool_name(&tool.name) {
+            problems.push(e);
+        }
+        if !seen.insert(tool.name.as_ref()) {
+            problems.push(format!("\"{}\" is listed more than once", tool.name));
+        }
+    }
+    if problems.is_empty() {
+        Finding::Pass(None)
+    } else {
+        Finding::Fail(problems.join("; "))
+    }
+}
+
+/// Check a tool name against the naming rules
+fn validate_tool_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_TOOL_NAME_LENGTH {
+        return Err(format!(
+            "\"{}\" must be 1 to {} characters long",
+            name, MAX_TOOL_NAME_LENGTH
+        ));
+    }
+    match name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+    {
+        Some(c) => Err(format!(
+            "\"{}\" contains '{}'; only A-Z, a-z, 0-9, '_', '-' and '.' are allowed",
+            name, c
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Calling an unknown tool must fail with invalid params
+async fn check_unknown_tool(peer: &Peer<RoleClient>, capabilities: &ServerCapabilities) -> Finding {
+    if capabilities.tools.is_none() {
+        return Finding::Skip("server does not advertise tools".to_string());
+    }
+    let params = CallToolRequestParam {
+        name: UNKNOWN_TOOL.into(),
+        arguments: None,
+    };
+    match peer.call_tool(params).await {
+        Ok(result) if result.is_error == Some(true) => Finding::Fail(format!(
+            "returned a tool error result; expected error code {}",
+            ErrorCode::INVALID_PARAMS.0
+        )),
+        Ok(_) => Finding::Fail("unknown tool call succeeded".to_string()),
+        Err(e) => expect_error_code(e, ErrorCode::INVALID_PARAMS),
+    }
+}
+
+/// Reading an unknown resource must fail with resource not found
+async fn check_unknown_resource(
+    peer: &Peer<RoleClient>,
+    capabilities: &ServerCapabilities,
+) -> Finding {
+    if capabilities.resources.is_none() {
+        return Finding::Skip("server does not advertise resources".to_string());
+    }
+    let params = ReadResourceRequestParam {
+        uri: UNKNOWN_RESOURCE.to_string(),
+    };
+    match peer.read_resource(params).await {
+        Ok(_) => Finding::Fail("reading an unknown resource succeeded".to_string()),
+        Err(e) => expect_error_code(e, ErrorCode::RESOURCE_NOT_FOUND),
+    }
+}
+
+/// Getting an unknown prompt must fail with invalid params
+async fn check_unknown_prompt(
+    peer: &Peer<RoleClient>,
+    capabilities: &ServerCapabilities,
+) -> Finding {
+    if capabilities.prompts.is_none() {
+        return Finding::Skip("server does not advertise prompts".to_string());
+    }
+    let params = GetPromptRequestParam {
+        name: UNKNOWN_PROMPT.to_string(),
+        arguments: None,
+    };
+    match peer.get_prompt(params).await {
+        Ok(_) => Finding::Fail("getting an unknown prompt succeeded".to_string()),
+        Err(e) => expect_error_code(e, ErrorCode::INVALID_PARAMS),
+    }
+}
+
+/// Pass if `error` is a JSON-RPC error with `expected` code
+fn expect_error_code(error: ServiceError, expected: ErrorCode) -> Finding {
+    match error {
+        ServiceError::McpError(e) if e.code == expected => Finding::Pass(None),
+        ServiceError::McpError(e) => Finding::Fail(format!(
+            "expected error code {}, got {} ({})",
+            expected.0, e.code.0, e.message
+        )),
+        other => Finding::Fail(format!("request failed: {}", other)),
+    }
+}
+
+/// Cursors of every advertised list must lead to the last page
+async fn check_pagination(peer: &Peer<RoleClient>, capabilities: &ServerCapabilities) -> Finding {
+    let mut walked = Vec::new();
+    let mut problems = Vec::new();
+    let mut record = |kind: &str, pages: Result<(usize, usize), String>| match pages {
+        Ok((items, pages)) => walked.push(format!("{}: {} in {} page(s)", kind, items, pages)),
+        Err(e) => problems.push(format!("{}: {}", kind, e)),
+    };
+
+    if capabilities.tools.is_some() {
+        let pages = walk_pages(|cursor| async move {
+            let page = peer
+                .list_tools(Some(PaginatedRequestParam { cursor }))
+                .await?;
+            let names = page.tools.into_iter().map(|t| t.name.to_string()).collect();
+            Ok((names, page.next_cursor))
+        })
+        .await;
+        record("tools", pages);
+    }
+    if capabilities.resources.is_some() {
+        let pages = walk_pages(|cursor| async move {
+            let page = peer
+                .list_resources(Some(PaginatedRequestParam { cursor }))
+                .await?;
+            let uris = page.resources.into_iter().map(|r| r.uri.clone()).collect();
+            Ok((uris, page.next_cursor))
+        })
+        .await;
+        record("resources", pages);
+    }
+    if capabilities.prompts.is_some() {
+        let pages = walk_pages(|cursor| async move {
+            let page = peer
+                .list_prompts(Some(PaginatedRequestParam { cursor }))
+                .await?;
+            let names = page.prompts.into_iter().map(|p| p.name).collect();
+            Ok((names, page.next_cursor))
+        })
+        .await;
+        record("prompts", pages);
+    }
+
+    if !problems.is_empty() {
+        Finding::Fail(problems.join("; "))
+    } else if walked.is_empty() {
+        Finding::Skip("server advertises no lists".to_string())
+    } else {
+        Finding::Pass(Some(walked.join(", ")))
+    }
+}
+
+/// Follow a list's cursors to the end; returns the item and page counts
+async fn walk_pages<F, Fut>(mut page: F) -> Result<(usize, usize), String>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<(Vec<String>, Option<String>), ServiceError>>,
+{
+    let mut cursors = HashSet::new();
+    let mut items = HashSet::new();
+    let mut cursor = None;
+    for pages in 1..=MAX_PAGES {
+        let (page_items, next) = page(cursor)
+            .await
+            .map_err(|e| format!("page {} failed: {}", pages, e))?;
+        for item in page_items {
+            if !items.insert(item.clone()) {
+                return Err(format!("\"{}\" appears on more than one page", item));
+            }
+        }
+        match next {
+            None => return Ok((items.len(), pages)),
+            Some(next) if !cursors.insert(next.clone()) => {
+                return Err(format!("cursor \"{}\" was returned twice", next));
+            }
+            Some(next) => cursor = Some(next),
+        }
+    }
+    Err(format!("still returning cursors after {} pages", MAX_PAGES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MCPForgeServer;
+
+    #[test]
+    fn test_tool_name_rules() {
+        assert!(validate_tool_name("generate_project").is_ok());
+        assert!(validate_tool_name("admin.tools-list_2").is_ok());
+        assert!(validate_tool_name("").is_err());
+        assert!(validate_tool_name(&"a".repeat(129)).is_err());
+        assert_eq!(
+            validate_tool_name("get weather").unwrap_err(),
+            "\"get weather\" contains ' '; only A-Z, a-z, 0-9, '_', '-' and '.' are allowed"
+        );
+    }
+
+    #[test]
+    fn test_tool_schema_rules() {
+        let schema = |value: Value| value.as_object().unwrap().clone();
+        assert!(validate_tool_schema(&schema(serde_json::json!({"type": "object"}))).is_ok());
+        assert!(
+            validate_tool_schema(&schema(serde_json::json!({"type": "string"})))
+                .unwrap_err()
+                .contains("\"type\": \"object\"")
+        );
+        assert!(
+            validate_tool_schema(&schema(
+                serde_json::json!({"type": "object", "properties": {"n": {"type": 5}}})
+            ))
+            .unwrap_err()
+            .starts_with("is not a valid JSON Schema")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_suite_against_forge_server() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            let service = MCPForgeServer::default().serve(server_io).await?;
+            service.waiting().await?;
+            anyhow::Ok(())
+        });
+
+        let report = run_suite(client_io, "in-process", DEFAULT_TIMEOUT).await;
+        assert!(report.success(), "{}", report.summary());
+        assert_eq!(report.passed, 8);
+        assert_eq!(report.server_info.unwrap().name, "MCP Forge");
+    }
+
+    #[test]
+    fn test_report_formats() {
+        let mut report = ConformanceReport::new("my-server --stdio");
+        report.record("initialize", Finding::Pass(None), Duration::from_millis(12));
+        report.record(
+            "unknown_tool",
+            Finding::Fail("expected error code -32602, got -32600 (<bad>)".to_string()),
+            Duration::from_millis(3),
+        );
+        report.record(
+            "unknown_prompt",
+            Finding::Skip("server does not advertise prompts".to_string()),
+            Duration::ZERO,
+        );
+        assert!(!report.success());
+        assert_eq!(
+            report.summary(),
+            "Conformance of `my-server --stdio`: 1 passed, 1 failed, 1 skipped.\n\
+             PASS initialize\n\
+             FAIL unknown_tool: expected error code -32602, got -32600 (<bad>)\n\
+             SKIP unknown_prompt: server does not advertise prompts"
+        );
+
+        let xml = report.junit_xml();
+        assert!(xml.contains(
+            "<testsuites name=\"mcp-conformance\" tests=\"3\" failures=\"1\" skipped=\"1\" time=\"0.015\">"
+        ));
+        assert!(xml.contains(
+            "<testcase classname=\"mcp.conformance\" name=\"initialize\" time=\"0.012\"/>"
+        ));
+        assert!(xml.contains(
+            "<failure message=\"expected error code -32602, got -32600 (&lt;bad&gt;)\">"
+        ));
+        assert!(xml.contains("<skipped message=\"server does not advertise prompts\"/>"));
+    }
+}
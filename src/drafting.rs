@@ -0,0 +1,158 @@
+//! Sampling-assisted tool implementation drafts
+//!
+//! `generate_tool` normally emits a placeholder body. With
+//! `draft_implementation: true` and a client that supports sampling, the
+//! server asks the client's model for a body through `sampling/createMessage`
+//! instead. The model gets the tool description and the advanced tool
+//! template, rendered for the tool, as a reference for style and error
+//! handling; the body it returns is inserted between [`DRAFT_BEGIN`] and [`DRAFT_END`]
+//! markers so it is never mistaken for reviewed code.
+
+use crate::resources;
+use crate::tool_executor::render_tool_template;
+use rmcp::model::{Content, CreateMessageRequestParam, ModelPreferences, Role, SamplingMessage};
+use rmcp::{RoleServer, service::RequestContext};
+
+/// Marker line before a drafted implementation
+pub const DRAFT_BEGIN: &str =
+    "// BEGIN DRAFT IMPLEMENTATION: written by a language model, review before use";
+
+/// Marker line after a drafted implementation
+pub const DRAFT_END: &str = "// END DRAFT IMPLEMENTATION";
+
+/// Upper bound on the length of a drafted body
+const MAX_DRAFT_TOKENS: u32 = 2048;
+
+const SYSTEM_PROMPT: &str = "You write Rust implementations for MCP server tools. \
+Reply with only the statements of the function body: no signature, no surrounding \
+braces, no explanations and no code fences.";
+
+/// Whether the client in `context` can answer sampling requests
+pub fn supported(context: &RequestContext<RoleServer>) -> bool {
+    context
+        .peer
+        .peer_info()
+        .is_some_and(|info| info.capabilities.sampling.is_some())
+}
+
+/// The sampling request for drafting `tool_name`
+pub fn draft_request(tool_name: &str, description: &str) -> CreateMessageRequestParam {
+    let template = resources::get_resource("template/advanced-tool-rs")
+        .and_then(|resource| {
+            resource
+                .text()
+                .map(|text| render_tool_template(text, tool_name))
+        })
+        .unwrap_or_default();
+    let prompt = format!(
+        "Write the body of this function for the MCP tool `{tool_name}`:\n\n\
+         ```rust\npub async fn execute_{tool_name_snake}() -> Result<String, String>\n```\n\n\
+         Tool description: {description}\n\n\
+         The function takes no arguments. \
+         Return `Ok` with the tool's text output or `Err` with a message for the caller. \
+         Only the standard library, anyhow, serde, serde_json and tokio are available. \
+         Follow the structure and error handling of this template:\n\n```rust\n{template}\n```",
+        tool_name_snake = tool_name.to_lowercase(),
+    );
+
+    CreateMessageRequestParam {
+        messages: vec![SamplingMessage {
+            role: Role::User,
+            content: Content::text(prompt),
+        }],
+        model_preferences: Some(ModelPreferences {
+            hints: None,
+            cost_priority: None,
+            speed_priority: None,
+            intelligence_priority: Some(0.8),
+        }),
+        system_prompt: Some(SYSTEM_PROMPT.to_string()),
+        include_context: None,
+        temperature: Some(0.2),
+        max_tokens: MAX_DRAFT_TOKENS,
+        stop_sequences: None,
+        metadata: None,
+    }
+}
+
+/// Ask the client's model to draft the body of `tool_name`
+///
+/// # Errors
+///
+/// Returns an error if the client doesn't support sampling, the request
+/// fails or is cancelled, or the reply contains no code.
+pub async fn draft_body(
+    context: &RequestContext<RoleServer>,
+    tool_name: &str,
+    description: &str,
+) -> Result<String, String> {
+    if !supported(context) {
+        return Err("The client does not support sampling".to_string());
+    }
+
+    tracing::info!("Requesting a draft implementation of {}", tool_name);
+    let request = context
+        .peer
+        .create_message(draft_request(tool_name, description));
+    let reply = tokio::select! {
+        reply = request => reply.map_err(|e| format!("Sampling request failed: {}", e))?,
+        () = context.ct.cancelled() => return Err("Cancelled by the client".to_string()),
+    };
+    let text = reply
+        .message
+        .content
+        .as_text()
+        .map(|text| text.text.as_str())
+        .ok_or("The model did not reply with text")?;
+    let body = extract_body(text);
+    if body.is_empty() {
+        return Err("The model replied without code".to_string());
+    }
+    tracing::info!("Received a draft from {}", reply.model);
+    Ok(body)
+}
+
+/// The code in a model reply, without surrounding prose or code fences
+fn extract_body(reply: &str) -> String {
+    let code = match reply.split_once("```") {
+        Some((_, fenced)) => {
+            // Skip the fence's language tag
+            let fenced = fenced.split_once('\n').map_or("", |(_, code)| code);
+            fenced.split_once("```").map_or(fenced, |(code, _)| code)
+        }
+        None => reply,
+    };
+    code.trim_matches('\n').trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_body() {
+        assert_eq!(
+            extract_body("let n = 1;\nOk(n.to_string())\n"),
+            "let n = 1;\nOk(n.to_string())"
+        );
+        assert_eq!(
+            extract_body("Here you go:\n```rust\n    Ok(\"hi\".into())\n```\nDone."),
+            "    Ok(\"hi\".into())"
+        );
+        assert_eq!(extract_body("```\n```"), "");
+    }
+
+    #[test]
+    fn test_draft_request() {
+        let request = draft_request("word_count", "Count the words in a text");
+        let prompt = &request.messages[0].content.as_text().unwrap().text;
+        assert!(prompt.contains("pub async fn execute_word_count()"));
+        assert!(prompt.contains("Count the words in a text"));
+        assert!(prompt.contains("The function takes no arguments."));
+        assert!(prompt.contains("pub struct WordCountRequest"));
+        assert!(prompt.contains("pub async fn handle_word_count("));
+        assert!(!prompt.contains("{tool_name"));
+        assert!(!prompt.contains("draft_implementation"));
+        assert_eq!(request.max_tokens, MAX_DRAFT_TOKENS);
+    }
+}
@@ -0,0 +1,192 @@
+//! Asking the user for missing tool arguments
+//!
+//! When a generator is called without an argument it would otherwise
+//! default, or with one it would reject, the server can ask the user through
+//! `elicitation/create` instead. [`request_fields`] derives the form from the
+//! tool's request struct, so the user sees the same descriptions and allowed
+//! values as the assistant did, and merges the answers back into the request.
+//!
+//! Elicitation needs protocol version 2025-06-18 and a client that declares
+//! the capability. Without either, or when the user declines, the request is
+//! returned unchanged and the tool behaves as it always has.
+
+use crate::protocol::ProtocolFeatures;
+use rmcp::model::{
+    CreateElicitationRequestParam, ElicitationAction, ElicitationSchema, EnumSchema, JsonObject,
+    PrimitiveSchema,
+};
+use rmcp::schemars::{JsonSchema, generate::SchemaSettings, transform::AddNullable};
+use rmcp::{RoleServer, service::RequestContext};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Whether the client in `context` can answer elicitation requests
+pub fn supported(context: &RequestContext<RoleServer>) -> bool {
+    ProtocolFeatures::negotiated(&context.peer).elicitation && context.peer.supports_elicitation()
+}
+
+/// Ask the user for `fields` of `request`
+///
+/// `fields` are serialized field names of `R`. Returns the request with the
+/// user's answers filled in, or unchanged if the client can't elicit, the
+/// user declines or the call is cancelled while waiting.
+pub async fn request_fields<R>(
+    context: &RequestContext<RoleServer>,
+    request: R,
+    fields: &[&str],
+    message: String,
+) -> R
+where
+    R: JsonSchema + Serialize + DeserializeOwned,
+{
+    if !supported(context) {
+        return request;
+    }
+    let requested_schema = match schema_for::<R>(fields) {
+        Ok(schema) => schema,
+        Err(e) => {
+            tracing::warn!("Cannot elicit {}: {}", fields.join(", "), e);
+            return request;
+        }
+    };
+
+    tracing::info!("Asking the user for {}", fields.join(", "));
+    let params = CreateElicitationRequestParam {
+        message,
+        requested_schema,
+    };
+    let response = tokio::select! {
+        response = context.peer.create_elicitation(params) => response,
+        () = context.ct.cancelled() => return request,
+    };
+    match response {
+        Ok(response) if response.action == ElicitationAction::Accept => {
+            merge(request, response.content.as_ref(), fields)
+        }
+        Ok(response) => {
+            tracing::info!(
+                "User did not provide {}: {:?}",
+                fields.join(", "),
+                response.action
+            );
+            request
+        }
+        Err(e) => {
+            tracing::warn!("Elicitation failed: {}", e);
+            request
+        }
+    }
+}
+
+/// Elicitation schema for `fields` of `R`
+///
+/// Elicitation forms are flat objects of primitive values, so only the
+/// requested fields are kept; they are all required.
+///
+/// # Errors
+///
+/// Returns an error if a field doesn't exist or isn't a primitive value.
+pub fn schema_for<R: JsonSchema>(fields: &[&str]) -> Result<ElicitationSchema, String> {
+    let mut settings = SchemaSettings::draft07();
+    settings.transforms = vec![Box::new(AddNullable::default())];
+    let schema = settings.into_generator().into_root_schema_for::<R>();
+    let Some(Value::Object(mut properties)) = schema.get("properties").cloned() else {
+        return Err(format!("{} has no properties", R::schema_name()));
+    };
+
+    let mut elicited = BTreeMap::new();
+    for field in fields {
+        let property = properties
+            .remove(*field)
+            .ok_or_else(|| format!("{} has no field '{}'", R::schema_name(), field))?;
+        elicited.insert(field.to_string(), primitive_schema(property, field)?);
+    }
+
+    Ok(ElicitationSchema::new(elicited)
+        .with_required(fields.iter().map(ToString::to_string).collect()))
+}
+
+/// Convert one property of a request schema
+///
+/// String properties with allowed values become enum schemas; without this
+/// the values would be dropped, since string schemas have no `enum`.
+fn primitive_schema(mut property: Value, field: &str) -> Result<PrimitiveSchema, String> {
+    if let Some(object) = property.as_object_mut() {
+        object.remove("nullable");
+        object.remove("default");
+    }
+    let result = if property.get("enum").is_some() {
+        serde_json::from_value::<EnumSchema>(property).map(PrimitiveSchema::Enum)
+    } else {
+        serde_json::from_value(property)
+    };
+    result.map_err(|e| format!("Field '{}' cannot be elicited: {}", field, e))
+}
+
+/// Copy the answers for `fields` from `content` into `request`
+///
+/// Answers that don't fit the request leave it unchanged.
+fn merge<R>(request: R, content: Option<&Value>, fields: &[&str]) -> R
+where
+    R: Serialize + DeserializeOwned,
+{
+    let Some(Value::Object(answers)) = content else {
+        return request;
+    };
+    let Ok(Value::Object(mut merged)) = serde_json::to_value(&request) else {
+        return request;
+    };
+    let answers: JsonObject = answers
+        .iter()
+        .filter(|(field, _)| fields.contains(&field.as_str()))
+        .map(|(field, value)| (field.clone(), value.clone()))
+        .collect();
+    merged.extend(answers);
+    serde_json::from_value(Value::Object(merged)).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring elicited values: {}", e);
+        request
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{GenerateProjectRequest, GenerateResourceRequest};
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_for_request_fields() {
+        let schema = schema_for::<GenerateResourceRequest>(&["type"]).unwrap();
+        assert_eq!(schema.required, Some(vec!["type".to_string()]));
+        let Some(PrimitiveSchema::Enum(resource_type)) = schema.properties.get("type") else {
+            panic!("expected an enum schema: {:?}", schema.properties);
+        };
+        assert_eq!(resource_type.enum_values, ["text", "binary", "json"]);
+
+        let schema = schema_for::<GenerateProjectRequest>(&["description"]).unwrap();
+        assert!(matches!(
+            schema.properties.get("description"),
+            Some(PrimitiveSchema::String(_))
+        ));
+        assert_eq!(schema.properties.len(), 1);
+
+        assert!(schema_for::<GenerateProjectRequest>(&["missing"]).is_err());
+    }
+
+    #[test]
+    fn test_merge_answers() {
+        let request = GenerateResourceRequest {
+            resource_name: "config".to_string(),
+            resource_type: "yaml".to_string(),
+            description: None,
+        };
+        let answers = json!({"type": "json", "resource_name": "other"});
+        let merged = merge(request.clone(), Some(&answers), &["type"]);
+        assert_eq!(merged.resource_type, "json");
+        assert_eq!(merged.resource_name, "config");
+
+        let merged = merge(request, Some(&json!({"type": 42})), &["type"]);
+        assert_eq!(merged.resource_type, "yaml");
+    }
+}
@@ -0,0 +1,1307 @@
+//! Schema-driven fuzzing of MCP tool inputs
+//!
+//! [`fuzz_server`] launches any MCP server over stdio and calls each of its
+//! tools with arguments generated from the tool's input schema. Every case
+//! starts from a valid call (the required properties with example values)
+//! and changes one property:
+//!
+//! | Kind | Arguments |
+//! |------|-----------|
+//! | `missing_required` | a required property left out |
+//! | `wrong_type` | a value of another JSON type |
+//! | `boundary_string` | empty, blank, long, Unicode, control characters, format and template strings |
+//! | `path_traversal` | `../` sequences, absolute paths, drive letters and NUL bytes |
+//! | `boundary_number` | zero, negative, extreme and fractional numbers, and the schema's limits ±1 |
+//! | `huge_payload` | a 1 MiB string, 100 000 nested brackets, 10 000 array items, deep objects |
+//! | `malformed_document` | a manifest truncated at every byte, with swapped or mismatched closers, or with stray delimiters |
+//! | `random` | random values for a random subset of the properties |
+//!
+//! A tool rejects arguments with an invalid-params error (-32602) or a
+//! result with `isError` set. Arguments the input schema doesn't allow, and
+//! path traversal in properties named like paths (`*path`, `*name`, `*dir`,
+//! `*file`), must be rejected; anything else may be accepted or rejected.
+//! Malformed documents are only sent to properties named like documents
+//! (`*content`, `*document`, `*manifest`, `*config`, `*json`, `*yaml`,
+//! `*toml`), which tools parse.
+//! The server fails a case when it:
+//!
+//! - doesn't answer within the timeout, usually because the handler hung or
+//!   panicked
+//! - answers with any other JSON-RPC error, such as an internal error
+//! - accepts arguments it must reject
+//! - closes the connection, which also ends the run
+//!
+//! Tools are really called, so their side effects happen: run the server in
+//! a scratch directory and use [`FuzzOptions::with_fixed`] to pin arguments
+//! such as `verify: false`. Cases are generated from a seed, so a failing
+//! run can be repeated exactly.
+
+use rmcp::model::{CallToolRequestParam, CallToolResult, ErrorCode, JsonObject, Tool};
+use rmcp::service::ServiceError;
+use rmcp::transport::IntoTransport;
+use rmcp::{RoleClient, ServiceExt};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::fmt;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{ChildStderr, Command};
+
+/// Default time a server gets to answer each call
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default number of random cases per tool
+pub const DEFAULT_RANDOM_CASES: usize = 20;
+
+/// Length of the huge string payload
+const HUGE_STRING_BYTES: usize = 1024 * 1024;
+
+/// Nesting depth of bracket strings
+const NESTED_BRACKETS: usize = 100_000;
+
+/// Items of the huge array payload
+const HUGE_ARRAY_ITEMS: usize = 10_000;
+
+/// Nesting depth of object and array payloads; JSON-RPC parsers commonly
+/// stop at 128 levels, which would end the session instead of the call
+const NESTED_VALUE_DEPTH: usize = 100;
+
+/// Characters of the arguments shown for a failure
+const ARGUMENT_PREVIEW: usize = 160;
+
+/// Panic messages kept from the server's stderr
+const MAX_PANICS: usize = 10;
+
+/// Time the server gets to exit once its stdin is closed
+const EXIT_GRACE: Duration = Duration::from_secs(5);
+
+/// Strings at the edges of what string handling usually expects
+const BOUNDARY_STRINGS: [&str; 14] = [
+    "",
+    " ",
+    "\t\r\n",
+    "\u{0}",
+    "\u{1b}[31mred\u{1b}[0m",
+    "caf\u{e9} \u{6f22}\u{5b57} \u{1f980}",
+    "e\u{301}\u{301}\u{301}",
+    "\u{202e}gnp.exe",
+    "\u{feff}bom",
+    "%s%n%x{}{0}",
+    "{project_name}{{}}",
+    "'; DROP TABLE tools; --",
+    "<script>alert(1)</script>",
+    "-rf",
+];
+
+/// Strings that escape a directory when used as a path
+const TRAVERSAL_STRINGS: [&str; 8] = [
+    "../../../../../../etc/passwd",
+    "..",
+    "name/../../escape",
+    "..\\..\\..\\windows\\win.ini",
+    "/etc/passwd",
+    "/",
+    "C:\\Windows\\System32",
+    "name\u{0}.txt",
+];
+
+/// Property name endings that mark a value used as a path
+const PATH_LIKE_SUFFIXES: [&str; 5] = ["path", "name", "dir", "directory", "file"];
+
+/// Property name endings that mark a value parsed as a document
+const DOCUMENT_LIKE_SUFFIXES: [&str; 7] = [
+    "content", "document", "manifest", "config", "json", "yaml", "toml",
+];
+
+/// Well-formed manifest the malformed documents are derived from
+const SAMPLE_DOCUMENT: &str = r#"{"mcpServers": {"forge": {"command": "mcp-forge", "args": ["serve", "--transport=stdio"], "env": {"RUST_LOG": "info"}}}}"#;
+
+/// Documents with broken structure that a parser must recover from
+const BROKEN_DOCUMENTS: [&str; 18] = [
+    "{\"a\": 1]",
+    "{]",
+    "[}",
+    "{\"a\": [1}",
+    "{\"a\": {\"b\": 1]}",
+    "[{\"a\": 1]]",
+    "{\"a\" 1]",
+    "{:]",
+    "{,]",
+    "]",
+    "}",
+    ",",
+    "{\"a\": 1}}",
+    "[[]]]",
+    "{\"a\":,}",
+    "{\"a\": 1,,}",
+    "{\"a\": \"\\",
+    "{\"a\": 1} trailing",
+];
+
+/// Delimiters inserted into a document where they don't belong
+const STRAY_DELIMITERS: [char; 6] = [',', ':', '[', ']', '{', '}'];
+
+/// Characters random strings are drawn from
+const RANDOM_CHARS: [char; 24] = [
+    'a',
+    'b',
+    'x',
+    'Z',
+    '0',
+    '9',
+    '_',
+    '-',
+    '.',
+    '/',
+    '\\',
+    ':',
+    ' ',
+    '"',
+    '\'',
+    '{',
+    '}',
+    '%',
+    '\n',
+    '\u{0}',
+    '\u{e9}',
+    '\u{6f22}',
+    '\u{1f980}',
+    '\u{202e}',
+];
+
+/// What a case changes about a valid call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseKind {
+    MissingRequired,
+    WrongType,
+    BoundaryString,
+    PathTraversal,
+    BoundaryNumber,
+    HugePayload,
+    MalformedDocument,
+    Random,
+}
+
+impl fmt::Display for CaseKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CaseKind::MissingRequired => "missing_required",
+            CaseKind::WrongType => "wrong_type",
+            CaseKind::BoundaryString => "boundary_string",
+            CaseKind::PathTraversal => "path_traversal",
+            CaseKind::BoundaryNumber => "boundary_number",
+            CaseKind::HugePayload => "huge_payload",
+            CaseKind::MalformedDocument => "malformed_document",
+            CaseKind::Random => "random",
+        })
+    }
+}
+
+/// One generated call
+#[derive(Debug, Clone)]
+pub struct FuzzCase {
+    /// What the case changes
+    pub kind: CaseKind,
+    /// The property it changes; `None` for random cases
+    pub property: Option<String>,
+    /// Arguments of the call
+    pub arguments: Map<String, Value>,
+    /// Whether the tool must reject the arguments
+    pub must_reject: bool,
+}
+
+/// Settings of a fuzzing run
+#[derive(Debug, Clone)]
+pub struct FuzzOptions {
+    /// Time the server gets to answer each call
+    pub timeout: Duration,
+    /// Random cases per tool, on top of the systematic ones
+    pub random_cases: usize,
+    /// Seed the cases are generated from
+    pub seed: u64,
+    /// Tools to fuzz; all listed tools if empty
+    pub tools: Vec<String>,
+    /// Arguments added to every call of a tool that declares them, and
+    /// never changed
+    pub fixed: Map<String, Value>,
+}
+
+impl Default for FuzzOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            random_cases: DEFAULT_RANDOM_CASES,
+            seed: 0,
+            tools: Vec::new(),
+            fixed: Map::new(),
+        }
+    }
+}
+
+impl FuzzOptions {
+    /// Give the server `timeout` to answer each call
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Generate `count` random cases per tool
+    pub fn with_random_cases(mut self, count: usize) -> Self {
+        self.random_cases = count;
+        self
+    }
+
+    /// Generate cases from `seed`
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Fuzz only the named tools
+    pub fn with_tools(mut self, tools: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tools = tools.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Pass `value` as argument `name` to every tool that declares it
+    pub fn with_fixed(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.fixed.insert(name.into(), value);
+        self
+    }
+}
+
+/// A problem found by one or more cases
+#[derive(Debug, Clone, Serialize)]
+pub struct FuzzFailure {
+    /// Tool that was called
+    pub tool: String,
+    /// Kind of the failing cases
+    pub kind: CaseKind,
+    /// Property the cases changed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    /// What went wrong
+    pub problem: String,
+    /// Error message of the first failing case, if the server sent one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Arguments of the first failing case, shortened
+    pub arguments: String,
+    /// Number of cases that failed this way
+    pub occurrences: usize,
+}
+
+/// Result of fuzzing a server
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FuzzReport {
+    /// The fuzzed server's command line
+    pub server: String,
+    /// Seed the cases were generated from
+    pub seed: u64,
+    /// Number of fuzzed tools
+    pub tools: usize,
+    /// Number of calls made
+    pub cases: usize,
+    /// Problems found, one per tool, kind, property and problem
+    pub failures: Vec<FuzzFailure>,
+    /// Why the run ended early or the server misbehaved outside a call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Panic messages the server wrote to stderr
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub panics: Vec<String>,
+}
+
+impl FuzzReport {
+    fn new(server: impl Into<String>, seed: u64) -> Self {
+        Self {
+            server: server.into(),
+            seed,
+            ..Default::default()
+        }
+    }
+
+    /// Whether no case failed and the server stayed healthy
+    pub fn success(&self) -> bool {
+        self.failures.is_empty() && self.error.is_none() && self.panics.is_empty()
+    }
+
+    /// Human-readable report, one line per failure
+    pub fn summary(&self) -> String {
+        let failed: usize = self.failures.iter().map(|f| f.occurrences).sum();
+        let mut summary = format!(
+            "Fuzzed {} tool(s) of `{}` with {} case(s) (seed {}): {} failed.",
+            self.tools, self.server, self.cases, self.seed, failed
+        );
+        for failure in &self.failures {
+            summary.push_str(&format!("\nFAIL {} [{}]", failure.tool, failure.kind));
+            if let Some(property) = &failure.property {
+                summary.push_str(&format!(" {}", property));
+            }
+            summary.push_str(&format!(": {}", failure.problem));
+            if let Some(message) = &failure.message {
+                summary.push_str(&format!(" ({})", message));
+            }
+            summary.push_str(&format!(
+                "; {} case(s), e.g. {}",
+                failure.occurrences, failure.arguments
+            ));
+        }
+        if let Some(error) = &self.error {
+            summary.push_str(&format!("\nERROR {}", error));
+        }
+        for panic in &self.panics {
+            summary.push_str(&format!("\nPANIC {}", panic));
+        }
+        summary
+    }
+
+    fn record(&mut self, tool: &str, case: &FuzzCase, problem: String, message: Option<String>) {
+        if let Some(failure) = self.failures.iter_mut().find(|failure| {
+            failure.tool == tool
+                && failure.kind == case.kind
+                && failure.property == case.property
+                && failure.problem == problem
+        }) {
+            failure.occurrences += 1;
+            return;
+        }
+        self.failures.push(FuzzFailure {
+            tool: tool.to_string(),
+            kind: case.kind,
+            property: case.property.clone(),
+            problem,
+            message: message.map(|message| shorten(&message)),
+            arguments: shorten(&Value::Object(case.arguments.clone()).to_string()),
+            occurrences: 1,
+        });
+    }
+}
+
+/// How the server handled a case
+enum Verdict {
+    Passed,
+    /// Failed with a problem and the server's error message
+    Failed(String, Option<String>),
+    /// The connection is gone
+    Lost(String),
+}
+
+/// Launch `command` over stdio and fuzz its tools
+///
+/// The server runs in `cwd`. After the run its stdin is closed; a server
+/// that then exits unsuccessfully, or wrote panic messages to stderr, fails
+/// the run.
+pub async fn fuzz_server(
+    command: &str,
+    args: &[String],
+    cwd: &Path,
+    options: &FuzzOptions,
+) -> FuzzReport {
+    let server = std::iter::once(command)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let spawned = Command::new(command)
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            let mut report = FuzzReport::new(server, options.seed);
+            report.error = Some(format!("failed to start `{}`: {}", command, e));
+            return report;
+        }
+    };
+    let panics = Arc::new(Mutex::new(Vec::new()));
+    let stderr_reader = child
+        .stderr
+        .take()
+        .map(|stderr| tokio::spawn(capture_panics(stderr, panics.clone())));
+    let (Some(stdout), Some(stdin)) = (child.stdout.take(), child.stdin.take()) else {
+        let mut report = FuzzReport::new(server, options.seed);
+        report.error = Some("server stdio not captured".to_string());
+        return report;
+    };
+
+    tracing::info!("Fuzzing the tools of `{}`", server);
+    let mut report = run_fuzz((stdout, stdin), server, options).await;
+
+    // The run closed the transport, and with it the server's stdin
+    match tokio::time::timeout(EXIT_GRACE, child.wait()).await {
+        Ok(Ok(status)) if status.success() => {}
+        Ok(Ok(status)) => {
+            report
+                .error
+                .get_or_insert_with(|| format!("server exited with {}", status));
+        }
+        Ok(Err(e)) => {
+            report
+                .error
+                .get_or_insert_with(|| format!("failed to wait for the server: {}", e));
+        }
+        Err(_) => {
+            let _ = child.kill().await;
+        }
+    }
+    if let Some(reader) = stderr_reader {
+        let _ = tokio::time::timeout(EXIT_GRACE, reader).await;
+    }
+    report.panics = panics
+        .lock()
+        .map(|panics| panics.clone())
+        .unwrap_or_default();
+    report
+}
+
+/// Collect panic and stack overflow messages from a server's stderr
+///
+/// A panic message is the `panicked at` line and the line after it.
+async fn capture_panics(stderr: ChildStderr, panics: Arc<Mutex<Vec<String>>>) {
+    let mut lines = BufReader::new(stderr).lines();
+    let mut pending: Option<String> = None;
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Ok(mut panics) = panics.lock() else {
+            return;
+        };
+        if let Some(location) = pending.take() {
+            if panics.len() < MAX_PANICS {
+                panics.push(format!("{} {}", location, line.trim()));
+            }
+        } else if line.contains("panicked at") {
+            pending = Some(line.trim().to_string());
+        } else if line.contains("has overflowed its stack") && panics.len() < MAX_PANICS {
+            panics.push(line.trim().to_string());
+        }
+    }
+    if let Some(location) = pending
+        && let Ok(mut panics) = panics.lock()
+        && panics.len() < MAX_PANICS
+    {
+        panics.push(location);
+    }
+}
+
+/// Fuzz the tools of the server behind `transport`
+///
+/// `server` names the server in the report. The transport is closed when
+/// the run ends.
+pub async fn run_fuzz<T, E, A>(
+    transport: T,
+    server: impl Into<String>,
+    options: &FuzzOptions,
+) -> FuzzReport
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut report = FuzzReport::new(server, options.seed);
+    let seconds = options.timeout.as_secs_f32();
+
+    let client = match tokio::time::timeout(options.timeout, ().serve(transport)).await {
+        Ok(Ok(client)) => client,
+        Ok(Err(e)) => {
+            report.error = Some(format!("initialize failed: {}", e));
+            return report;
+        }
+        Err(_) => {
+            report.error = Some(format!(
+                "server did not complete initialize within {}s",
+                seconds
+            ));
+            return report;
+        }
+    };
+
+    let tools = match tokio::time::timeout(options.timeout, client.list_all_tools()).await {
+        Ok(Ok(tools)) => tools,
+        Ok(Err(e)) => {
+            report.error = Some(format!("tools/list failed: {}", e));
+            let _ = client.cancel().await;
+            return report;
+        }
+        Err(_) => {
+            report.error = Some(format!("tools/list got no answer within {}s", seconds));
+            let _ = client.cancel().await;
+            return report;
+        }
+    };
+    let mut tools: Vec<Tool> = tools
+        .into_iter()
+        .filter(|tool| options.tools.is_empty() || options.tools.iter().any(|t| *t == tool.name))
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    if let Some(missing) = options
+        .tools
+        .iter()
+        .find(|name| !tools.iter().any(|tool| tool.name == name.as_str()))
+    {
+        report.error = Some(format!("server has no tool named '{}'", missing));
+        let _ = client.cancel().await;
+        return report;
+    }
+    report.tools = tools.len();
+
+    'tools: for tool in &tools {
+        let seed = options.seed ^ name_hash(&tool.name);
+        let cases = generate_cases(
+            &tool.input_schema,
+            &options.fixed,
+            options.random_cases,
+            seed,
+        );
+        tracing::debug!("Fuzzing {} with {} case(s)", tool.name, cases.len());
+        for case in cases {
+            report.cases += 1;
+            let call = client.call_tool(CallToolRequestParam {
+                name: tool.name.clone(),
+                arguments: Some(case.arguments.clone()),
+            });
+            let outcome = tokio::time::timeout(options.timeout, call).await;
+            match classify(&case, outcome, options.timeout) {
+                Verdict::Passed => {}
+                Verdict::Failed(problem, message) => {
+                    report.record(&tool.name, &case, problem, message);
+                }
+                Verdict::Lost(problem) => {
+                    report.record(&tool.name, &case, problem.clone(), None);
+                    report.error = Some(format!("{} while calling {}", problem, tool.name));
+                    break 'tools;
+                }
+            }
+        }
+    }
+
+    if report.error.is_none() {
+        match tokio::time::timeout(options.timeout, client.list_tools(None)).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => report.error = Some(format!("tools/list failed after fuzzing: {}", e)),
+            Err(_) => {
+                report.error = Some(format!(
+                    "server stopped answering: tools/list got no answer within {}s after fuzzing",
+                    seconds
+                ));
+            }
+        }
+    }
+
+    if let Err(e) = client.cancel().await {
+        tracing::warn!("Failed to close the connection: {}", e);
+    }
+    report
+}
+
+/// Decide whether the server handled a case correctly
+fn classify(
+    case: &FuzzCase,
+    outcome: Result<Result<CallToolResult, ServiceError>, tokio::time::error::Elapsed>,
+    timeout: Duration,
+) -> Verdict {
+    match outcome {
+        Err(_) => Verdict::Failed(
+            format!(
+                "no answer within {}s (hung or panicked)",
+                timeout.as_secs_f32()
+            ),
+            None,
+        ),
+        Ok(Ok(result)) if case.must_reject && result.is_error != Some(true) => {
+            Verdict::Failed("accepted invalid arguments".to_string(), None)
+        }
+        Ok(Ok(_)) => Verdict::Passed,
+        Ok(Err(ServiceError::McpError(error))) if error.code == ErrorCode::INVALID_PARAMS => {
+            Verdict::Passed
+        }
+        Ok(Err(ServiceError::McpError(error))) => Verdict::Failed(
+            format!("answered with error {} instead of -32602", error.code.0),
+            Some(error.message.to_string()),
+        ),
+        Ok(Err(e @ (ServiceError::TransportClosed | ServiceError::TransportSend(_)))) => {
+            Verdict::Lost(format!("connection lost: {}", e))
+        }
+        Ok(Err(e)) => Verdict::Failed("request failed".to_string(), Some(e.to_string())),
+    }
+}
+
+/// Generate the cases for a tool with input schema `schema`
+///
+/// `fixed` arguments the schema declares are added to every case and never
+/// changed. The same schema, arguments and seed always give the same cases.
+pub fn generate_cases(
+    schema: &JsonObject,
+    fixed: &Map<String, Value>,
+    random_cases: usize,
+    seed: u64,
+) -> Vec<FuzzCase> {
+    let mut root = Value::Object(schema.clone());
+    normalize_nullable(&mut root);
+    let validator = jsonschema::validator_for(&root).ok();
+    let properties = root
+        .get("properties")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    let fixed: Map<String, Value> = fixed
+        .iter()
+        .filter(|(name, _)| properties.contains_key(*name))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    let required: Vec<&str> = root
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+
+    let mut base: Map<String, Value> = required
+        .iter()
+        .map(|name| {
+            let value = properties
+                .get(*name)
+                .map_or_else(|| json!("example"), |schema| example_value(schema, &root));
+            (name.to_string(), value)
+        })
+        .collect();
+    base.extend(fixed.clone());
+
+    let mut cases = Vec::new();
+    let mut push = |kind: CaseKind, property: &str, arguments: Map<String, Value>| {
+        let valid = match &validator {
+            Some(validator) => validator.is_valid(&Value::Object(arguments.clone())),
+            None => !matches!(kind, CaseKind::MissingRequired | CaseKind::WrongType),
+        };
+        let must_reject = !valid || (kind == CaseKind::PathTraversal && is_path_like(property));
+        cases.push(FuzzCase {
+            kind,
+            property: Some(property.to_string()),
+            arguments,
+            must_reject,
+        });
+    };
+    let with = |name: &str, value: Value| {
+        let mut arguments = base.clone();
+        arguments.insert(name.to_string(), value);
+        arguments
+    };
+
+    for (name, property) in properties
+        .iter()
+        .filter(|(name, _)| !fixed.contains_key(*name))
+    {
+        let property = resolve(property, &root);
+        let kinds = schema_types(property, &root);
+
+        if required.contains(&name.as_str()) {
+            let mut arguments = base.clone();
+            arguments.remove(name);
+            push(CaseKind::MissingRequired, name, arguments);
+        }
+        if !kinds.is_empty() {
+            for (kind, value) in [
+                ("string", json!("fuzz")),
+                ("integer", json!(42)),
+                ("number", json!(4.5)),
+                ("boolean", json!(true)),
+                ("array", json!([])),
+                ("object", json!({})),
+                ("null", Value::Null),
+            ] {
+                let compatible =
+                    kinds.contains(&kind) || (kind == "integer" && kinds.contains(&"number"));
+                if !compatible {
+                    push(CaseKind::WrongType, name, with(name, value));
+                }
+            }
+        }
+        if kinds.contains(&"string") {
+            let long = ["a".repeat(256), "a".repeat(65_536)];
+            for value in BOUNDARY_STRINGS.iter().map(|s| s.to_string()).chain(long) {
+                push(CaseKind::BoundaryString, name, with(name, json!(value)));
+            }
+            for value in TRAVERSAL_STRINGS {
+                push(CaseKind::PathTraversal, name, with(name, json!(value)));
+            }
+            for value in [
+                "x".repeat(HUGE_STRING_BYTES),
+                "[".repeat(NESTED_BRACKETS),
+                "{\"a\":".repeat(NESTED_BRACKETS / 5),
+                "a:\n ".repeat(NESTED_BRACKETS / 5),
+            ] {
+                push(CaseKind::HugePayload, name, with(name, json!(value)));
+            }
+            if is_document_like(name) {
+                for value in malformed_documents() {
+                    push(CaseKind::MalformedDocument, name, with(name, json!(value)));
+                }
+            }
+        }
+        if kinds.contains(&"integer") || kinds.contains(&"number") {
+            let mut values = vec![
+                json!(0),
+                json!(-1),
+                json!(i64::MIN),
+                json!(i64::MAX),
+                json!(u64::MAX),
+                json!(0.5),
+                json!(-1e308),
+                json!(1e308),
+            ];
+            for limit in ["minimum", "maximum"] {
+                if let Some(limit) = property.get(limit).and_then(Value::as_i64) {
+                    values.extend([
+                        json!(limit.saturating_sub(1)),
+                        json!(limit.saturating_add(1)),
+                    ]);
+                }
+            }
+            for value in values {
+                push(CaseKind::BoundaryNumber, name, with(name, value));
+            }
+        }
+        if kinds.contains(&"array") {
+            let item = property
+                .get("items")
+                .map_or_else(|| json!("item"), |items| example_value(items, &root));
+            let huge = Value::Array(vec![item; HUGE_ARRAY_ITEMS]);
+            push(CaseKind::HugePayload, name, with(name, huge));
+            push(
+                CaseKind::HugePayload,
+                name,
+                with(name, nested(json!([]), |v| json!([v]))),
+            );
+        }
+        if kinds.contains(&"object") || kinds.is_empty() {
+            let deep = nested(json!({}), |v| json!({ "a": v }));
+            push(CaseKind::HugePayload, name, with(name, deep));
+        }
+    }
+
+    let mut rng = Rng::new(seed);
+    for _ in 0..random_cases {
+        let mut arguments = Map::new();
+        for (name, property) in &properties {
+            let chance = if required.contains(&name.as_str()) {
+                90
+            } else {
+                50
+            };
+            if rng.chance(chance) {
+                let value = if rng.chance(80) {
+                    random_value(resolve(property, &root), &root, &mut rng, 0)
+                } else {
+                    random_any(&mut rng, 0)
+                };
+                arguments.insert(name.clone(), value);
+            }
+        }
+        arguments.extend(fixed.clone());
+        let must_reject = validator
+            .as_ref()
+            .is_some_and(|validator| !validator.is_valid(&Value::Object(arguments.clone())));
+        cases.push(FuzzCase {
+            kind: CaseKind::Random,
+            property: None,
+            arguments,
+            must_reject,
+        });
+    }
+    cases
+}
+
+/// Manifests with broken structure
+///
+/// [`BROKEN_DOCUMENTS`], and [`SAMPLE_DOCUMENT`] truncated after every
+/// byte, with each closing bracket swapped for the other kind and with
+/// each of [`STRAY_DELIMITERS`] inserted after every structural character.
+pub fn malformed_documents() -> Vec<String> {
+    let sample = SAMPLE_DOCUMENT;
+    let mut documents: Vec<String> = BROKEN_DOCUMENTS.iter().map(|s| s.to_string()).collect();
+    documents.extend((0..sample.len()).map(|end| sample[..end].to_string()));
+    for (offset, c) in sample.char_indices() {
+        let swapped = match c {
+            '}' => ']',
+            ']' => '}',
+            _ => continue,
+        };
+        let mut document = sample.to_string();
+        document.replace_range(offset..offset + 1, &swapped.to_string());
+        documents.push(document);
+    }
+    for (offset, c) in sample.char_indices() {
+        if !matches!(c, '{' | '}' | '[' | ']' | ',' | ':') {
+            continue;
+        }
+        for delimiter in STRAY_DELIMITERS {
+            let mut document = sample.to_string();
+            document.insert(offset + 1, delimiter);
+            documents.push(document);
+        }
+    }
+    documents
+}
+
+/// Whether a property name suggests its value is parsed as a document
+fn is_document_like(property: &str) -> bool {
+    let property = property.to_ascii_lowercase();
+    DOCUMENT_LIKE_SUFFIXES
+        .iter()
+        .any(|suffix| property.ends_with(suffix))
+}
+
+/// Whether a property name suggests its value is used as a path
+fn is_path_like(property: &str) -> bool {
+    let property = property.to_ascii_lowercase();
+    PATH_LIKE_SUFFIXES
+        .iter()
+        .any(|suffix| property.ends_with(suffix))
+}
+
+/// Rewrite OpenAPI-style `"nullable": true` as a `null` type
+///
+/// Schemas generated by schemars mark optional properties this way; JSON
+/// Schema validators don't understand it and would treat `null` as invalid.
+fn normalize_nullable(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            if object.get("nullable") == Some(&Value::Bool(true))
+                && let Some(Value::String(kind)) = object.get("type")
+            {
+                let types = json!([kind, "null"]);
+                object.insert("type".to_string(), types);
+            }
+            object.values_mut().for_each(normalize_nullable);
+        }
+        Value::Array(items) => items.iter_mut().for_each(normalize_nullable),
+        _ => {}
+    }
+}
+
+/// Follow a local `$ref` within the root schema
+fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .unwrap_or(schema),
+        None => schema,
+    }
+}
+
+/// The JSON types a schema allows, including those of its variants
+fn schema_types<'a>(schema: &'a Value, root: &'a Value) -> Vec<&'a str> {
+    let mut types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(kind)) => vec![kind.as_str()],
+        Some(Value::Array(kinds)) => kinds.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    for variants in ["anyOf", "oneOf"] {
+        for variant in schema
+            .get(variants)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            types.extend(schema_types(resolve(variant, root), root));
+        }
+    }
+    types
+}
+
+/// The first non-null type a schema allows
+fn main_type<'a>(schema: &'a Value, root: &'a Value) -> Option<&'a str> {
+    schema_types(schema, root)
+        .into_iter()
+        .find(|kind| *kind != "null")
+}
+
+/// An example value that matches `schema`
+fn example_value(schema: &Value, root: &Value) -> Value {
+    let schema = resolve(schema, root);
+    if let Some(value) = schema
+        .get("default")
+        .filter(|value| !value.is_null())
+        .or_else(|| schema.get("const"))
+        .or_else(|| schema.get("enum").and_then(|values| values.get(0)))
+    {
+        return value.clone();
+    }
+    if let Some(variant) = ["anyOf", "oneOf"]
+        .iter()
+        .filter_map(|key| schema.get(*key).and_then(Value::as_array))
+        .flatten()
+        .map(|variant| resolve(variant, root))
+        .find(|variant| main_type(variant, root).is_some())
+    {
+        return example_value(variant, root);
+    }
+    match main_type(schema, root) {
+        Some("string") => {
+            let min = schema.get("minLength").and_then(Value::as_u64).unwrap_or(0);
+            let max = schema.get("maxLength").and_then(Value::as_u64);
+            match max {
+                Some(max) if max < 7 => json!("e".repeat(max as usize)),
+                _ if min > 7 => json!("e".repeat(min as usize)),
+                _ => json!("example"),
+            }
+        }
+        Some("integer") => {
+            let min = schema.get("minimum").and_then(Value::as_i64).unwrap_or(1);
+            let max = schema
+                .get("maximum")
+                .and_then(Value::as_i64)
+                .unwrap_or(i64::MAX);
+            json!(min.max(1).min(max))
+        }
+        Some("number") => json!(1.5),
+        Some("boolean") => json!(true),
+        Some("array") => match schema.get("items") {
+            Some(items) => json!([example_value(items, root)]),
+            None => json!([]),
+        },
+        Some("object") => Value::Object(
+            schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(name, property)| (name.clone(), example_value(property, root)))
+                .collect(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+/// `leaf` wrapped [`NESTED_VALUE_DEPTH`] times by `wrap`
+fn nested(leaf: Value, wrap: impl Fn(Value) -> Value) -> Value {
+    (0..NESTED_VALUE_DEPTH).fold(leaf, |value, _| wrap(value))
+}
+
+/// A random value that usually matches `schema`
+fn random_value(schema: &Value, root: &Value, rng: &mut Rng, depth: usize) -> Value {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array)
+        && !values.is_empty()
+        && rng.chance(70)
+    {
+        return values[rng.below(values.len())].clone();
+    }
+    let kinds = schema_types(schema, root);
+    if kinds.is_empty() || depth > 3 {
+        return random_any(rng, depth);
+    }
+    match kinds[rng.below(kinds.len())] {
+        "string" => json!(random_string(rng)),
+        "integer" => json!(random_integer(rng)),
+        "number" => json!(random_integer(rng) as f64 / 8.0),
+        "boolean" => json!(rng.chance(50)),
+        "array" => {
+            let items = schema.get("items").map(|items| resolve(items, root));
+            let len = rng.below(5);
+            Value::Array(
+                (0..len)
+                    .map(|_| match items {
+                        Some(items) => random_value(items, root, rng, depth + 1),
+                        None => random_any(rng, depth + 1),
+                    })
+                    .collect(),
+            )
+        }
+        "object" => {
+            let mut object = Map::new();
+            for (name, property) in schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+            {
+                if rng.chance(50) {
+                    let value = random_value(resolve(property, root), root, rng, depth + 1);
+                    object.insert(name.clone(), value);
+                }
+            }
+            Value::Object(object)
+        }
+        _ => Value::Null,
+    }
+}
+
+/// A random value of any type
+fn random_any(rng: &mut Rng, depth: usize) -> Value {
+    let kinds = if depth > 3 { 4 } else { 6 };
+    match rng.below(kinds) {
+        0 => json!(random_string(rng)),
+        1 => json!(random_integer(rng)),
+        2 => json!(rng.chance(50)),
+        3 => Value::Null,
+        4 => Value::Array(
+            (0..rng.below(4))
+                .map(|_| random_any(rng, depth + 1))
+                .collect(),
+        ),
+        _ => json!({ random_string(rng): random_any(rng, depth + 1) }),
+    }
+}
+
+/// A short random string, mostly ASCII with some special characters
+fn random_string(rng: &mut Rng) -> String {
+    let len = rng.below(24);
+    (0..len)
+        .map(|_| RANDOM_CHARS[rng.below(RANDOM_CHARS.len())])
+        .collect()
+}
+
+/// A random integer, usually small and sometimes extreme
+fn random_integer(rng: &mut Rng) -> i64 {
+    match rng.below(10) {
+        0 => i64::MIN,
+        1 => i64::MAX,
+        _ => rng.below(2001) as i64 - 1000,
+    }
+}
+
+/// FNV-1a hash of a tool name, to give each tool its own case stream
+fn name_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Deterministic pseudo-random generator (xorshift64*, seeded with splitmix64)
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        Self(z.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// A number in `0..n`; `n` must not be zero
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// True with a probability of `percent` in 100
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+}
+
+/// `text` cut to [`ARGUMENT_PREVIEW`] characters
+fn shorten(text: &str) -> String {
+    match text.char_indices().nth(ARGUMENT_PREVIEW) {
+        Some((end, _)) => format!("{}... ({} bytes)", &text[..end], text.len()),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> JsonObject {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "kind": {"type": "string", "enum": ["a", "b"]},
+                "count": {"type": "integer", "minimum": 1, "maximum": 10, "nullable": true},
+                "verify": {"type": "boolean", "nullable": true}
+            },
+            "required": ["name", "kind"]
+        })
+        .as_object()
+        .unwrap()
+        .clone()
+    }
+
+    fn find<'a>(
+        cases: &'a [FuzzCase],
+        kind: CaseKind,
+        property: &str,
+        value: &Value,
+    ) -> &'a FuzzCase {
+        cases
+            .iter()
+            .find(|case| {
+                case.kind == kind
+                    && case.property.as_deref() == Some(property)
+                    && case.arguments.get(property) == Some(value)
+            })
+            .unwrap_or_else(|| panic!("no {} case for {} = {}", kind, property, value))
+    }
+
+    #[test]
+    fn test_generate_cases() {
+        let cases = generate_cases(&schema(), &Map::new(), 5, 7);
+
+        let missing = cases
+            .iter()
+            .filter(|case| case.kind == CaseKind::MissingRequired)
+            .collect::<Vec<_>>();
+        assert_eq!(missing.len(), 2);
+        assert!(missing.iter().all(|case| case.must_reject));
+
+        // Every case starts from a valid call
+        let traversal = find(
+            &cases,
+            CaseKind::PathTraversal,
+            "name",
+            &json!("/etc/passwd"),
+        );
+        assert_eq!(traversal.arguments["kind"], json!("a"));
+        assert!(traversal.must_reject, "names must not be paths");
+
+        assert!(find(&cases, CaseKind::WrongType, "name", &json!(42)).must_reject);
+        assert!(find(&cases, CaseKind::WrongType, "verify", &json!(42)).must_reject);
+        assert!(find(&cases, CaseKind::BoundaryString, "kind", &json!("")).must_reject);
+        assert!(!find(&cases, CaseKind::BoundaryString, "name", &json!("")).must_reject);
+        assert!(find(&cases, CaseKind::BoundaryNumber, "count", &json!(11)).must_reject);
+        assert!(!find(&cases, CaseKind::BoundaryNumber, "count", &json!(9)).must_reject);
+
+        // `nullable` properties accept null, so it is not a wrong type
+        assert!(!cases.iter().any(|case| case.kind == CaseKind::WrongType
+            && case.arguments.get("count") == Some(&Value::Null)));
+        assert_eq!(
+            cases
+                .iter()
+                .filter(|case| case.kind == CaseKind::Random)
+                .count(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_generate_cases_is_deterministic() {
+        let arguments = |seed| {
+            generate_cases(&schema(), &Map::new(), 10, seed)
+                .into_iter()
+                .map(|case| case.arguments)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(arguments(1), arguments(1));
+        assert_ne!(arguments(1), arguments(2));
+    }
+
+    #[test]
+    fn test_malformed_documents() {
+        let documents = malformed_documents();
+        assert!(documents.contains(&"{\"a\": 1]".to_string()));
+        assert!(documents.contains(&SAMPLE_DOCUMENT[..10].to_string()));
+        assert!(documents.iter().any(|d| d.ends_with("}}]")));
+        assert!(documents.iter().any(|d| d.starts_with("{]")));
+        assert!(!documents.contains(&SAMPLE_DOCUMENT.to_string()));
+
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "manifest_content": {"type": "string"},
+                "name": {"type": "string"}
+            }
+        });
+        let cases = generate_cases(schema.as_object().unwrap(), &Map::new(), 0, 1);
+        let malformed: Vec<_> = cases
+            .iter()
+            .filter(|case| case.kind == CaseKind::MalformedDocument)
+            .collect();
+        assert_eq!(malformed.len(), documents.len());
+        assert!(
+            malformed
+                .iter()
+                .all(|case| case.property.as_deref() == Some("manifest_content")
+                    && !case.must_reject)
+        );
+    }
+
+    #[test]
+    fn test_fixed_arguments() {
+        let fixed = json!({"verify": false}).as_object().unwrap().clone();
+        let cases = generate_cases(&schema(), &fixed, 10, 3);
+        assert!(
+            cases
+                .iter()
+                .all(|case| case.arguments["verify"] == json!(false))
+        );
+        assert!(
+            cases
+                .iter()
+                .all(|case| case.property.as_deref() != Some("verify"))
+        );
+    }
+
+    #[test]
+    fn test_classify() {
+        let case = |must_reject| FuzzCase {
+            kind: CaseKind::WrongType,
+            property: Some("name".to_string()),
+            arguments: Map::new(),
+            must_reject,
+        };
+        let timeout = Duration::from_secs(1);
+        let invalid = rmcp::ErrorData::invalid_params("bad", None);
+        let internal = rmcp::ErrorData::internal_error("oops", None);
+
+        let verdict = classify(
+            &case(true),
+            Ok(Err(ServiceError::McpError(invalid))),
+            timeout,
+        );
+        assert!(matches!(verdict, Verdict::Passed));
+        let rejected = CallToolResult::error(vec![]);
+        assert!(matches!(
+            classify(&case(true), Ok(Ok(rejected)), timeout),
+            Verdict::Passed
+        ));
+
+        let accepted = CallToolResult::success(vec![]);
+        let verdict = classify(&case(true), Ok(Ok(accepted.clone())), timeout);
+        assert!(
+            matches!(verdict, Verdict::Failed(problem, _) if problem == "accepted invalid arguments")
+        );
+        assert!(matches!(
+            classify(&case(false), Ok(Ok(accepted)), timeout),
+            Verdict::Passed
+        ));
+
+        let verdict = classify(
+            &case(false),
+            Ok(Err(ServiceError::McpError(internal))),
+            timeout,
+        );
+        assert!(matches!(verdict, Verdict::Failed(_, Some(message)) if message == "oops"));
+        let verdict = classify(
+            &case(false),
+            Ok(Err(ServiceError::TransportClosed)),
+            timeout,
+        );
+        assert!(matches!(verdict, Verdict::Lost(_)));
+    }
+
+    #[test]
+    fn test_shorten() {
+        assert_eq!(shorten("short"), "short");
+        let long = "\u{e9}".repeat(ARGUMENT_PREVIEW + 1);
+        assert!(shorten(&long).ends_with(&format!("... ({} bytes)", long.len())));
+    }
+}
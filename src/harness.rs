@@ -0,0 +1,341 @@
+//! In-process test harness for MCP servers
+//!
+//! [`TestHarness`] connects an rmcp client to any [`ServerHandler`] over an
+//! in-memory duplex transport, so tests issue real JSON-RPC requests through
+//! the protocol layer without spawning a process:
+//!
+//! ```no_run
+//! use mcp_forge::MCPForgeServer;
+//! use mcp_forge::harness::TestHarness;
+//!
+//! # async fn example() -> Result<(), String> {
+//! let harness = TestHarness::start(MCPForgeServer::default()).await?;
+//! let result = harness
+//!     .call_tool("generate_tool", serde_json::json!({"tool_name": "search", "description": "Search"}))
+//!     .await
+//!     .map_err(|e| e.to_string())?;
+//! assert_eq!(result.is_error, Some(false));
+//! harness.shutdown().await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`HarnessClient`] controls what the client advertises and how it answers
+//! the server's own requests: elicitation answers, sampling replies and
+//! roots. Log messages and resource notifications the server sends are
+//! collected for assertions.
+
+use crate::protocol;
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, ClientCapabilities, ClientInfo, Content,
+    CreateElicitationRequestParam, CreateElicitationResult, CreateMessageRequestParam,
+    CreateMessageResult, ElicitationAction, ErrorCode, GetPromptRequestParam, GetPromptResult,
+    Implementation, ListRootsResult, LoggingMessageNotificationParam, ProtocolVersion,
+    ReadResourceRequestParam, ReadResourceResult, ResourceUpdatedNotificationParam, Role, Root,
+    SamplingMessage,
+};
+use rmcp::service::{NotificationContext, RequestContext, RunningService};
+use rmcp::{ErrorData as McpError, Peer, RoleClient, ServerHandler, ServiceError, ServiceExt};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// Buffer size of the in-memory transport
+const TRANSPORT_BUFFER: usize = 64 * 1024;
+
+/// Notifications received from the server
+#[derive(Debug, Default)]
+struct Received {
+    log_messages: Vec<LoggingMessageNotificationParam>,
+    updated_resources: Vec<String>,
+    resource_list_changes: usize,
+}
+
+/// The client side of a [`TestHarness`]
+///
+/// By default it requests [`protocol::LATEST`] and advertises no
+/// optional capabilities.
+#[derive(Debug, Clone)]
+pub struct HarnessClient {
+    protocol_version: ProtocolVersion,
+    elicitation_answers: Option<Value>,
+    sampling_reply: Option<String>,
+    roots: Option<Vec<Root>>,
+    received: Arc<Mutex<Received>>,
+}
+
+impl Default for HarnessClient {
+    fn default() -> Self {
+        Self {
+            protocol_version: protocol::LATEST,
+            elicitation_answers: None,
+            sampling_reply: None,
+            roots: None,
+            received: Arc::default(),
+        }
+    }
+}
+
+impl HarnessClient {
+    /// Create a client with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request `version` in `initialize`
+    pub fn with_protocol_version(mut self, version: ProtocolVersion) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// Advertise elicitation and accept every request with `answers`
+    pub fn with_elicitation_answers(mut self, answers: Value) -> Self {
+        self.elicitation_answers = Some(answers);
+        self
+    }
+
+    /// Advertise sampling and answer every request with `reply`
+    pub fn with_sampling_reply(mut self, reply: impl Into<String>) -> Self {
+        self.sampling_reply = Some(reply.into());
+        self
+    }
+
+    /// Advertise roots and list `roots` when asked
+    pub fn with_roots(mut self, roots: Vec<Root>) -> Self {
+        self.roots = Some(roots);
+        self
+    }
+
+    fn received(&self) -> std::sync::MutexGuard<'_, Received> {
+        self.received
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl rmcp::ClientHandler for HarnessClient {
+    async fn create_elicitation(
+        &self,
+        _request: CreateElicitationRequestParam,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<CreateElicitationResult, McpError> {
+        Ok(match &self.elicitation_answers {
+            Some(answers) => CreateElicitationResult {
+                action: ElicitationAction::Accept,
+                content: Some(answers.clone()),
+            },
+            None => CreateElicitationResult {
+                action: ElicitationAction::Decline,
+                content: None,
+            },
+        })
+    }
+
+    async fn create_message(
+        &self,
+        _params: CreateMessageRequestParam,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, McpError> {
+        let reply = self.sampling_reply.clone().ok_or_else(|| {
+            McpError::method_not_found::<rmcp::model::CreateMessageRequestMethod>()
+        })?;
+        Ok(CreateMessageResult {
+            model: "test-model".to_string(),
+            stop_reason: Some(CreateMessageResult::STOP_REASON_END_TURN.to_string()),
+            message: SamplingMessage {
+                role: Role::Assistant,
+                content: Content::text(reply),
+            },
+        })
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<ListRootsResult, McpError> {
+        Ok(ListRootsResult {
+            roots: self.roots.clone().unwrap_or_default(),
+        })
+    }
+
+    async fn on_logging_message(
+        &self,
+        params: LoggingMessageNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        self.received().log_messages.push(params);
+    }
+
+    async fn on_resource_updated(
+        &self,
+        params: ResourceUpdatedNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        self.received().updated_resources.push(params.uri);
+    }
+
+    async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        self.received().resource_list_changes += 1;
+    }
+
+    fn get_info(&self) -> ClientInfo {
+        let mut capabilities = ClientCapabilities::default();
+        if self.elicitation_answers.is_some() {
+            capabilities.elicitation = Some(Default::default());
+        }
+        if self.sampling_reply.is_some() {
+            capabilities.sampling = Some(Default::default());
+        }
+        if self.roots.is_some() {
+            capabilities.roots = Some(Default::default());
+        }
+        ClientInfo {
+            protocol_version: self.protocol_version.clone(),
+            capabilities,
+            client_info: Implementation::from_build_env(),
+        }
+    }
+}
+
+/// A server under test and a client connected to it
+///
+/// Dereferences to the client's [`Peer`], so every request rmcp supports
+/// (`list_all_tools`, `subscribe`, `set_level`, ...) is available directly.
+pub struct TestHarness {
+    client: RunningService<RoleClient, HarnessClient>,
+    received: Arc<Mutex<Received>>,
+}
+
+impl TestHarness {
+    /// Start `server` and connect a default [`HarnessClient`] to it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server doesn't complete the initialize
+    /// handshake.
+    pub async fn start<S: ServerHandler>(server: S) -> Result<Self, String> {
+        Self::start_with(server, HarnessClient::default()).await
+    }
+
+    /// Start `server` and connect `client` to it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server doesn't complete the initialize
+    /// handshake.
+    pub async fn start_with<S: ServerHandler>(
+        server: S,
+        client: HarnessClient,
+    ) -> Result<Self, String> {
+        let (server_transport, client_transport) = tokio::io::duplex(TRANSPORT_BUFFER);
+        tokio::spawn(async move {
+            match server.serve(server_transport).await {
+                Ok(running) => {
+                    let _ = running.waiting().await;
+                }
+                Err(e) => tracing::warn!("Server under test failed to start: {}", e),
+            }
+        });
+        // Clones of a client share buffers; each connection starts empty
+        let client = HarnessClient {
+            received: Arc::default(),
+            ..client
+        };
+        let received = client.received.clone();
+        let client = client
+            .serve(client_transport)
+            .await
+            .map_err(|e| format!("Failed to connect to the server: {}", e))?;
+        Ok(Self { client, received })
+    }
+
+    /// Call tool `name` with a JSON object of `arguments`
+    ///
+    /// # Errors
+    ///
+    /// Returns the server's JSON-RPC error, if any.
+    pub async fn call_tool(
+        &self,
+        name: &str,
+        arguments: Value,
+    ) -> Result<CallToolResult, ServiceError> {
+        self.client
+            .call_tool(CallToolRequestParam {
+                name: name.to_string().into(),
+                arguments: arguments.as_object().cloned(),
+            })
+            .await
+    }
+
+    /// Read the resource at `uri`
+    ///
+    /// # Errors
+    ///
+    /// Returns the server's JSON-RPC error, if any.
+    pub async fn read_resource(&self, uri: &str) -> Result<ReadResourceResult, ServiceError> {
+        self.client
+            .read_resource(ReadResourceRequestParam {
+                uri: uri.to_string(),
+            })
+            .await
+    }
+
+    /// Get prompt `name` with a JSON object of `arguments`
+    ///
+    /// # Errors
+    ///
+    /// Returns the server's JSON-RPC error, if any.
+    pub async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: Value,
+    ) -> Result<GetPromptResult, ServiceError> {
+        self.client
+            .get_prompt(GetPromptRequestParam {
+                name: name.to_string(),
+                arguments: arguments.as_object().cloned(),
+            })
+            .await
+    }
+
+    /// Log messages received so far
+    pub fn log_messages(&self) -> Vec<LoggingMessageNotificationParam> {
+        self.lock().log_messages.clone()
+    }
+
+    /// URIs of `notifications/resources/updated` received so far
+    pub fn updated_resources(&self) -> Vec<String> {
+        self.lock().updated_resources.clone()
+    }
+
+    /// Number of `notifications/resources/list_changed` received so far
+    pub fn resource_list_changes(&self) -> usize {
+        self.lock().resource_list_changes
+    }
+
+    /// Close the connection, which also stops the server
+    pub async fn shutdown(self) {
+        let _ = self.client.cancel().await;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Received> {
+        self.received
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl std::ops::Deref for TestHarness {
+    type Target = Peer<RoleClient>;
+
+    fn deref(&self) -> &Self::Target {
+        self.client.peer()
+    }
+}
+
+/// The JSON-RPC error code of a failed request, if the server sent one
+pub fn error_code(error: &ServiceError) -> Option<ErrorCode> {
+    match error {
+        ServiceError::McpError(error) => Some(error.code),
+        _ => None,
+    }
+}
@@ -0,0 +1,155 @@
+//! Streamable HTTP transport
+//!
+//! Serves MCP Forge over MCP's streamable HTTP transport so one instance can
+//! be shared by several editors. Every client gets its own session (tracked
+//! by the `Mcp-Session-Id` header) backed by a [`MCPForgeServer::for_session`]
+//! clone; sessions share templates, the workspace and resource subscriptions.
+//!
+//! [`serve`] runs until its shutdown future completes, then closes every
+//! open session so long-lived SSE streams end and in-flight requests can
+//! finish. [`shutdown_signal`] completes on SIGINT or SIGTERM.
+
+use crate::MCPForgeServer;
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService,
+    session::{SessionManager, local::LocalSessionManager},
+};
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+/// Address the HTTP transport binds to by default
+pub const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1:8080";
+
+/// Path of the MCP endpoint
+pub const MCP_ENDPOINT: &str = "/mcp";
+
+/// Time in-flight requests get to finish after shutdown was requested
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// Serve `server` over streamable HTTP on `listener` until `shutdown` completes
+///
+/// # Errors
+///
+/// Returns an error if accepting connections fails.
+pub async fn serve(
+    server: MCPForgeServer,
+    listener: TcpListener,
+    shutdown: impl Future<Output = ()> + Send,
+) -> io::Result<()> {
+    let sessions = Arc::new(LocalSessionManager::default());
+    let service = StreamableHttpService::new(
+        move || Ok(server.for_session()),
+        sessions.clone(),
+        StreamableHttpServerConfig::default(),
+    );
+    let router = axum::Router::new().nest_service(MCP_ENDPOINT, service);
+
+    let stop = CancellationToken::new();
+    let mut http = tokio::spawn({
+        let stop = stop.clone();
+        async move {
+            axum::serve(listener, router)
+                .with_graceful_shutdown(stop.cancelled_owned())
+                .await
+        }
+    });
+
+    tokio::select! {
+        result = &mut http => return result.map_err(io::Error::other)?,
+        () = shutdown => {}
+    }
+
+    tracing::info!("Shutting down HTTP server");
+    close_sessions(&sessions).await;
+    stop.cancel();
+
+    match tokio::time::timeout(SHUTDOWN_GRACE, &mut http).await {
+        Ok(result) => result.map_err(io::Error::other)?,
+        Err(_) => {
+            tracing::warn!(
+                "Connections still open after {}s, closing them",
+                SHUTDOWN_GRACE.as_secs()
+            );
+            http.abort();
+            Ok(())
+        }
+    }
+}
+
+/// Close every open session, ending its streams
+async fn close_sessions(sessions: &LocalSessionManager) {
+    let ids: Vec<_> = sessions.sessions.read().await.keys().cloned().collect();
+    for id in ids {
+        if let Err(e) = sessions.close_session(&id).await {
+            tracing::warn!("Failed to close session {}: {}", id, e);
+        }
+    }
+}
+
+/// Complete when the process receives SIGINT (Ctrl+C) or SIGTERM
+pub async fn shutdown_signal() {
+    let interrupt = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = interrupt => tracing::info!("Received SIGINT"),
+        () = terminate => tracing::info!("Received SIGTERM"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::ServiceExt;
+    use rmcp::transport::StreamableHttpClientTransport;
+
+    #[tokio::test]
+    async fn test_sessions_and_graceful_shutdown() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!("http://{}{}", listener.local_addr().unwrap(), MCP_ENDPOINT);
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(MCPForgeServer::default(), listener, async {
+            let _ = stopped.await;
+        }));
+
+        let first = ().serve(StreamableHttpClientTransport::from_uri(uri.clone())).await.unwrap();
+        let second = ().serve(StreamableHttpClientTransport::from_uri(uri)).await.unwrap();
+        assert_eq!(first.peer_info().unwrap().server_info.name, "MCP Forge");
+
+        let tools = first.list_all_tools().await.unwrap();
+        assert!(tools.iter().any(|t| t.name == "generate_project"));
+        let prompts = second.list_all_prompts().await.unwrap();
+        assert!(prompts.iter().any(|p| p.name == "validate-manifest"));
+
+        // Shut down with both sessions still open
+        stop.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(10), server)
+            .await
+            .expect("server did not shut down")
+            .unwrap()
+            .unwrap();
+    }
+}
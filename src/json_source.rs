@@ -0,0 +1,933 @@
+//! Position-tracking JSON parser
+//!
+//! Parses JSON text while recording the line and column of every value and
+//! object key, addressed by JSON pointer. Unlike `serde_json`, the parser
+//! does not stop at the first syntax error: it records the error, recovers
+//! (trailing commas, missing commas, comments, single-quoted strings,
+//! unquoted keys, unclosed brackets, ...) and keeps going, so a manifest's
+//! syntax and schema problems can be reported together in one pass.
+//!
+//! [`ParsedJson`] is also the common document model for the YAML and TOML
+//! sources, which record their positions in the original text the same way.
+
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Deepest nesting of objects and arrays the parsers accept
+///
+/// Deeper documents are cut off with a syntax error instead of exhausting
+/// the stack, as `serde_json` does at the same depth.
+pub const MAX_DEPTH: usize = 128;
+
+/// A position in the source text (1-based line and column)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Position {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number in characters, starting at 1
+    pub column: usize,
+    /// Byte offset from the start of the text
+    pub offset: usize,
+}
+
+/// A syntax error found while parsing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// Where the error was detected
+    pub position: Position,
+    /// JSON pointer of the value being parsed when the error occurred
+    pub pointer: String,
+    /// Description of the error
+    pub message: String,
+    /// How to fix the error, if known
+    pub suggestion: Option<String>,
+}
+
+/// Result of parsing JSON text
+#[derive(Debug, Clone, Default)]
+pub struct ParsedJson {
+    /// The recovered document, or `None` if no value could be parsed at all
+    pub value: Option<Value>,
+    /// Syntax errors, in source order
+    pub errors: Vec<SyntaxError>,
+    values: HashMap<String, Position>,
+    keys: HashMap<String, Position>,
+    value_ends: HashMap<String, usize>,
+    key_ends: HashMap<String, usize>,
+}
+
+impl ParsedJson {
+    /// Position of the value at `pointer`
+    pub fn value_position(&self, pointer: &str) -> Option<Position> {
+        self.values.get(pointer).copied()
+    }
+
+    /// Position of the object key naming the value at `pointer`
+    pub fn key_position(&self, pointer: &str) -> Option<Position> {
+        self.keys.get(pointer).copied()
+    }
+
+    /// Byte range of the value at `pointer` in the source text
+    pub fn value_span(&self, pointer: &str) -> Option<Range<usize>> {
+        let start = self.values.get(pointer)?.offset;
+        Some(start..*self.value_ends.get(pointer)?)
+    }
+
+    /// Byte range of the object key naming the value at `pointer`, including quotes
+    pub fn key_span(&self, pointer: &str) -> Option<Range<usize>> {
+        let start = self.keys.get(pointer)?.offset;
+        Some(start..*self.key_ends.get(pointer)?)
+    }
+
+    /// Pointers of the members of the object or array at `pointer`, in source order
+    pub fn children(&self, pointer: &str) -> Vec<&str> {
+        let prefix = format!("{}/", pointer);
+        let mut children: Vec<(&str, usize)> = self
+            .values
+            .iter()
+            .filter(|(child, _)| {
+                child
+                    .strip_prefix(&prefix)
+                    .is_some_and(|rest| !rest.contains('/'))
+            })
+            .map(|(child, position)| (child.as_str(), position.offset))
+            .collect();
+        children.sort_by_key(|(_, offset)| *offset);
+        children.into_iter().map(|(child, _)| child).collect()
+    }
+
+    /// Record the position of the value at `pointer`
+    pub(crate) fn record_value(&mut self, pointer: &str, start: Position, end: Option<usize>) {
+        self.values.insert(pointer.to_string(), start);
+        if let Some(end) = end {
+            self.value_ends.insert(pointer.to_string(), end);
+        }
+    }
+
+    /// Record the position of the object key naming the value at `pointer`
+    pub(crate) fn record_key(&mut self, pointer: &str, start: Position, end: Option<usize>) {
+        self.keys.insert(pointer.to_string(), start);
+        if let Some(end) = end {
+            self.key_ends.insert(pointer.to_string(), end);
+        }
+    }
+
+    /// Best position to report for `pointer`
+    ///
+    /// Prefers the key of an object member, then the value itself, then the
+    /// closest ancestor that exists (e.g. the enclosing object of a missing
+    /// property).
+    pub fn locate(&self, pointer: &str) -> Option<Position> {
+        let mut current = pointer;
+        loop {
+            if let Some(position) = self.key_position(current).or(self.value_position(current)) {
+                return Some(position);
+            }
+            let (parent, _) = current.rsplit_once('/')?;
+            current = parent;
+        }
+    }
+}
+
+/// Parse JSON text, recovering from syntax errors
+pub fn parse(text: &str) -> ParsedJson {
+    let mut parser = Parser {
+        bytes: text.as_bytes(),
+        text,
+        position: Position {
+            line: 1,
+            column: 1,
+            offset: 0,
+        },
+        parsed: ParsedJson::default(),
+        depth: 0,
+        truncated: false,
+    };
+
+    parser.skip_trivia("");
+    if parser.peek().is_none() {
+        parser.error("", "document is empty", Some("provide a JSON object"));
+        return parser.parsed;
+    }
+
+    let value = parser.parse_value("");
+    parser.skip_trivia("");
+    if parser.peek().is_some() {
+        parser.error(
+            "",
+            "unexpected content after the end of the document",
+            Some("remove the trailing content or wrap all values in a single object"),
+        );
+    }
+
+    parser.parsed.value = value;
+    parser.parsed
+}
+
+/// Line and column of a byte offset in `text`
+///
+/// Offsets past the end of the text, or inside a UTF-8 character, are
+/// clamped to the previous character boundary.
+pub fn position_at(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+        offset,
+    }
+}
+
+/// Finds positions in a text, scanning on from the previous lookup
+///
+/// [`position_at`] scans the text from the start on every call. Parsers
+/// that look up positions in increasing order use this instead, so a whole
+/// document costs one scan; a lookup before the previous one starts over.
+pub(crate) struct PositionCursor<'a> {
+    text: &'a str,
+    position: Position,
+    /// Characters before `position`
+    chars: usize,
+}
+
+impl<'a> PositionCursor<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            position: position_at(text, 0),
+            chars: 0,
+        }
+    }
+
+    /// Position of a byte offset, clamped like [`position_at`]
+    pub(crate) fn at_offset(&mut self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        if offset < self.position.offset {
+            *self = Self::new(self.text);
+        }
+        let text = self.text;
+        for c in text[self.position.offset..offset].chars() {
+            self.step(c);
+        }
+        self.position
+    }
+
+    /// Position of the character with index `index`
+    pub(crate) fn at_char(&mut self, index: usize) -> Position {
+        if index < self.chars {
+            *self = Self::new(self.text);
+        }
+        let text = self.text;
+        for c in text[self.position.offset..]
+            .chars()
+            .take(index - self.chars)
+        {
+            self.step(c);
+        }
+        self.position
+    }
+
+    fn step(&mut self, c: char) {
+        self.chars += 1;
+        self.position.offset += c.len_utf8();
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+    }
+}
+
+/// Append an object key to a JSON pointer, escaping `~` and `/` (RFC 6901)
+pub fn push_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"))
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    text: &'a str,
+    position: Position,
+    parsed: ParsedJson,
+    /// Objects and arrays currently open
+    depth: usize,
+    /// Whether the rest of the text was skipped for nesting too deeply
+    truncated: bool,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position.offset).copied()
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<u8> {
+        self.bytes.get(self.position.offset + ahead).copied()
+    }
+
+    fn advance(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.position.offset += 1;
+        if byte == b'\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else if byte & 0xC0 != 0x80 {
+            // Count characters, not UTF-8 continuation bytes
+            self.position.column += 1;
+        }
+        Some(byte)
+    }
+
+    fn error(&mut self, pointer: &str, message: impl Into<String>, suggestion: Option<&str>) {
+        self.error_at(self.position, pointer, message, suggestion);
+    }
+
+    fn error_at(
+        &mut self,
+        position: Position,
+        pointer: &str,
+        message: impl Into<String>,
+        suggestion: Option<&str>,
+    ) {
+        self.parsed.errors.push(SyntaxError {
+            position,
+            pointer: pointer.to_string(),
+            message: message.into(),
+            suggestion: suggestion.map(str::to_string),
+        });
+    }
+
+    /// Skip whitespace and (reporting them) comments
+    fn skip_trivia(&mut self, pointer: &str) {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(b' ' | b'\t' | b'\n' | b'\r'), _) => {
+                    self.advance();
+                }
+                (Some(b'/'), Some(b'/')) => {
+                    self.error(
+                        pointer,
+                        "comments are not allowed in JSON",
+                        Some("remove the comment"),
+                    );
+                    while !matches!(self.peek(), None | Some(b'\n')) {
+                        self.advance();
+                    }
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    self.error(
+                        pointer,
+                        "comments are not allowed in JSON",
+                        Some("remove the comment"),
+                    );
+                    self.advance();
+                    self.advance();
+                    while self.peek().is_some()
+                        && !(self.peek() == Some(b'*') && self.peek_at(1) == Some(b'/'))
+                    {
+                        self.advance();
+                    }
+                    self.advance();
+                    self.advance();
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn parse_value(&mut self, pointer: &str) -> Option<Value> {
+        self.parsed
+            .values
+            .insert(pointer.to_string(), self.position);
+        let value = self.parse_value_inner(pointer);
+        self.parsed
+            .value_ends
+            .insert(pointer.to_string(), self.position.offset);
+        value
+    }
+
+    fn parse_value_inner(&mut self, pointer: &str) -> Option<Value> {
+        match self.peek() {
+            Some(b'{' | b'[') if self.depth == MAX_DEPTH => {
+                self.error(
+                    pointer,
+                    format!("values are nested more than {} levels deep", MAX_DEPTH),
+                    Some("flatten the document"),
+                );
+                self.truncated = true;
+                self.position = position_at(self.text, self.text.len());
+                None
+            }
+            Some(b'{') => {
+                self.depth += 1;
+                let object = self.parse_object(pointer);
+                self.depth -= 1;
+                Some(object)
+            }
+            Some(b'[') => {
+                self.depth += 1;
+                let array = self.parse_array(pointer);
+                self.depth -= 1;
+                Some(array)
+            }
+            Some(quote @ (b'"' | b'\'')) => {
+                if quote == b'\'' {
+                    self.error(
+                        pointer,
+                        "strings must use double quotes",
+                        Some("replace the single quotes with double quotes"),
+                    );
+                }
+                Some(Value::String(self.parse_string(pointer, quote)))
+            }
+            Some(b'-' | b'0'..=b'9') => Some(self.parse_number(pointer)),
+            Some(c) if c.is_ascii_alphabetic() => self.parse_literal(pointer),
+            None => {
+                self.error(pointer, "unexpected end of input, expected a value", None);
+                None
+            }
+            Some(_) => {
+                let c = self.current_char();
+                self.error(
+                    pointer,
+                    format!("unexpected character '{}', expected a value", c),
+                    None,
+                );
+                self.skip_to_delimiter();
+                None
+            }
+        }
+    }
+
+    fn current_char(&self) -> char {
+        self.text[self.position.offset..]
+            .chars()
+            .next()
+            .unwrap_or('\0')
+    }
+
+    /// Skip to the next `,`, `}`, `]` or end of line after an invalid token
+    fn skip_to_delimiter(&mut self) {
+        while !matches!(self.peek(), None | Some(b',' | b'}' | b']' | b'\n')) {
+            self.advance();
+        }
+    }
+
+    /// Skip an invalid token, consuming at least its first character
+    fn skip_token(&mut self) {
+        self.advance();
+        self.skip_to_delimiter();
+    }
+
+    /// Report a `]` that closes an object and consume it
+    fn mismatched_bracket(&mut self, pointer: &str) {
+        self.error(
+            pointer,
+            "mismatched ']', expected '}'",
+            Some("replace ']' with '}'"),
+        );
+        self.advance();
+    }
+
+    fn parse_object(&mut self, pointer: &str) -> Value {
+        let open = self.position;
+        self.advance();
+        let mut map = Map::new();
+
+        loop {
+            self.skip_trivia(pointer);
+            let key_position = self.position;
+            let key = match self.peek() {
+                Some(b'}') => {
+                    self.advance();
+                    break;
+                }
+                Some(b']') => {
+                    self.mismatched_bracket(pointer);
+                    break;
+                }
+                None => {
+                    if !self.truncated {
+                        self.error_at(open, pointer, "unclosed object", Some("add a closing '}'"));
+                    }
+                    break;
+                }
+                Some(quote @ (b'"' | b'\'')) => {
+                    if quote == b'\'' {
+                        self.error(
+                            pointer,
+                            "object keys must use double quotes",
+                            Some("replace the single quotes with double quotes"),
+                        );
+                    }
+                    self.parse_string(pointer, quote)
+                }
+                Some(c) if c.is_ascii_alphabetic() || c == b'_' || c == b'$' => {
+                    let key = self.take_identifier();
+                    self.error_at(
+                        key_position,
+                        &push_pointer(pointer, &key),
+                        format!("object key '{}' must be a double-quoted string", key),
+                        Some("wrap the key in double quotes"),
+                    );
+                    key
+                }
+                Some(_) => {
+                    let c = self.current_char();
+                    self.error(
+                        pointer,
+                        format!("unexpected character '{}', expected an object key", c),
+                        None,
+                    );
+                    self.skip_token();
+                    if matches!(self.peek(), Some(b',' | b'\n')) {
+                        self.advance();
+                    }
+                    continue;
+                }
+            };
+
+            let member = push_pointer(pointer, &key);
+            self.parsed.keys.insert(member.clone(), key_position);
+            self.parsed
+                .key_ends
+                .insert(member.clone(), self.position.offset);
+
+            self.skip_trivia(&member);
+            if self.peek() == Some(b':') {
+                self.advance();
+            } else {
+                self.error(
+                    &member,
+                    format!("expected ':' after key \"{}\"", key),
+                    Some("add a ':'"),
+                );
+            }
+
+            self.skip_trivia(&member);
+            if matches!(self.peek(), Some(b',' | b'}')) {
+                self.error(&member, format!("missing value for key \"{}\"", key), None);
+            } else if let Some(value) = self.parse_value(&member) {
+                if map.contains_key(&key) {
+                    self.error_at(
+                        key_position,
+                        &member,
+                        format!("duplicate key \"{}\"", key),
+                        Some("remove one of the duplicate entries"),
+                    );
+                }
+                map.insert(key, value);
+            }
+
+            self.skip_trivia(pointer);
+            match self.peek() {
+                Some(b',') => {
+                    let comma = self.position;
+                    self.advance();
+                    self.skip_trivia(pointer);
+                    if self.peek() == Some(b'}') {
+                        self.error_at(
+                            comma,
+                            pointer,
+                            "trailing comma in object",
+                            Some("remove the trailing ','"),
+                        );
+                    }
+                }
+                Some(b'}') => {}
+                None => {}
+                Some(b']') => {
+                    self.mismatched_bracket(pointer);
+                    break;
+                }
+                Some(b'"' | b'\'') => {
+                    self.error(
+                        pointer,
+                        "missing ',' between object members",
+                        Some("add a ','"),
+                    );
+                }
+                Some(_) => {
+                    let c = self.current_char();
+                    self.error(
+                        pointer,
+                        format!("unexpected character '{}', expected ',' or '}}'", c),
+                        None,
+                    );
+                    self.skip_token();
+                    if self.peek() == Some(b',') {
+                        self.advance();
+                    }
+                }
+            }
+        }
+
+        Value::Object(map)
+    }
+
+    fn parse_array(&mut self, pointer: &str) -> Value {
+        let open = self.position;
+        self.advance();
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_trivia(pointer);
+            match self.peek() {
+                Some(b']') => {
+                    self.advance();
+                    break;
+                }
+                None => {
+                    if !self.truncated {
+                        self.error_at(open, pointer, "unclosed array", Some("add a closing ']'"));
+                    }
+                    break;
+                }
+                _ => {}
+            }
+
+            let item = format!("{}/{}", pointer, items.len());
+            if let Some(value) = self.parse_value(&item) {
+                items.push(value);
+            } else if self.peek() == Some(b'\n') {
+                self.advance();
+            }
+
+            self.skip_trivia(pointer);
+            match self.peek() {
+                Some(b',') => {
+                    let comma = self.position;
+                    self.advance();
+                    self.skip_trivia(pointer);
+                    if self.peek() == Some(b']') {
+                        self.error_at(
+                            comma,
+                            pointer,
+                            "trailing comma in array",
+                            Some("remove the trailing ','"),
+                        );
+                    }
+                }
+                Some(b']') | None => {}
+                Some(b'}') => {
+                    self.error(
+                        pointer,
+                        "mismatched '}', expected ']'",
+                        Some("replace '}' with ']'"),
+                    );
+                    self.advance();
+                    break;
+                }
+                Some(_) => {
+                    self.error(
+                        pointer,
+                        "missing ',' between array items",
+                        Some("add a ','"),
+                    );
+                }
+            }
+        }
+
+        Value::Array(items)
+    }
+
+    fn parse_string(&mut self, pointer: &str, quote: u8) -> String {
+        let open = self.position;
+        self.advance();
+        let mut value = String::new();
+
+        loop {
+            match self.peek() {
+                None | Some(b'\n') => {
+                    self.error_at(
+                        open,
+                        pointer,
+                        "unterminated string",
+                        Some("add the closing quote"),
+                    );
+                    return value;
+                }
+                Some(c) if c == quote => {
+                    self.advance();
+                    return value;
+                }
+                Some(b'\\') => {
+                    self.advance();
+                    let escape = self.position;
+                    match self.advance() {
+                        Some(b'"') => value.push('"'),
+                        Some(b'\'') => value.push('\''),
+                        Some(b'\\') => value.push('\\'),
+                        Some(b'/') => value.push('/'),
+                        Some(b'b') => value.push('\u{08}'),
+                        Some(b'f') => value.push('\u{0C}'),
+                        Some(b'n') => value.push('\n'),
+                        Some(b'r') => value.push('\r'),
+                        Some(b't') => value.push('\t'),
+                        Some(b'u') => {
+                            let hex: String = (0..4)
+                                .filter_map(|_| match self.peek() {
+                                    Some(c) if c.is_ascii_hexdigit() => {
+                                        self.advance().map(char::from)
+                                    }
+                                    _ => None,
+                                })
+                                .collect();
+                            match u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .filter(|_| hex.len() == 4)
+                                .and_then(char::from_u32)
+                            {
+                                Some(c) => value.push(c),
+                                None => self.error_at(
+                                    escape,
+                                    pointer,
+                                    "invalid unicode escape",
+                                    Some("use \\uXXXX with four hex digits"),
+                                ),
+                            }
+                        }
+                        _ => self.error_at(
+                            escape,
+                            pointer,
+                            "invalid escape sequence",
+                            Some("escape backslashes as \\\\"),
+                        ),
+                    }
+                }
+                Some(c) if c < 0x20 => {
+                    self.error(
+                        pointer,
+                        "control characters must be escaped in strings",
+                        None,
+                    );
+                    self.advance();
+                }
+                Some(_) => {
+                    let c = self.current_char();
+                    for _ in 0..c.len_utf8() {
+                        self.advance();
+                    }
+                    value.push(c);
+                }
+            }
+        }
+    }
+
+    fn parse_number(&mut self, pointer: &str) -> Value {
+        let start = self.position;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.advance();
+        }
+        let raw = &self.text[start.offset..self.position.offset];
+
+        match serde_json::from_str::<Number>(raw) {
+            Ok(number) => Value::Number(number),
+            Err(_) => {
+                self.error_at(start, pointer, format!("invalid number '{}'", raw), None);
+                Value::Null
+            }
+        }
+    }
+
+    fn take_identifier(&mut self) -> String {
+        let start = self.position.offset;
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == b'_' || c == b'$' || c == b'-')
+        {
+            self.advance();
+        }
+        self.text[start..self.position.offset].to_string()
+    }
+
+    fn parse_literal(&mut self, pointer: &str) -> Option<Value> {
+        let start = self.position;
+        let word = self.take_identifier();
+        let (value, suggestion) = match word.as_str() {
+            "true" => return Some(Value::Bool(true)),
+            "false" => return Some(Value::Bool(false)),
+            "null" => return Some(Value::Null),
+            "True" => (Value::Bool(true), "use lowercase 'true'"),
+            "False" => (Value::Bool(false), "use lowercase 'false'"),
+            "None" | "Null" | "NULL" | "undefined" | "nil" => (Value::Null, "use 'null'"),
+            _ => {
+                self.error_at(
+                    start,
+                    pointer,
+                    format!("unexpected token '{}', expected a value", word),
+                    Some("wrap text values in double quotes"),
+                );
+                return Some(Value::String(word));
+            }
+        };
+        self.error_at(
+            start,
+            pointer,
+            format!("'{}' is not a JSON literal", word),
+            Some(suggestion),
+        );
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_valid_json_with_positions() {
+        let text = "{\n  \"mcpServers\": {\n    \"forge\": {\"command\": \"mcp-forge\"}\n  }\n}";
+        let parsed = parse(text);
+        assert!(parsed.errors.is_empty());
+        assert_eq!(
+            parsed.value,
+            Some(json!({"mcpServers": {"forge": {"command": "mcp-forge"}}}))
+        );
+
+        let key = parsed.key_position("/mcpServers/forge/command").unwrap();
+        assert_eq!((key.line, key.column), (3, 15));
+        let value = parsed.value_position("/mcpServers/forge/command").unwrap();
+        assert_eq!((value.line, value.column), (3, 26));
+    }
+
+    #[test]
+    fn test_locate_missing_pointer_falls_back_to_parent() {
+        let parsed = parse("{\"mcpServers\": {\"forge\": {}}}");
+        let position = parsed.locate("/mcpServers/forge/command").unwrap();
+        assert_eq!((position.line, position.column), (1, 17));
+    }
+
+    #[test]
+    fn test_recovers_from_multiple_errors() {
+        let text = "{\n  // comment\n  'name': \"forge\",\n  version: \"1.0.0\"\n  \"tags\": [\"a\", \"b\",],\n}";
+        let parsed = parse(text);
+        assert_eq!(
+            parsed.value,
+            Some(json!({"name": "forge", "version": "1.0.0", "tags": ["a", "b"]}))
+        );
+
+        let messages: Vec<&str> = parsed.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "comments are not allowed in JSON",
+                "object keys must use double quotes",
+                "object key 'version' must be a double-quoted string",
+                "missing ',' between object members",
+                "trailing comma in array",
+                "trailing comma in object",
+            ]
+        );
+        assert_eq!(parsed.errors[3].position.line, 5);
+    }
+
+    #[test]
+    fn test_unclosed_and_invalid_values() {
+        let parsed = parse("{\"a\": True, \"b\": [1, 2");
+        assert_eq!(parsed.value, Some(json!({"a": true, "b": [1, 2]})));
+        let messages: Vec<&str> = parsed.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "'True' is not a JSON literal",
+                "unclosed array",
+                "unclosed object"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mismatched_closers() {
+        let parsed = parse("{\"a\": 1]");
+        assert_eq!(parsed.value, Some(json!({"a": 1})));
+        assert_eq!(parsed.errors.len(), 1, "{:?}", parsed.errors);
+        assert_eq!(parsed.errors[0].message, "mismatched ']', expected '}'");
+        assert_eq!(parsed.errors[0].position.column, 8);
+
+        let parsed = parse("{]");
+        assert_eq!(parsed.value, Some(json!({})));
+        assert_eq!(parsed.errors[0].message, "mismatched ']', expected '}'");
+
+        let parsed = parse("{\"a\": [1}, \"b\": 2}");
+        assert_eq!(parsed.errors[0].message, "mismatched '}', expected ']'");
+    }
+
+    #[test]
+    fn test_not_json_at_all() {
+        let parsed = parse("not valid json");
+        assert!(!parsed.errors.is_empty());
+
+        let parsed = parse("   ");
+        assert!(parsed.value.is_none());
+        assert_eq!(parsed.errors[0].message, "document is empty");
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let deep = format!("{}1{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        let parsed = parse(&deep);
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+
+        let parsed = parse(&"[".repeat(100_000));
+        assert_eq!(parsed.errors.len(), 1, "{:?}", parsed.errors);
+        assert_eq!(
+            parsed.errors[0].message,
+            format!("values are nested more than {} levels deep", MAX_DEPTH)
+        );
+        assert!(parse(&"{\"a\":".repeat(100_000)).value.is_some());
+    }
+
+    #[test]
+    fn test_unicode_columns() {
+        let parsed = parse("{\"név\": \"é\", \"x\": 1}");
+        let key = parsed.key_position("/x").unwrap();
+        assert_eq!(key.column, 14);
+    }
+
+    #[test]
+    fn test_position_at() {
+        let text = "{\n  \"é\": 1\n}";
+        let position = position_at(text, text.find('1').unwrap());
+        assert_eq!((position.line, position.column), (2, 8));
+        assert_eq!(position_at(text, 1000).line, 3);
+    }
+
+    #[test]
+    fn test_position_cursor() {
+        let text = "{\n  \"n\u{e9}me\": 1,\n  \"x\": [true]\n}";
+        let mut cursor = PositionCursor::new(text);
+        for offset in [0, 3, 9, 12, 20, 21, 5, text.len(), 1000] {
+            assert_eq!(
+                cursor.at_offset(offset),
+                position_at(text, offset),
+                "{}",
+                offset
+            );
+        }
+        let offsets: Vec<usize> = text.char_indices().map(|(offset, _)| offset).collect();
+        for (index, offset) in offsets.into_iter().enumerate().rev() {
+            assert_eq!(cursor.at_char(index), position_at(text, offset));
+        }
+    }
+
+    #[test]
+    fn test_spans_and_children() {
+        let text = "{\"b\": [1, 2], \"a\": \"x\"}";
+        let parsed = parse(text);
+        assert_eq!(&text[parsed.key_span("/a").unwrap()], "\"a\"");
+        assert_eq!(&text[parsed.value_span("/b").unwrap()], "[1, 2]");
+        assert_eq!(&text[parsed.value_span("").unwrap()], text);
+        assert_eq!(parsed.children(""), vec!["/b", "/a"]);
+        assert_eq!(parsed.children("/b"), vec!["/b/0", "/b/1"]);
+    }
+}
@@ -5,17 +5,57 @@
 //!
 //! # Modules
 //!
+//! - [`autofix`] - Automatic fixes for manifest validation problems
+//! - [`check`] - `cargo check` runs on generated projects
+//! - [`config`] - Layered configuration files, environment and flags
+//! - [`conformance`] - Protocol conformance checks for any stdio server
+//! - [`drafting`] - Sampling-assisted tool implementation drafts
+//! - [`elicitation`] - Asking the user for missing tool arguments
+//! - [`fuzz`] - Schema-driven fuzzing of MCP tool inputs
+//! - [`harness`] - In-process test client for MCP servers
+//! - [`http`] - Streamable HTTP transport with per-client sessions
+//! - [`json_source`] - Position-tracking, error-recovering JSON parser
+//! - [`logging`] - Tracing events forwarded to clients as MCP log messages
+//! - [`manifest`] - Manifest validation against bundled JSON Schemas
 //! - [`prompts`] - Reusable prompt templates for Claude integration
+//! - [`protocol`] - Protocol version negotiation and per-version features
+//! - [`recording`] - Recording MCP sessions through a proxy and replaying them
 //! - [`resources`] - Documentation and code templates as resources
 //! - [`server`] - Main MCP server implementation
+//! - [`subscriptions`] - Resource subscriptions and change notifications
+//! - [`toml_source`] - Position-tracking TOML parser
 //! - [`tool_executor`] - Tool execution logic and handlers
 //! - [`tools`] - Tool definitions and metadata
+//! - [`verify`] - Manifest verification against the servers it launches
+//! - [`watcher`] - Polling watcher for on-disk resources
+//! - [`yaml_source`] - Position-tracking YAML parser
+//! - [`workspace`] - Workspace sandbox and `forge://workspace/...` resources
 
+pub mod autofix;
+pub mod check;
+pub mod config;
+pub mod conformance;
+pub mod drafting;
+pub mod elicitation;
+pub mod fuzz;
+pub mod harness;
+pub mod http;
+pub mod json_source;
+pub mod logging;
+pub mod manifest;
 pub mod prompts;
+pub mod protocol;
+pub mod recording;
 pub mod resources;
 pub mod server;
+pub mod subscriptions;
+pub mod toml_source;
 pub mod tool_executor;
 pub mod tools;
+pub mod verify;
+pub mod watcher;
+pub mod workspace;
+pub mod yaml_source;
 
 pub use server::MCPForgeServer;
 
@@ -0,0 +1,404 @@
+//! MCP logging
+//!
+//! Forwards `tracing` events to connected clients as
+//! `notifications/message`, so an assistant can see why a generation failed.
+//! Clients opt in with `logging/setLevel`; each session then receives the
+//! events at or above its level. The [`ClientLogLayer`] is installed in the
+//! process's tracing subscriber next to the stderr output, and every session
+//! registers with the process-wide [`ClientLoggers`] returned by
+//! [`client_loggers`].
+//!
+//! An event only goes to the session it was produced for: the server runs
+//! each request in a span with a [`SESSION_FIELD`] field, and events outside
+//! such a span are not forwarded at all. With several HTTP sessions in one
+//! process, no client sees another's project names or paths.
+//!
+//! Events from the MCP transport itself (`rmcp`) are never forwarded, since
+//! sending a notification would log again.
+
+use crate::subscriptions::SubscriberId;
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::{RoleServer, service::Peer};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Span field holding the [`SubscriberId`] of the session a request is
+/// handled for
+pub const SESSION_FIELD: &str = "mcp_session";
+
+/// Targets whose events are never sent to clients
+const EXCLUDED_TARGETS: &[&str] = &["rmcp", "mcp_forge::logging"];
+
+/// Rank of a logging level, lowest for `debug`
+fn rank(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+/// MCP logging level of a tracing level (`trace` maps to `debug`)
+pub fn logging_level(level: &Level) -> LoggingLevel {
+    match *level {
+        Level::ERROR => LoggingLevel::Error,
+        Level::WARN => LoggingLevel::Warning,
+        Level::INFO => LoggingLevel::Info,
+        Level::DEBUG | Level::TRACE => LoggingLevel::Debug,
+    }
+}
+
+/// A client that asked for log messages
+#[derive(Debug, Clone)]
+struct ClientLogger {
+    peer: Peer<RoleServer>,
+    level: LoggingLevel,
+}
+
+/// Registry of the clients that set a logging level
+#[derive(Debug, Clone, Default)]
+pub struct ClientLoggers {
+    clients: Arc<Mutex<HashMap<SubscriberId, ClientLogger>>>,
+}
+
+impl ClientLoggers {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send a client the log messages at or above `level`
+    pub fn set_level(&self, id: SubscriberId, peer: Peer<RoleServer>, level: LoggingLevel) {
+        self.lock().insert(id, ClientLogger { peer, level });
+    }
+
+    /// The client of session `id` if it wants a message at `level`,
+    /// dropping disconnected clients
+    fn recipient(&self, id: SubscriberId, level: LoggingLevel) -> Option<Peer<RoleServer>> {
+        let mut clients = self.lock();
+        clients.retain(|_, client| !client.peer.is_transport_closed());
+        clients
+            .get(&id)
+            .filter(|client| rank(level) >= rank(client.level))
+            .map(|client| client.peer.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<SubscriberId, ClientLogger>> {
+        self.clients.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The process-wide registry used by every server session
+pub fn client_loggers() -> &'static ClientLoggers {
+    static LOGGERS: OnceLock<ClientLoggers> = OnceLock::new();
+    LOGGERS.get_or_init(ClientLoggers::new)
+}
+
+/// Tracing layer forwarding events to the clients in a [`ClientLoggers`]
+#[derive(Debug, Clone)]
+pub struct ClientLogLayer {
+    loggers: ClientLoggers,
+}
+
+impl ClientLogLayer {
+    /// Forward events to the clients registered in `loggers`
+    pub fn new(loggers: ClientLoggers) -> Self {
+        Self { loggers }
+    }
+}
+
+/// Layer forwarding events to the sessions of this process
+pub fn layer() -> ClientLogLayer {
+    ClientLogLayer::new(client_loggers().clone())
+}
+
+/// Session of a span, stored in its extensions
+struct SessionOf(SubscriberId);
+
+impl<S> Layer<S> for ClientLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut session = SessionVisitor(None);
+        attrs.record(&mut session);
+        if let Some(session) = session.0
+            && let Some(span) = ctx.span(id)
+        {
+            span.extensions_mut().insert(SessionOf(session));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if EXCLUDED_TARGETS
+            .iter()
+            .any(|target| metadata.target().starts_with(target))
+        {
+            return;
+        }
+
+        // The innermost span naming a session decides who gets the event
+        let Some(session) = ctx.event_scope(event).and_then(|mut scope| {
+            scope.find_map(|span| {
+                span.extensions()
+                    .get::<SessionOf>()
+                    .map(|session| session.0)
+            })
+        }) else {
+            return;
+        };
+        let level = logging_level(metadata.level());
+        let Some(peer) = self.loggers.recipient(session, level) else {
+            return;
+        };
+        // Notifications are sent asynchronously; without a runtime there is
+        // no connected client either
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+        let params = LoggingMessageNotificationParam {
+            level,
+            logger: Some(metadata.target().to_string()),
+            data: Value::Object(fields.0),
+        };
+
+        runtime.spawn(async move {
+            let _ = peer.notify_logging_message(params).await;
+        });
+    }
+}
+
+/// Reads the [`SESSION_FIELD`] of a new span
+struct SessionVisitor(Option<SubscriberId>);
+
+impl Visit for SessionVisitor {
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == SESSION_FIELD {
+            self.0 = Some(value);
+        }
+    }
+}
+
+/// Collects the message and fields of an event into a JSON object
+#[derive(Default)]
+struct FieldVisitor(Map<String, Value>);
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(
+            field.name().to_string(),
+            Value::from(format!("{:?}", value)),
+        );
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MCPForgeServer, http};
+    use rmcp::model::{CallToolRequestParam, SetLevelRequestParam};
+    use rmcp::service::NotificationContext;
+    use rmcp::transport::StreamableHttpClientTransport;
+    use rmcp::{ClientHandler, RoleClient, ServiceExt};
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Client collecting the log messages it receives
+    #[derive(Clone)]
+    struct LogCollector(mpsc::UnboundedSender<LoggingMessageNotificationParam>);
+
+    impl ClientHandler for LogCollector {
+        async fn on_logging_message(
+            &self,
+            params: LoggingMessageNotificationParam,
+            _context: NotificationContext<RoleClient>,
+        ) {
+            let _ = self.0.send(params);
+        }
+    }
+
+    #[test]
+    fn test_level_mapping() {
+        assert_eq!(logging_level(&Level::TRACE), LoggingLevel::Debug);
+        assert_eq!(logging_level(&Level::WARN), LoggingLevel::Warning);
+        assert!(rank(LoggingLevel::Error) > rank(LoggingLevel::Warning));
+    }
+
+    #[tokio::test]
+    async fn test_events_forwarded_at_client_level() {
+        let loggers = ClientLoggers::new();
+        let subscriber = tracing_subscriber::registry().with(ClientLogLayer::new(loggers.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        let server = MCPForgeServer::default().with_client_loggers(loggers.clone());
+        let session = server.session_id();
+        tokio::spawn(async move {
+            let service = server.serve(server_io).await?;
+            service.waiting().await?;
+            anyhow::Ok(())
+        });
+
+        let (sender, mut messages) = mpsc::unbounded_channel();
+        let client = LogCollector(sender).serve(client_io).await.unwrap();
+        assert!(client.peer_info().unwrap().capabilities.logging.is_some());
+        client
+            .set_level(SetLevelRequestParam {
+                level: LoggingLevel::Warning,
+            })
+            .await
+            .unwrap();
+
+        // Outside a session nobody gets the event
+        tracing::warn!(target: "mcp_forge::tool_executor", "No session");
+        tracing::info_span!("request", mcp_session = session).in_scope(|| {
+            tracing::info!(target: "mcp_forge::tool_executor", "Generating project");
+            tracing::warn!(target: "mcp_forge::tool_executor", project = "demo", "Template missing");
+        });
+
+        let message = tokio::time::timeout(Duration::from_secs(5), messages.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.level, LoggingLevel::Warning);
+        assert_eq!(message.logger.as_deref(), Some("mcp_forge::tool_executor"));
+        assert_eq!(
+            message.data,
+            serde_json::json!({"message": "Template missing", "project": "demo"})
+        );
+        assert!(messages.try_recv().is_err());
+
+        client.cancel().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_http_sessions_only_get_their_own_events() {
+        let loggers = ClientLoggers::new();
+        let subscriber = tracing_subscriber::registry().with(ClientLogLayer::new(loggers.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!(
+            "http://{}{}",
+            listener.local_addr().unwrap(),
+            http::MCP_ENDPOINT
+        );
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = MCPForgeServer::default().with_client_loggers(loggers);
+        let server = tokio::spawn(http::serve(server, listener, async {
+            let _ = stopped.await;
+        }));
+
+        let connect = |sender| {
+            LogCollector(sender).serve(StreamableHttpClientTransport::from_uri(uri.clone()))
+        };
+        let (tool_sender, mut tool_messages) = mpsc::unbounded_channel();
+        let (resource_sender, mut resource_messages) = mpsc::unbounded_channel();
+        let tool_client = connect(tool_sender).await.unwrap();
+        let resource_client = connect(resource_sender).await.unwrap();
+        for client in [&tool_client, &resource_client] {
+            client
+                .set_level(SetLevelRequestParam {
+                    level: LoggingLevel::Debug,
+                })
+                .await
+                .unwrap();
+        }
+
+        tool_client
+            .call_tool(CallToolRequestParam {
+                name: "generate_tool".into(),
+                arguments: serde_json::json!({"tool_name": "secret_tool", "description": "Hidden"})
+                    .as_object()
+                    .cloned(),
+            })
+            .await
+            .unwrap();
+        resource_client
+            .call_tool(CallToolRequestParam {
+                name: "generate_resource".into(),
+                arguments: serde_json::json!({"resource_name": "secret_data", "type": "json"})
+                    .as_object()
+                    .cloned(),
+            })
+            .await
+            .unwrap();
+
+        // Wait for the messages of both calls, then check who got what
+        let received = |messages: &mut mpsc::UnboundedReceiver<LoggingMessageNotificationParam>| {
+            let mut texts = Vec::new();
+            while let Ok(message) = messages.try_recv() {
+                texts.push(message.data.to_string());
+            }
+            texts
+        };
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let tool_texts = received(&mut tool_messages);
+        let resource_texts = received(&mut resource_messages);
+        assert!(
+            tool_texts.iter().any(|text| text.contains("secret_tool")),
+            "{:?}",
+            tool_texts
+        );
+        assert!(
+            resource_texts
+                .iter()
+                .any(|text| text.contains("secret_data")),
+            "{:?}",
+            resource_texts
+        );
+        assert!(
+            tool_texts.iter().all(|text| !text.contains("secret_data")),
+            "{:?}",
+            tool_texts
+        );
+        assert!(
+            resource_texts
+                .iter()
+                .all(|text| !text.contains("secret_tool")),
+            "{:?}",
+            resource_texts
+        );
+
+        tool_client.cancel().await.unwrap();
+        resource_client.cancel().await.unwrap();
+        stop.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+}
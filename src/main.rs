@@ -1,59 +1,710 @@
-use anyhow::Result;
-use mcp_forge::MCPForgeServer;
+use anyhow::{Context, Result, bail};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use mcp_forge::config::{ConfigLayer, ForgeConfig};
+use mcp_forge::{
+    MCPForgeServer, fuzz, http, logging, prompts, recording, resources, tool_executor, tools,
+};
 use rmcp::ServiceExt;
+use serde_json::json;
 use std::env;
-use tracing_subscriber::EnvFilter;
+use std::fs;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// MCP server development framework using Rust SDK
+///
+/// Without a subcommand, serves MCP over stdio (same as `mcp-forge serve`).
+///
+/// Defaults are read from ~/.config/mcp-forge/config.toml, the nearest
+/// .mcp-forge.toml and MCP_FORGE_* environment variables; flags override them.
+#[derive(Debug, Parser)]
+#[command(name = "mcp-forge", version, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    #[command(flatten)]
+    serve: ServeArgs,
+}
+
+/// Flags overriding the configuration files and environment
+#[derive(Debug, Clone, Default, Args)]
+struct ConfigArgs {
+    /// Author of generated projects
+    #[arg(long, global = true)]
+    author: Option<String>,
+    /// License of generated projects [default: MIT]
+    #[arg(long, global = true)]
+    license: Option<String>,
+    /// rmcp version requirement of generated projects [default: 0.8]
+    #[arg(long, global = true)]
+    rmcp_version: Option<String>,
+    /// Directory projects are generated into [default: .]
+    #[arg(long, global = true)]
+    workspace: Option<PathBuf>,
+    /// Directory of `<name>.template` files overriding the built-in templates
+    #[arg(long, global = true)]
+    template_dir: Option<PathBuf>,
+    /// Log level (error, warn, info, debug, trace or off)
+    #[arg(long, global = true)]
+    log_level: Option<String>,
+}
+
+impl ConfigArgs {
+    /// The configuration layer set by the flags
+    fn layer(&self) -> ConfigLayer {
+        ConfigLayer {
+            author: self.author.clone(),
+            license: self.license.clone(),
+            rmcp_version: self.rmcp_version.clone(),
+            workspace_root: self.workspace.clone(),
+            template_dir: self.template_dir.clone(),
+            log_level: self.log_level.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the MCP server
+    Serve(ServeArgs),
+    /// Generate a new MCP server project
+    New {
+        /// Project name (also the directory name)
+        name: String,
+        /// Project description
+        #[arg(long, short)]
+        description: Option<String>,
+        /// Run `cargo check --offline` on the generated project
+        #[arg(long)]
+        verify: bool,
+        /// Newest MCP protocol version the generated server implements
+        #[arg(long)]
+        protocol_version: Option<String>,
+    },
+    /// Generate the code for a new tool
+    AddTool {
+        /// Tool name
+        name: String,
+        /// Tool description
+        #[arg(long, short)]
+        description: String,
+        /// Write the code to this file instead of stdout (must not exist)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Validate a manifest file (exits with status 1 if it is invalid)
+    Validate(ValidateArgs),
+    /// Run the conformance suite against a stdio MCP server (exits with
+    /// status 1 if a check fails)
+    Test(TestArgs),
+    /// Fuzz the tools of a stdio MCP server with generated arguments (exits
+    /// with status 1 if a case fails)
+    Fuzz(FuzzArgs),
+    /// Run a stdio MCP server behind a proxy that records every message
+    Record(RecordArgs),
+    /// Replay a recorded session against a stdio MCP server and compare the
+    /// responses (exits with status 1 if any differ)
+    Replay(ReplayArgs),
+    /// List or print code templates
+    Templates {
+        #[command(subcommand)]
+        command: TemplatesCommand,
+    },
+    /// Print server information
+    Info,
+}
+
+#[derive(Debug, Clone, Args)]
+struct ServeArgs {
+    /// Transport to serve MCP over
+    #[arg(long, value_enum, default_value_t = TransportKind::Stdio)]
+    transport: TransportKind,
+    /// Address to listen on with `--transport http` [default: 127.0.0.1:8080]
+    #[arg(long)]
+    bind: Option<SocketAddr>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TransportKind {
+    /// stdin/stdout, for a single client that launches the server
+    Stdio,
+    /// Streamable HTTP, shared by several clients
+    Http,
+}
+
+#[derive(Debug, Args)]
+struct ValidateArgs {
+    /// Manifest file (JSON, YAML or TOML)
+    file: PathBuf,
+    /// Manifest format (defaults to auto-detection)
+    #[arg(long, default_value = "auto")]
+    format: String,
+    /// Manifest syntax (defaults to the file extension or content)
+    #[arg(long, default_value = "auto")]
+    syntax: String,
+    /// Apply automatic fixes and rewrite the file
+    #[arg(long)]
+    fix: bool,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Args)]
+struct TestArgs {
+    /// Executable that starts the server
+    command: String,
+    /// Arguments passed to the server
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+    /// Seconds the server gets for each check
+    #[arg(long, default_value_t = 10)]
+    timeout: u64,
+    /// Also write the report as JUnit XML to this file
+    #[arg(long)]
+    junit: Option<PathBuf>,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Args)]
+struct FuzzArgs {
+    /// Executable that starts the server
+    command: String,
+    /// Arguments passed to the server
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+    /// Seconds the server gets for each call
+    #[arg(long, default_value_t = 5)]
+    timeout: u64,
+    /// Random cases generated per tool, on top of the boundary cases
+    #[arg(long, default_value_t = fuzz::DEFAULT_RANDOM_CASES)]
+    cases: usize,
+    /// Seed for the random cases, to reproduce a run [default: random]
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Only fuzz this tool; repeatable
+    #[arg(long = "tool")]
+    tools: Vec<String>,
+    /// Fix an argument to a value, as `NAME=JSON` (plain text is taken as a
+    /// string); repeatable
+    #[arg(long = "fix", value_name = "NAME=JSON", value_parser = parse_fixed)]
+    fixed: Vec<(String, serde_json::Value)>,
+    /// Directory the server runs in [default: a temporary directory]
+    #[arg(long)]
+    workdir: Option<PathBuf>,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// Parse a `--fix NAME=JSON` argument
+fn parse_fixed(arg: &str) -> Result<(String, serde_json::Value), String> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=JSON, got {:?}", arg))?;
+    if name.is_empty() {
+        return Err(format!("missing argument name in {:?}", arg));
+    }
+    let value = serde_json::from_str(value)
+        .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+    Ok((name.to_string(), value))
+}
+
+#[derive(Debug, Args)]
+struct RecordArgs {
+    /// JSONL file to write the recording to
+    #[arg(long, short)]
+    output: PathBuf,
+    /// Executable that starts the server
+    command: String,
+    /// Arguments passed to the server
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+struct ReplayArgs {
+    /// JSONL recording to replay
+    recording: PathBuf,
+    /// Field to leave out of the comparison: a JSON pointer (`*` matches
+    /// any segment) or a key name ignored at any depth; repeatable
+    #[arg(long, default_values_t = recording::DEFAULT_IGNORE.map(String::from))]
+    ignore: Vec<String>,
+    /// Seconds the server gets to answer each request
+    #[arg(long, default_value_t = 10)]
+    timeout: u64,
+    /// Overwrite the recording with the replayed session
+    #[arg(long)]
+    update: bool,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+    /// Executable that starts the server
+    command: String,
+    /// Arguments passed to the server
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum TemplatesCommand {
+    /// List the available templates
+    List,
+    /// Print a template
+    Show {
+        /// Template name, e.g. `main.rs` or `Cargo.toml`
+        name: String,
+    },
+}
+
+/// Transport the server is reachable over
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Transport {
+    Stdio,
+    Http(SocketAddr),
+}
+
+impl ServeArgs {
+    /// Resolve the transport and bind address
+    fn transport(&self) -> Result<Transport> {
+        match (self.transport, self.bind) {
+            (TransportKind::Stdio, Some(_)) => bail!("--bind requires --transport http"),
+            (TransportKind::Stdio, None) => Ok(Transport::Stdio),
+            (TransportKind::Http, Some(addr)) => Ok(Transport::Http(addr)),
+            (TransportKind::Http, None) => Ok(Transport::Http(
+                http::DEFAULT_BIND_ADDRESS
+                    .parse()
+                    .expect("default bind address is valid"),
+            )),
+        }
+    }
+}
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging (write to stderr so stdout is clean for MCP messages)
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into()))
-        .with_writer(std::io::stderr)
-        .with_ansi(false)
+async fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+
+    let mut config = ForgeConfig::load(&env::current_dir()?)?;
+    config.apply(cli.config.layer())?;
+
+    // Keep the shell commands quiet unless configured or RUST_LOG asks for more
+    let level = match (&config.log_level, &cli.command) {
+        (Some(level), _) => level.parse()?,
+        (None, None | Some(Command::Serve(_))) => LevelFilter::INFO,
+        (None, Some(_)) => LevelFilter::WARN,
+    };
+
+    // Initialize logging (write to stderr so stdout is clean for MCP messages);
+    // clients that set a log level receive events down to debug regardless
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_ansi(false)
+                .with_filter(EnvFilter::from_default_env().add_directive(level.into())),
+        )
+        .with(logging::layer().with_filter(LevelFilter::DEBUG))
         .init();
 
-    // Check for debug mode via environment variable
-    let debug_mode = env::var("MCP_FORGE_DEBUG").is_ok();
+    match cli.command {
+        // MCP_FORGE_DEBUG predates the `info` subcommand and still prints the banner
+        None if env::var("MCP_FORGE_DEBUG").is_ok() => print_server_info(&config),
+        None => run_mcp_server(cli.serve, config).await?,
+        Some(Command::Serve(args)) => run_mcp_server(args, config).await?,
+        Some(Command::New {
+            name,
+            description,
+            verify,
+            protocol_version,
+        }) => {
+            let mut args = json!({
+                "project_name": name,
+                "verify": verify,
+                "protocol_version": protocol_version,
+            });
+            if let Some(description) = description {
+                args["description"] = json!(description);
+            }
+            let project = tool_executor::generate_project(
+                &args,
+                &config,
+                &tool_executor::ExecutionContext::default(),
+            )
+            .await
+            .map_err(anyhow::Error::msg)?;
+            match &project.check {
+                Some(check) if !check.success => bail!(project.summary()),
+                _ => println!("{}", project.summary()),
+            }
+        }
+        Some(Command::AddTool {
+            name,
+            description,
+            output,
+        }) => {
+            let args = json!({"tool_name": name, "description": description});
+            let code = run_tool("generate_tool", &args).await?;
+            match output {
+                Some(path) => {
+                    write_new_file(&path, &code)?;
+                    println!("Tool '{}' written to {}", name, path.display());
+                }
+                None => print!("{}", code),
+            }
+        }
+        Some(Command::Validate(args)) => return validate(&args),
+        Some(Command::Test(args)) => return test(&args).await,
+        Some(Command::Fuzz(args)) => return fuzz(&args).await,
+        Some(Command::Record(args)) => return record(&args).await,
+        Some(Command::Replay(args)) => return replay(&args).await,
+        Some(Command::Templates { command }) => templates(command, config.template_dir.as_deref())?,
+        Some(Command::Info) => print_server_info(&config),
+    }
+    Ok(ExitCode::SUCCESS)
+}
 
-    if debug_mode {
-        // Debug mode: display server information
-        print_server_info();
-        Ok(())
+/// Run a tool through the same executor the MCP tools use
+async fn run_tool(name: &str, args: &serde_json::Value) -> Result<String> {
+    tool_executor::execute_tool(name, args)
+        .await
+        .map_err(anyhow::Error::msg)
+}
+
+/// Write `content` to `path`, refusing to overwrite an existing file
+fn write_new_file(path: &Path, content: &str) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Validate (and optionally fix) a manifest file
+fn validate(args: &ValidateArgs) -> Result<ExitCode> {
+    let file_name = args
+        .file
+        .file_name()
+        .with_context(|| format!("Not a file: {}", args.file.display()))?;
+    let dir = args
+        .file
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let tool_args = json!({
+        "path": file_name.to_string_lossy(),
+        "workspace_root": dir,
+        "format": args.format,
+        "syntax": args.syntax,
+    });
+    let display_path = args.file.display().to_string();
+
+    let report = if args.fix {
+        let result = tool_executor::fix_manifest(&tool_args).map_err(anyhow::Error::msg)?;
+        if !result.fixes.is_empty() {
+            fs::write(&args.file, &result.fixed_manifest)
+                .with_context(|| format!("Failed to write {}", args.file.display()))?;
+        }
+        if !args.json {
+            for fix in &result.fixes {
+                println!("Fixed {}: {}", fix.pointer, fix.description);
+            }
+        }
+        result.report.with_path(display_path)
     } else {
-        // Server mode: run MCP server with proper MCP SDK
-        run_mcp_server().await
+        tool_executor::validate_manifest_report(&tool_args)
+            .map_err(anyhow::Error::msg)?
+            .with_path(display_path)
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report.summary());
     }
+    Ok(if report.valid {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
 }
 
-/// Print server information (debug mode)
-fn print_server_info() {
-    let _server = MCPForgeServer::new();
+/// Run the conformance suite against a server
+async fn test(args: &TestArgs) -> Result<ExitCode> {
+    let tool_args = json!({
+        "command": args.command,
+        "args": args.args,
+        "timeout_secs": args.timeout,
+    });
+    let report = tool_executor::conformance_report(&tool_args)
+        .await
+        .map_err(anyhow::Error::msg)?;
+
+    if let Some(path) = &args.junit {
+        fs::write(path, report.junit_xml())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report.summary());
+    }
+    Ok(if report.success() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
 
+/// Fuzz the tools of a server
+async fn fuzz(args: &FuzzArgs) -> Result<ExitCode> {
+    // Fuzzed tools really run, so keep whatever they write out of the
+    // current directory unless asked otherwise
+    let scratch = args.workdir.is_none();
+    let workdir = args
+        .workdir
+        .clone()
+        .unwrap_or_else(|| env::temp_dir().join(format!("mcp-forge-fuzz-{}", std::process::id())));
+    fs::create_dir_all(&workdir)
+        .with_context(|| format!("Failed to create {}", workdir.display()))?;
+    let fixed: serde_json::Map<_, _> = args.fixed.iter().cloned().collect();
+    let tool_args = json!({
+        "command": args.command,
+        "args": args.args,
+        "workspace_root": workdir,
+        "tools": args.tools,
+        "fixed": fixed,
+        "cases": args.cases,
+        "seed": args.seed,
+        "timeout_secs": args.timeout,
+    });
+    let report = tool_executor::fuzz_report(&tool_args).await;
+    if scratch {
+        let _ = fs::remove_dir_all(&workdir);
+    }
+    let report = report.map_err(anyhow::Error::msg)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report.summary());
+    }
+    Ok(if report.success() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Start a stdio server with piped stdin and stdout
+fn spawn_server(command: &str, args: &[String]) -> Result<tokio::process::Child> {
+    tokio::process::Command::new(command)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start {}", command))
+}
+
+/// Proxy stdio to a server, recording the session
+async fn record(args: &RecordArgs) -> Result<ExitCode> {
+    let output = tokio::fs::File::create(&args.output)
+        .await
+        .with_context(|| format!("Failed to create {}", args.output.display()))?;
+    let mut child = spawn_server(&args.command, &args.args)?;
+    let server_in = child.stdin.take().context("Server stdin is not piped")?;
+    let server_out = child.stdout.take().context("Server stdout is not piped")?;
+
+    let count = recording::proxy(
+        tokio::io::stdin(),
+        tokio::io::stdout(),
+        server_in,
+        server_out,
+        output,
+    )
+    .await
+    .map_err(anyhow::Error::msg)?;
+    let status = child.wait().await?;
+    tracing::info!("Recorded {} message(s) to {}", count, args.output.display());
+    Ok(if status.success() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Replay a recording against a server and report differences
+async fn replay(args: &ReplayArgs) -> Result<ExitCode> {
+    let text = fs::read_to_string(&args.recording)
+        .with_context(|| format!("Failed to read {}", args.recording.display()))?;
+    let messages = recording::parse_recording(&text).map_err(anyhow::Error::msg)?;
+    let mut child = spawn_server(&args.command, &args.args)?;
+    let server_in = child.stdin.take().context("Server stdin is not piped")?;
+    let server_out = child.stdout.take().context("Server stdout is not piped")?;
+
+    let report = recording::replay(
+        &messages,
+        server_in,
+        server_out,
+        &args.ignore,
+        std::time::Duration::from_secs(args.timeout),
+    )
+    .await
+    .map_err(anyhow::Error::msg)?;
+    // Give the server a moment to exit on its own now that stdin is closed
+    if tokio::time::timeout(std::time::Duration::from_secs(5), child.wait())
+        .await
+        .is_err()
+    {
+        child.kill().await?;
+    }
+
+    if args.update {
+        fs::write(&args.recording, recording::to_jsonl(&report.recording))
+            .with_context(|| format!("Failed to write {}", args.recording.display()))?;
+    }
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report.summary());
+    }
+    Ok(if report.success() || args.update {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// List or print templates, with on-disk overrides applied
+fn templates(command: TemplatesCommand, template_dir: Option<&Path>) -> Result<()> {
+    let resources = resources::get_resources_with_overrides(template_dir);
+    let mut templates: Vec<(&str, &resources::Resource)> = resources
+        .values()
+        .filter_map(|resource| {
+            let name = resource.uri.strip_prefix("forge://templates/")?;
+            Some((name, resource))
+        })
+        .collect();
+    templates.sort_by_key(|(name, _)| *name);
+
+    match command {
+        TemplatesCommand::List => {
+            for (name, resource) in templates {
+                println!("{:<24} {}", name, resource.name);
+            }
+        }
+        TemplatesCommand::Show { name } => {
+            let Some((_, resource)) = templates.iter().find(|(n, _)| *n == name) else {
+                let names: Vec<&str> = templates.iter().map(|(n, _)| *n).collect();
+                bail!(
+                    "Unknown template '{}'. Available: {}",
+                    name,
+                    names.join(", ")
+                );
+            };
+            print!("{}", resource.text().unwrap_or_default());
+        }
+    }
+    Ok(())
+}
+
+/// Print server information
+fn print_server_info(config: &ForgeConfig) {
     println!("=== MCP Forge Server ===");
     println!("Version: {}", env!("CARGO_PKG_VERSION"));
     println!("Description: {}", env!("CARGO_PKG_DESCRIPTION"));
     println!();
 
     println!("This server provides tools and resources for MCP development:");
-    println!("- Project generation tools");
-    println!("- Code generation utilities");
-    println!("- Documentation and examples");
+    println!(
+        "- {} tools: {}",
+        tools::get_available_tools().len(),
+        tools::get_available_tools()
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    println!("- {} prompts", prompts::get_available_prompts().len());
+    println!("- {} resources", resources::get_available_resources().len());
+    println!();
+
+    println!("Configuration:");
+    println!(
+        "- author: {}",
+        config.author.as_deref().unwrap_or("(not set)")
+    );
+    println!("- license: {}", config.license);
+    println!("- rmcp version: {}", config.rmcp_version);
+    println!("- workspace: {}", config.workspace_root.display());
+    println!(
+        "- template dir: {}",
+        config
+            .template_dir
+            .as_ref()
+            .map_or("(built-in templates)".into(), |dir| dir
+                .display()
+                .to_string())
+    );
     println!();
 
     println!("To test with MCP Inspector:");
     println!("  npx @modelcontextprotocol/inspector cargo run");
     println!();
 
+    println!("To share one server between several clients over HTTP:");
+    println!(
+        "  mcp-forge serve --transport http --bind {}",
+        http::DEFAULT_BIND_ADDRESS
+    );
+    println!();
+
     tracing::info!("MCP Forge server info displayed");
 }
 
 /// Run MCP server using official MCP SDK
-async fn run_mcp_server() -> Result<()> {
+async fn run_mcp_server(args: ServeArgs, config: ForgeConfig) -> Result<()> {
+    let transport = args.transport()?;
     tracing::info!("Starting MCP Forge server with official MCP SDK");
 
+    // Generate projects into the configured workspace
+    tracing::info!("Using workspace: {}", config.workspace_root.display());
+
+    // Serve template overrides from disk if configured
+    if let Some(template_dir) = &config.template_dir {
+        tracing::info!("Using template overrides from: {}", template_dir.display());
+    }
+
     // Create MCP Forge server instance
-    let server = MCPForgeServer::new();
+    let server = MCPForgeServer::new(config);
+
+    if let Transport::Http(addr) = transport {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to bind {}", addr))?;
+        tracing::info!(
+            "MCP Forge server listening on http://{}{}",
+            listener.local_addr()?,
+            http::MCP_ENDPOINT
+        );
+        http::serve(server, listener, http::shutdown_signal()).await?;
+        tracing::info!("MCP Forge server stopped");
+        return Ok(());
+    }
 
     // Start server with stdio transport
     let transport = (tokio::io::stdin(), tokio::io::stdout());
@@ -69,3 +720,130 @@ async fn run_mcp_server() -> Result<()> {
     tracing::info!("MCP Forge server stopped");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transport(args: &[&str]) -> Result<Transport> {
+        let cli = Cli::try_parse_from(std::iter::once("mcp-forge").chain(args.iter().copied()))?;
+        match cli.command {
+            Some(Command::Serve(args)) => args.transport(),
+            None => cli.serve.transport(),
+            Some(other) => bail!("unexpected command {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_serve_arguments() {
+        assert_eq!(transport(&[]).unwrap(), Transport::Stdio);
+        assert_eq!(
+            transport(&["--transport", "http"]).unwrap(),
+            Transport::Http(http::DEFAULT_BIND_ADDRESS.parse().unwrap())
+        );
+        assert_eq!(
+            transport(&["serve", "--transport=http", "--bind", "0.0.0.0:9000"]).unwrap(),
+            Transport::Http("0.0.0.0:9000".parse().unwrap())
+        );
+        assert!(transport(&["--bind", "0.0.0.0:9000"]).is_err());
+        assert!(transport(&["--transport", "websocket"]).is_err());
+        assert!(transport(&["serve", "--transport", "http", "--bind", "localhost"]).is_err());
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let cli = Cli::try_parse_from([
+            "mcp-forge",
+            "new",
+            "demo",
+            "-d",
+            "A demo",
+            "--license",
+            "Apache-2.0",
+        ])
+        .unwrap();
+        assert_eq!(cli.config.layer().license.as_deref(), Some("Apache-2.0"));
+        assert!(matches!(
+            cli.command,
+            Some(Command::New { name, description: Some(_), .. }) if name == "demo"
+        ));
+
+        let cli = Cli::try_parse_from(["mcp-forge", "validate", "claude.yaml", "--fix", "--json"])
+            .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Validate(ValidateArgs {
+                fix: true,
+                json: true,
+                ..
+            }))
+        ));
+
+        let cli = Cli::try_parse_from(["mcp-forge", "templates", "show", "main.rs"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Templates {
+                command: TemplatesCommand::Show { .. },
+                ..
+            })
+        ));
+
+        let cli = Cli::try_parse_from([
+            "mcp-forge",
+            "fuzz",
+            "--fix",
+            "verify=false",
+            "--fix",
+            "name=demo",
+            "--tool",
+            "search",
+            "my-server",
+            "--flag",
+        ])
+        .unwrap();
+        let Some(Command::Fuzz(args)) = cli.command else {
+            panic!("expected the fuzz command");
+        };
+        assert_eq!(
+            args.fixed,
+            [
+                ("verify".to_string(), json!(false)),
+                ("name".to_string(), json!("demo"))
+            ]
+        );
+        assert_eq!(args.tools, ["search"]);
+        assert_eq!(args.args, ["--flag"]);
+        assert!(Cli::try_parse_from(["mcp-forge", "fuzz", "--fix", "verify", "x"]).is_err());
+
+        assert!(Cli::try_parse_from(["mcp-forge", "add-tool", "search"]).is_err());
+        assert!(Cli::try_parse_from(["mcp-forge", "--transport", "http", "info"]).is_err());
+    }
+
+    #[test]
+    fn test_validate_exit_code() {
+        let dir = env::temp_dir().join("mcp_forge_cli_validate");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("manifest.json");
+        fs::write(
+            &file,
+            r#"{"name": "demo", "version": "1.0", "description": "Demo"}"#,
+        )
+        .unwrap();
+
+        let mut args = ValidateArgs {
+            file: file.clone(),
+            format: "auto".to_string(),
+            syntax: "auto".to_string(),
+            fix: false,
+            json: false,
+        };
+        assert_eq!(validate(&args).unwrap(), ExitCode::FAILURE);
+
+        args.fix = true;
+        assert_eq!(validate(&args).unwrap(), ExitCode::SUCCESS);
+        assert!(fs::read_to_string(&file).unwrap().contains("\"1.0.0\""));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
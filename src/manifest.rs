@@ -0,0 +1,1229 @@
+//! Manifest validation
+//!
+//! Validates MCP client configuration files and server manifests against
+//! bundled JSON Schemas, followed by semantic checks the schemas cannot
+//! express. Every problem is reported as a [`Diagnostic`] carrying its
+//! severity, the JSON pointer and source line/column of the offending value,
+//! and a suggested fix where one is known. Syntax errors do not stop
+//! validation: the document is recovered by [`json_source`] and checked
+//! against the schema in the same pass.
+//!
+//! Manifests may also be written in YAML or TOML ([`SourceSyntax`]); they are
+//! normalized to the same JSON model before validation, and diagnostics
+//! point at lines and columns of the original YAML or TOML text.
+//!
+//! Supported formats:
+//! - `claude_desktop_config.json` (`mcpServers` → `command`, `args`, `env`)
+//! - MCP registry `server.json` (semver version, package/transport consistency)
+//! - Desktop extension `manifest.json` (semver version, tool and prompt lists)
+//! - Basic server manifests (`name`, `version`, `description`, capabilities)
+
+use crate::json_source::{self, ParsedJson, push_pointer};
+use crate::{toml_source, yaml_source};
+use jsonschema::JsonType;
+use jsonschema::error::{TypeKind, ValidationErrorKind};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// JSON Schema for `claude_desktop_config.json`
+const CLAUDE_DESKTOP_CONFIG_SCHEMA: &str =
+    include_str!("../schemas/claude_desktop_config.schema.json");
+
+/// JSON Schema for MCP registry `server.json`
+const SERVER_JSON_SCHEMA: &str = include_str!("../schemas/server.schema.json");
+
+/// JSON Schema for desktop extension `manifest.json`
+const DESKTOP_EXTENSION_SCHEMA: &str =
+    include_str!("../schemas/desktop_extension_manifest.schema.json");
+
+static CLAUDE_DESKTOP_CONFIG: LazyLock<BundledSchema> =
+    LazyLock::new(|| BundledSchema::from_str(CLAUDE_DESKTOP_CONFIG_SCHEMA));
+
+static SERVER_JSON: LazyLock<BundledSchema> =
+    LazyLock::new(|| BundledSchema::from_str(SERVER_JSON_SCHEMA));
+
+static DESKTOP_EXTENSION: LazyLock<BundledSchema> =
+    LazyLock::new(|| BundledSchema::from_str(DESKTOP_EXTENSION_SCHEMA));
+
+static SERVER_MANIFEST: LazyLock<BundledSchema> = LazyLock::new(|| {
+    BundledSchema::new(json!({
+        "type": "object",
+        "required": ["name", "version", "description"],
+        "properties": {
+            "name": {"type": "string"},
+            "version": {"type": "string"},
+            "description": {"type": "string"},
+            "capabilities": {"type": "object"},
+            "tools": {"type": "array", "items": {"type": "object", "required": ["name"]}},
+            "prompts": {"type": "array", "items": {"type": "object", "required": ["name"]}},
+            "resources": {"type": "array", "items": {"type": "object"}}
+        }
+    }))
+});
+
+/// Version inserted by auto-fix when a manifest has none
+pub const DEFAULT_VERSION: &str = "0.1.0";
+
+/// A compiled bundled schema
+struct BundledSchema {
+    validator: jsonschema::Validator,
+    /// The schema document, for looking up the properties at an error
+    schema: Value,
+}
+
+impl BundledSchema {
+    /// Compile a bundled schema, panicking if it is malformed
+    fn new(schema: Value) -> Self {
+        Self {
+            validator: jsonschema::validator_for(&schema)
+                .expect("bundled schema is a valid JSON Schema"),
+            schema,
+        }
+    }
+
+    /// Property names of the schema object holding the keyword at
+    /// `keyword_path`, a validation error's schema path
+    ///
+    /// Used for typo suggestions, so they only name properties that are
+    /// allowed where the error is.
+    fn properties_at(&self, keyword_path: &str) -> BTreeSet<String> {
+        let segments: Vec<String> = keyword_path
+            .split('/')
+            .skip(1)
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        let Some((_keyword, parents)) = segments.split_last() else {
+            return BTreeSet::new();
+        };
+
+        let mut node = &self.schema;
+        for segment in parents {
+            let next = if segment == "$ref" {
+                node.get("$ref")
+                    .and_then(Value::as_str)
+                    .and_then(|reference| reference.strip_prefix('#'))
+                    .and_then(|pointer| self.schema.pointer(pointer))
+            } else {
+                match node {
+                    Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+                    _ => node.get(segment),
+                }
+            };
+            let Some(next) = next else {
+                return BTreeSet::new();
+            };
+            node = next;
+        }
+        node.get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| properties.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn from_str(schema: &str) -> Self {
+        Self::new(serde_json::from_str(schema).expect("bundled schema is valid JSON"))
+    }
+}
+
+/// Syntax of manifest text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceSyntax {
+    /// JSON (parsed leniently, with error recovery)
+    #[default]
+    Json,
+    /// YAML (first document of the stream)
+    Yaml,
+    /// TOML
+    Toml,
+}
+
+impl SourceSyntax {
+    /// Syntax implied by a file extension (`.json`, `.yaml`/`.yml`, `.toml`)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        extension.parse().ok()
+    }
+
+    /// Guess the syntax of manifest text from its first significant line
+    ///
+    /// `key = value` and `[table]` lines are TOML, `key:` lines, list items
+    /// and `---` are YAML, and anything else is treated as JSON.
+    pub fn detect(text: &str) -> Self {
+        let Some(line) = text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+        else {
+            return Self::Json;
+        };
+
+        let is_key = |key: &str| {
+            let key = key.trim();
+            !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '"' | '\''))
+        };
+        let is_table_header = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .map(|inner| inner.trim_start_matches('[').trim_end_matches(']'))
+            .is_some_and(is_key);
+        let is_yaml_key = line
+            .split_once(':')
+            .is_some_and(|(key, rest)| is_key(key) && (rest.is_empty() || rest.starts_with(' ')));
+
+        if is_table_header || line.split_once('=').is_some_and(|(key, _)| is_key(key)) {
+            Self::Toml
+        } else if is_yaml_key
+            || line == "---"
+            || line.starts_with("- ")
+            || line.starts_with("%YAML")
+        {
+            Self::Yaml
+        } else {
+            Self::Json
+        }
+    }
+
+    /// Parse text of this syntax into the common document model
+    pub fn parse(self, text: &str) -> ParsedJson {
+        match self {
+            Self::Json => json_source::parse(text),
+            Self::Yaml => yaml_source::parse(text),
+            Self::Toml => toml_source::parse(text),
+        }
+    }
+}
+
+impl FromStr for SourceSyntax {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "toml" => Ok(Self::Toml),
+            _ => Err(format!(
+                "Unknown manifest syntax '{}': expected one of auto, json, yaml, toml",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SourceSyntax {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => f.write_str("JSON"),
+            Self::Yaml => f.write_str("YAML"),
+            Self::Toml => f.write_str("TOML"),
+        }
+    }
+}
+
+/// Manifest formats understood by the validator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ManifestFormat {
+    /// Claude Desktop client configuration (`claude_desktop_config.json`)
+    ClaudeDesktopConfig,
+    /// MCP registry server manifest (`server.json`)
+    ServerJson,
+    /// Desktop extension manifest (`manifest.json`)
+    DesktopExtension,
+    /// Basic server manifest with `name`, `version` and `description`
+    ServerManifest,
+}
+
+impl ManifestFormat {
+    /// Detect the format of a parsed manifest from its top-level keys
+    ///
+    /// - `mcpServers` → `claude_desktop_config.json`
+    /// - `manifest_version` or `dxt_version` → desktop extension `manifest.json`
+    /// - `packages`, `remotes` or a `server.schema.json` `$schema` → `server.json`
+    /// - anything else → basic server manifest
+    pub fn detect(manifest: &Value) -> Self {
+        let has = |key: &str| manifest.get(key).is_some();
+        let schema = manifest.get("$schema").and_then(Value::as_str);
+
+        if has("mcpServers") {
+            Self::ClaudeDesktopConfig
+        } else if has("manifest_version") || has("dxt_version") {
+            Self::DesktopExtension
+        } else if has("packages")
+            || has("remotes")
+            || schema.is_some_and(|s| s.ends_with("server.schema.json"))
+        {
+            Self::ServerJson
+        } else {
+            Self::ServerManifest
+        }
+    }
+
+    fn schema(self) -> &'static BundledSchema {
+        match self {
+            Self::ClaudeDesktopConfig => &CLAUDE_DESKTOP_CONFIG,
+            Self::ServerJson => &SERVER_JSON,
+            Self::DesktopExtension => &DESKTOP_EXTENSION,
+            Self::ServerManifest => &SERVER_MANIFEST,
+        }
+    }
+}
+
+impl FromStr for ManifestFormat {
+    type Err = String;
+
+    /// Parse a format name as accepted by the `format` tool argument
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "claude_desktop_config" | "claude_desktop_config.json" => Ok(Self::ClaudeDesktopConfig),
+            "server_json" | "server.json" => Ok(Self::ServerJson),
+            "desktop_extension" | "manifest.json" | "dxt" | "mcpb" => Ok(Self::DesktopExtension),
+            "server_manifest" => Ok(Self::ServerManifest),
+            _ => Err(format!(
+                "Unknown manifest format '{}': expected one of auto, claude_desktop_config, \
+                 server_json, desktop_extension, server_manifest",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ManifestFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClaudeDesktopConfig => f.write_str("claude_desktop_config.json"),
+            Self::ServerJson => f.write_str("server.json"),
+            Self::DesktopExtension => f.write_str("desktop extension manifest.json"),
+            Self::ServerManifest => f.write_str("server manifest"),
+        }
+    }
+}
+
+/// Severity of a diagnostic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The manifest is invalid
+    Error,
+    /// The manifest is valid but likely wrong
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => f.write_str("error"),
+            Self::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+/// A single validation problem
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// Severity of the problem
+    pub severity: Severity,
+    /// JSON pointer to the offending value ("" for the document root)
+    pub pointer: String,
+    /// Line in the original text (1-based), when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Column in the original text (1-based, in characters), when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// Description of the problem
+    pub message: String,
+    /// Suggested fix, when one is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// Edit that fixes the problem automatically, when one is safe to apply
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+/// An automatic fix for a diagnostic, applied at the diagnostic's pointer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Fix {
+    /// Add the missing object member with the given value
+    Insert { value: Value },
+    /// Replace the value
+    Replace { value: Value },
+    /// Rename the object key
+    RenameKey { to: String },
+}
+
+impl Diagnostic {
+    /// Create an error diagnostic
+    pub fn error(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            pointer: pointer.into(),
+            line: None,
+            column: None,
+            message: message.into(),
+            suggestion: None,
+            fix: None,
+        }
+    }
+
+    /// Create a warning diagnostic
+    pub fn warning(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(pointer, message)
+        }
+    }
+
+    /// Attach a suggested fix
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    /// Attach an automatic fix
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// Fill in the line and column of the pointer in the parsed source
+    pub fn located(mut self, parsed: &ParsedJson) -> Self {
+        if let Some(position) = parsed.locate(&self.pointer) {
+            self.line = Some(position.line);
+            self.column = Some(position.column);
+        }
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, "{}:{}: ", line, column)?;
+        }
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{} {}: {}", self.severity, pointer, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (fix: {})", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of validating manifest text
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    /// Format the manifest was validated as
+    pub format: ManifestFormat,
+    /// Syntax the manifest was written in
+    pub syntax: SourceSyntax,
+    /// Workspace-relative path the manifest was read from, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Whether the manifest has no errors (warnings are allowed)
+    pub valid: bool,
+    /// Number of errors
+    pub errors: usize,
+    /// Number of warnings
+    pub warnings: usize,
+    /// All problems, in source order
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    fn new(format: ManifestFormat, syntax: SourceSyntax, diagnostics: Vec<Diagnostic>) -> Self {
+        let errors = diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count();
+        Self {
+            format,
+            syntax,
+            path: None,
+            valid: errors == 0,
+            errors,
+            warnings: diagnostics.len() - errors,
+            diagnostics,
+        }
+    }
+
+    /// Record the path the manifest was read from
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Human-readable summary listing every diagnostic
+    ///
+    /// Diagnostics are prefixed with `path:line:column:` when the manifest
+    /// was read from a file.
+    pub fn summary(&self) -> String {
+        let status = if self.valid { "valid" } else { "invalid" };
+        let mut summary = match self.syntax {
+            SourceSyntax::Json => format!("Manifest is {} ({}).", status, self.format),
+            syntax => format!(
+                "Manifest is {} ({}, {} source).",
+                status, self.format, syntax
+            ),
+        };
+
+        if !self.diagnostics.is_empty() {
+            summary.push_str(&format!(
+                " Found {} error(s) and {} warning(s):",
+                self.errors, self.warnings
+            ));
+            for diagnostic in &self.diagnostics {
+                match (&self.path, diagnostic.line) {
+                    (Some(path), Some(_)) => {
+                        summary.push_str(&format!("\n- {}:{}", path, diagnostic))
+                    }
+                    (Some(path), None) => {
+                        summary.push_str(&format!("\n- {}: {}", path, diagnostic))
+                    }
+                    (None, _) => summary.push_str(&format!("\n- {}", diagnostic)),
+                }
+            }
+        }
+        summary
+    }
+}
+
+/// Validate JSON manifest text, reporting syntax and schema problems together
+///
+/// The text is parsed with error recovery, so syntax errors are reported
+/// alongside the schema and semantic problems of the recovered document.
+/// When `format` is `None` it is detected from the recovered document.
+pub fn validate_source(text: &str, format: Option<ManifestFormat>) -> ValidationReport {
+    validate_document(text, SourceSyntax::Json, format)
+}
+
+/// Validate manifest text written in the given syntax
+///
+/// YAML and TOML are normalized to the JSON model before schema validation;
+/// diagnostic positions refer to the original text.
+pub fn validate_document(
+    text: &str,
+    syntax: SourceSyntax,
+    format: Option<ManifestFormat>,
+) -> ValidationReport {
+    let parsed = syntax.parse(text);
+
+    let mut diagnostics: Vec<Diagnostic> = parsed
+        .errors
+        .iter()
+        .map(|error| Diagnostic {
+            severity: Severity::Error,
+            pointer: error.pointer.clone(),
+            line: Some(error.position.line),
+            column: Some(error.position.column),
+            message: error.message.clone(),
+            suggestion: error.suggestion.clone(),
+            fix: None,
+        })
+        .collect();
+
+    let format = match &parsed.value {
+        Some(value) => {
+            let format = format.unwrap_or_else(|| ManifestFormat::detect(value));
+            diagnostics.extend(
+                validate(value, format)
+                    .into_iter()
+                    .map(|diagnostic| diagnostic.located(&parsed)),
+            );
+            format
+        }
+        None => format.unwrap_or(ManifestFormat::ServerManifest),
+    };
+
+    diagnostics.sort_by_key(|d| (d.line.is_none(), d.line, d.column, d.severity));
+    ValidationReport::new(format, syntax, diagnostics)
+}
+
+/// Validate a parsed manifest against the schema of the given format
+///
+/// Returns every problem found, ordered by JSON pointer. Missing and
+/// unexpected properties point at the property itself rather than at the
+/// enclosing object. Source positions are not filled in; use
+/// [`validate_source`] to validate text.
+pub fn validate(manifest: &Value, format: ManifestFormat) -> Vec<Diagnostic> {
+    let mut diagnostics = schema_diagnostics(manifest, format);
+
+    match format {
+        ManifestFormat::ClaudeDesktopConfig => {}
+        ManifestFormat::ServerJson => check_server_json(manifest, &mut diagnostics),
+        ManifestFormat::DesktopExtension => check_desktop_extension(manifest, &mut diagnostics),
+        ManifestFormat::ServerManifest => check_server_manifest(manifest, &mut diagnostics),
+    }
+
+    diagnostics.sort_by(|a, b| a.pointer.cmp(&b.pointer));
+    diagnostics.dedup();
+    diagnostics
+}
+
+/// Collect the JSON Schema violations of a manifest
+fn schema_diagnostics(manifest: &Value, format: ManifestFormat) -> Vec<Diagnostic> {
+    let schema = format.schema();
+    schema
+        .validator
+        .iter_errors(manifest)
+        .flat_map(|error| {
+            let pointer = error.instance_path.to_string();
+            let schema_path = error.schema_path.to_string();
+            match &error.kind {
+                ValidationErrorKind::Required { property } => {
+                    let property = property.as_str().unwrap_or_default();
+                    let diagnostic = Diagnostic::error(
+                        push_pointer(&pointer, property),
+                        format!("missing required property \"{}\"", property),
+                    )
+                    .with_suggestion(format!("add the \"{}\" property", property));
+                    vec![match property {
+                        "version" => diagnostic.with_fix(Fix::Insert {
+                            value: json!(DEFAULT_VERSION),
+                        }),
+                        _ => diagnostic,
+                    }]
+                }
+                ValidationErrorKind::AdditionalProperties { unexpected } => {
+                    let known_properties = schema.properties_at(&schema_path);
+                    unexpected
+                        .iter()
+                        .map(|property| {
+                            let diagnostic = Diagnostic::error(
+                                push_pointer(&pointer, property),
+                                format!("unknown property \"{}\"", property),
+                            );
+                            match closest_match(property, &known_properties) {
+                                // Renaming onto an existing member would drop one of the two values
+                                Some(known) if error.instance.get(known).is_none() => diagnostic
+                                    .with_suggestion(format!("rename it to \"{}\"", known))
+                                    .with_fix(Fix::RenameKey {
+                                        to: known.to_string(),
+                                    }),
+                                Some(known) => diagnostic
+                                    .with_suggestion(format!("rename it to \"{}\"", known)),
+                                None => diagnostic.with_suggestion("remove the property"),
+                            }
+                        })
+                        .collect()
+                }
+                ValidationErrorKind::Type { kind } => {
+                    let diagnostic = Diagnostic::error(pointer, error.to_string());
+                    vec![match type_fix(kind, &error.instance) {
+                        Some((suggestion, fix)) => {
+                            diagnostic.with_suggestion(suggestion).with_fix(fix)
+                        }
+                        None => diagnostic,
+                    }]
+                }
+                _ => vec![Diagnostic::error(pointer, error.to_string())],
+            }
+        })
+        .collect()
+}
+
+/// Suggest a fix for a value of the wrong type
+fn type_fix(kind: &TypeKind, instance: &Value) -> Option<(String, Fix)> {
+    let TypeKind::Single(expected) = kind else {
+        return None;
+    };
+    match (expected, instance) {
+        (JsonType::Array, Value::String(_)) => Some((
+            format!("wrap the value in an array: [{}]", instance),
+            Fix::Replace {
+                value: json!([instance]),
+            },
+        )),
+        (JsonType::String, Value::Number(_) | Value::Bool(_)) => Some((
+            format!("quote the value: \"{}\"", instance),
+            Fix::Replace {
+                value: Value::String(instance.to_string()),
+            },
+        )),
+        _ => None,
+    }
+}
+
+/// Find the known property name closest to a misspelled one
+fn closest_match<'a>(name: &str, candidates: &'a BTreeSet<String>) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Levenshtein distance between two strings (case-insensitive)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Semantic checks for MCP registry `server.json`
+///
+/// - `version` and package versions are valid semantic versions
+/// - package versions match the server version
+/// - `stdio` transports have no `url`, HTTP transports require one
+/// - at least one package or remote is declared
+fn check_server_json(manifest: &Value, diagnostics: &mut Vec<Diagnostic>) {
+    check_semver(manifest.get("version"), "/version", diagnostics);
+    let server_version = manifest.get("version").and_then(Value::as_str);
+
+    let packages = manifest.get("packages").and_then(Value::as_array);
+    let remotes = manifest.get("remotes").and_then(Value::as_array);
+    if packages.is_none_or(Vec::is_empty) && remotes.is_none_or(Vec::is_empty) {
+        diagnostics.push(
+            Diagnostic::error(
+                "",
+                "server declares no packages or remotes, so clients cannot run it",
+            )
+            .with_suggestion("add a \"packages\" or \"remotes\" entry"),
+        );
+    }
+
+    for (index, package) in packages.into_iter().flatten().enumerate() {
+        let pointer = format!("/packages/{}", index);
+
+        if let Some(version) = package.get("version").and_then(Value::as_str) {
+            check_semver(
+                package.get("version"),
+                &format!("{}/version", pointer),
+                diagnostics,
+            );
+            if let Some(server_version) = server_version.filter(|server| *server != version) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        format!("{}/version", pointer),
+                        format!(
+                            "package version \"{}\" does not match server version \"{}\"",
+                            version, server_version
+                        ),
+                    )
+                    .with_suggestion(format!("set it to \"{}\"", server_version)),
+                );
+            }
+        }
+
+        let Some(transport) = package.get("transport") else {
+            continue;
+        };
+        let transport_type = transport.get("type").and_then(Value::as_str);
+        let has_url = transport.get("url").is_some();
+        match transport_type {
+            Some("stdio") if has_url => diagnostics.push(
+                Diagnostic::error(
+                    format!("{}/transport/url", pointer),
+                    "stdio transport must not declare a url",
+                )
+                .with_suggestion("remove the url or use a streamable-http transport"),
+            ),
+            Some(kind @ ("streamable-http" | "sse")) if !has_url => diagnostics.push(
+                Diagnostic::error(
+                    format!("{}/transport/url", pointer),
+                    format!("{} transport requires a url", kind),
+                )
+                .with_suggestion("add the endpoint url"),
+            ),
+            _ => {}
+        }
+
+        if package.get("registryType").and_then(Value::as_str) == Some("mcpb")
+            && package.get("fileSha256").is_none()
+        {
+            diagnostics.push(
+                Diagnostic::error(
+                    format!("{}/fileSha256", pointer),
+                    "mcpb packages require a fileSha256 checksum",
+                )
+                .with_suggestion("add the SHA-256 of the .mcpb file"),
+            );
+        }
+    }
+}
+
+/// Semantic checks for desktop extension `manifest.json`
+///
+/// - `version` is a valid semantic version
+/// - tool and prompt names are valid and unique
+fn check_desktop_extension(manifest: &Value, diagnostics: &mut Vec<Diagnostic>) {
+    check_semver(manifest.get("version"), "/version", diagnostics);
+    check_named_list(manifest, "tools", diagnostics);
+    check_named_list(manifest, "prompts", diagnostics);
+}
+
+/// Semantic checks for basic server manifests
+///
+/// - `version` is a valid semantic version
+/// - tool and prompt names are valid and unique
+/// - listed tools, prompts and resources match the declared capabilities
+fn check_server_manifest(manifest: &Value, diagnostics: &mut Vec<Diagnostic>) {
+    check_semver(manifest.get("version"), "/version", diagnostics);
+    check_named_list(manifest, "tools", diagnostics);
+    check_named_list(manifest, "prompts", diagnostics);
+
+    let Some(capabilities) = manifest.get("capabilities").and_then(Value::as_object) else {
+        return;
+    };
+    for kind in ["tools", "prompts", "resources"] {
+        let listed = manifest.get(kind).and_then(Value::as_array).map(Vec::len);
+        let declared = capabilities.contains_key(kind);
+        match listed {
+            Some(count) if count > 0 && !declared => diagnostics.push(
+                Diagnostic::error(
+                    format!("/capabilities/{}", kind),
+                    format!(
+                        "manifest lists {} {} but does not declare the {} capability",
+                        count, kind, kind
+                    ),
+                )
+                .with_suggestion(format!("add \"{}\": {{}} to capabilities", kind)),
+            ),
+            Some(0) if declared => diagnostics.push(
+                Diagnostic::warning(
+                    format!("/{}", kind),
+                    format!(
+                        "manifest declares the {} capability but lists no {}",
+                        kind, kind
+                    ),
+                )
+                .with_suggestion(format!("list the {} or drop the capability", kind)),
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Report a version string at `pointer` that is not a valid semantic version
+fn check_semver(version: Option<&Value>, pointer: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(version) = version.and_then(Value::as_str) else {
+        return;
+    };
+    if let Err(e) = semver::Version::parse(version) {
+        let diagnostic = Diagnostic::error(
+            pointer,
+            format!("\"{}\" is not a valid semantic version: {}", version, e),
+        );
+        diagnostics.push(match normalize_semver(version) {
+            Some(fixed) => diagnostic
+                .with_suggestion(format!("use \"{}\"", fixed))
+                .with_fix(Fix::Replace {
+                    value: Value::String(fixed),
+                }),
+            None => diagnostic.with_suggestion("use MAJOR.MINOR.PATCH, e.g. \"1.0.0\""),
+        });
+    }
+}
+
+/// Turn a near-semver version ("v1.2", "1") into a valid semantic version
+pub fn normalize_semver(version: &str) -> Option<String> {
+    let trimmed = version.trim().trim_start_matches(['v', 'V']);
+    let (core, rest) = match trimmed.find(['-', '+']) {
+        Some(index) => trimmed.split_at(index),
+        None => (trimmed, ""),
+    };
+
+    let mut parts: Vec<&str> = core.split('.').collect();
+    if parts.len() > 3
+        || parts
+            .iter()
+            .any(|p| p.is_empty() || !p.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    parts.resize(3, "0");
+
+    let candidate = format!(
+        "{}{}",
+        parts
+            .iter()
+            .map(|p| p.parse::<u64>().map(|n| n.to_string()))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?
+            .join("."),
+        rest
+    );
+    semver::Version::parse(&candidate)
+        .ok()
+        .map(|v| v.to_string())
+}
+
+/// Check that the entries of a `tools`/`prompts` list have valid, unique names
+fn check_named_list(manifest: &Value, key: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(items) = manifest.get(key).and_then(Value::as_array) else {
+        return;
+    };
+
+    let mut seen = HashSet::new();
+    for (index, item) in items.iter().enumerate() {
+        let Some(name) = item.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let pointer = format!("/{}/{}/name", key, index);
+        if !is_valid_tool_name(name) {
+            diagnostics.push(
+                Diagnostic::error(
+                    pointer.clone(),
+                    format!(
+                        "\"{}\" is not a valid name: use 1-128 ASCII letters, digits, '_', '-' or '.'",
+                        name
+                    ),
+                )
+                .with_suggestion(format!(
+                    "use \"{}\"",
+                    name.replace(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')), "_")
+                )),
+            );
+        }
+        if !seen.insert(name) {
+            diagnostics.push(
+                Diagnostic::error(
+                    pointer,
+                    format!("duplicate {} name \"{}\"", key.trim_end_matches('s'), name),
+                )
+                .with_suggestion("rename or remove the duplicate entry"),
+            );
+        }
+    }
+}
+
+/// Whether a tool or prompt name follows the MCP naming rules
+pub fn is_valid_tool_name(name: &str) -> bool {
+    (1..=128).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            ManifestFormat::detect(&json!({"mcpServers": {}})),
+            ManifestFormat::ClaudeDesktopConfig
+        );
+        assert_eq!(
+            ManifestFormat::detect(&json!({"name": "test"})),
+            ManifestFormat::ServerManifest
+        );
+    }
+
+    #[test]
+    fn test_valid_desktop_config() {
+        let config = json!({
+            "mcpServers": {
+                "forge": {
+                    "command": "/usr/local/bin/mcp-forge",
+                    "args": ["serve"],
+                    "env": {"RUST_LOG": "info"}
+                },
+                "minimal": {"command": "npx"}
+            }
+        });
+        assert!(validate(&config, ManifestFormat::ClaudeDesktopConfig).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_desktop_config_reports_pointers() {
+        let config = json!({
+            "mcpServers": {
+                "broken": {
+                    "args": "serve",
+                    "env": {"PORT": 8080},
+                    "comand": "mcp-forge"
+                },
+                "other": {"command": "x", "args": ["ok", 1]}
+            }
+        });
+        let issues = validate(&config, ManifestFormat::ClaudeDesktopConfig);
+        let pointers: Vec<&str> = issues.iter().map(|i| i.pointer.as_str()).collect();
+        assert_eq!(
+            pointers,
+            vec![
+                "/mcpServers/broken/args",
+                "/mcpServers/broken/comand",
+                "/mcpServers/broken/command",
+                "/mcpServers/broken/env/PORT",
+                "/mcpServers/other/args/1",
+            ]
+        );
+        assert!(issues[1].message.contains("unknown property"));
+        assert!(issues[2].message.contains("missing required property"));
+    }
+
+    #[test]
+    fn test_server_manifest_missing_fields() {
+        let issues = validate(&json!({"name": "test"}), ManifestFormat::ServerManifest);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].pointer, "/description");
+        assert_eq!(issues[1].pointer, "/version");
+    }
+
+    #[test]
+    fn test_detect_registry_and_extension_formats() {
+        assert_eq!(
+            ManifestFormat::detect(&json!({"packages": []})),
+            ManifestFormat::ServerJson
+        );
+        assert_eq!(
+            ManifestFormat::detect(&json!({"manifest_version": "0.2"})),
+            ManifestFormat::DesktopExtension
+        );
+        assert_eq!(
+            "server.json".parse::<ManifestFormat>(),
+            Ok(ManifestFormat::ServerJson)
+        );
+        assert!("yaml".parse::<ManifestFormat>().is_err());
+    }
+
+    #[test]
+    fn test_valid_server_json() {
+        let server = json!({
+            "$schema": "https://static.modelcontextprotocol.io/schemas/2025-09-29/server.schema.json",
+            "name": "io.github.h315uk3/mcp-forge",
+            "description": "MCP server development framework",
+            "version": "0.1.0",
+            "packages": [{
+                "registryType": "npm",
+                "identifier": "@h315uk3/mcp-forge",
+                "version": "0.1.0",
+                "transport": {"type": "stdio"}
+            }],
+            "remotes": [{"type": "streamable-http", "url": "https://forge.example.com/mcp"}]
+        });
+        assert_eq!(
+            validate(&server, ManifestFormat::ServerJson),
+            Vec::<Diagnostic>::new()
+        );
+    }
+
+    #[test]
+    fn test_server_json_semantic_issues() {
+        let server = json!({
+            "name": "io.github.h315uk3/mcp-forge",
+            "description": "MCP server development framework",
+            "version": "latest",
+            "packages": [
+                {
+                    "registryType": "npm",
+                    "identifier": "@h315uk3/mcp-forge",
+                    "version": "1.0.0",
+                    "transport": {"type": "stdio", "url": "http://localhost"}
+                },
+                {
+                    "registryType": "oci",
+                    "identifier": "ghcr.io/h315uk3/mcp-forge",
+                    "transport": {"type": "sse"}
+                }
+            ]
+        });
+        let issues = validate(&server, ManifestFormat::ServerJson);
+        let pointers: Vec<&str> = issues.iter().map(|i| i.pointer.as_str()).collect();
+        assert_eq!(
+            pointers,
+            vec![
+                "/packages/0/transport/url",
+                "/packages/0/version",
+                "/packages/1/transport/url",
+                "/version",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_desktop_extension() {
+        let manifest = json!({
+            "manifest_version": "0.2",
+            "name": "forge",
+            "version": "1.0",
+            "description": "MCP Forge",
+            "author": {"name": "h315uk3"},
+            "server": {
+                "type": "binary",
+                "entry_point": "server/mcp-forge",
+                "mcp_config": {"command": "${__dirname}/server/mcp-forge"}
+            },
+            "tools": [
+                {"name": "generate_project", "description": "Generate a project"},
+                {"name": "generate_project"},
+                {"name": "bad name"}
+            ]
+        });
+        let issues = validate(&manifest, ManifestFormat::DesktopExtension);
+        let pointers: Vec<&str> = issues.iter().map(|i| i.pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/tools/1/name", "/tools/2/name", "/version"]);
+    }
+
+    #[test]
+    fn test_server_manifest_capabilities() {
+        let manifest = json!({
+            "name": "test",
+            "version": "0.1.0",
+            "description": "test",
+            "capabilities": {"prompts": {}},
+            "tools": [{"name": "echo"}],
+            "prompts": []
+        });
+        let issues = validate(&manifest, ManifestFormat::ServerManifest);
+        let pointers: Vec<&str> = issues.iter().map(|i| i.pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/capabilities/tools", "/prompts"]);
+    }
+
+    #[test]
+    fn test_typo_and_type_suggestions() {
+        let config = json!({"mcpServers": {"forge": {"comand": "x", "args": "serve"}}});
+        let diagnostics = validate(&config, ManifestFormat::ClaudeDesktopConfig);
+        let suggestions: Vec<&str> = diagnostics
+            .iter()
+            .filter_map(|d| d.suggestion.as_deref())
+            .collect();
+        assert_eq!(
+            suggestions,
+            vec![
+                "wrap the value in an array: [\"serve\"]",
+                "rename it to \"command\"",
+                "add the \"command\" property",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggestions_come_from_the_error_location() {
+        // A top-level key misspelled inside a server entry
+        let config = json!({"mcpServers": {"x": {"command": "a", "mcpServer": 1}}});
+        let diagnostics = validate(&config, ManifestFormat::ClaudeDesktopConfig);
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert_eq!(diagnostics[0].pointer, "/mcpServers/x/mcpServer");
+        assert_eq!(
+            diagnostics[0].suggestion.as_deref(),
+            Some("remove the property")
+        );
+        assert!(diagnostics[0].fix.is_none());
+
+        // Properties of a definition reached through `$ref`
+        let server = json!({
+            "name": "io.github.forge/forge",
+            "description": "Forge",
+            "version": "1.0.0",
+            "packages": [{
+                "registryType": "npm",
+                "identifier": "forge",
+                "version": "1.0.0",
+                "transport": {"type": "stdio", "headrs": []}
+            }]
+        });
+        let diagnostics = validate(&server, ManifestFormat::ServerJson);
+        let typo = diagnostics
+            .iter()
+            .find(|d| d.pointer == "/packages/0/transport/headrs")
+            .unwrap();
+        assert_eq!(typo.suggestion.as_deref(), Some("rename it to \"headers\""));
+    }
+
+    #[test]
+    fn test_normalize_semver() {
+        assert_eq!(normalize_semver("1.0").as_deref(), Some("1.0.0"));
+        assert_eq!(normalize_semver("v2").as_deref(), Some("2.0.0"));
+        assert_eq!(
+            normalize_semver("1.2-beta.1").as_deref(),
+            Some("1.2.0-beta.1")
+        );
+        assert_eq!(normalize_semver("latest"), None);
+    }
+
+    #[test]
+    fn test_validate_source_reports_syntax_and_schema_together() {
+        let text = "{\n  \"mcpServers\": {\n    \"forge\": {\"args\": [\"serve\",]}\n  },\n}";
+        let report = validate_source(text, None);
+        assert_eq!(report.format, ManifestFormat::ClaudeDesktopConfig);
+        assert!(!report.valid);
+
+        let found: Vec<(Option<usize>, &str)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.line, d.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Some(3), "missing required property \"command\""),
+                (Some(3), "trailing comma in array"),
+                (Some(4), "trailing comma in object"),
+            ]
+        );
+        assert_eq!(report.diagnostics[0].column, Some(5));
+    }
+
+    #[test]
+    fn test_detect_source_syntax() {
+        assert_eq!(SourceSyntax::detect("{\"a\": 1}"), SourceSyntax::Json);
+        assert_eq!(SourceSyntax::detect("// config\n{}"), SourceSyntax::Json);
+        assert_eq!(SourceSyntax::detect("not valid json"), SourceSyntax::Json);
+        assert_eq!(
+            SourceSyntax::detect("# comment\nname = \"x\""),
+            SourceSyntax::Toml
+        );
+        assert_eq!(
+            SourceSyntax::detect("[mcpServers.forge]\n"),
+            SourceSyntax::Toml
+        );
+        assert_eq!(SourceSyntax::detect("[[packages]]\n"), SourceSyntax::Toml);
+        assert_eq!(
+            SourceSyntax::detect("mcpServers:\n  forge: {}"),
+            SourceSyntax::Yaml
+        );
+        assert_eq!(SourceSyntax::detect("---\nname: x"), SourceSyntax::Yaml);
+        assert_eq!(
+            SourceSyntax::from_path(Path::new("config/server.YML")),
+            Some(SourceSyntax::Yaml)
+        );
+        assert_eq!(SourceSyntax::from_path(Path::new("Makefile")), None);
+    }
+
+    #[test]
+    fn test_report_summary_and_json() {
+        let report = validate_source(
+            r#"{"name": "test", "version": "0.1.0", "description": "test"}"#,
+            None,
+        );
+        assert!(report.valid);
+        assert_eq!(report.summary(), "Manifest is valid (server manifest).");
+
+        let report = validate_source(r#"{"name": "test", "version": "1.0"}"#, None);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["format"], "server_manifest");
+        assert_eq!(json["valid"], false);
+        assert_eq!(json["errors"], 2);
+        assert_eq!(json["diagnostics"][0]["severity"], "error");
+        assert_eq!(json["diagnostics"][0]["line"], 1);
+        assert!(report.summary().contains("(fix: use \"1.0.0\")"));
+    }
+}
@@ -151,12 +151,25 @@ pub fn get_available_prompts() -> HashMap<String, Prompt> {
             "validate-manifest",
             "Validate an MCP server manifest file",
             "Use the validate_manifest tool to check if a manifest JSON is valid.\n\n\
-             Required fields in manifest:\n\
-             - name: Server name\n\
-             - version: Version number\n\
-             - description: Server description\n\n\
+             Supported formats (detected automatically):\n\
+             - claude_desktop_config.json: every mcpServers entry needs a command string,\n\
+               optional args (array of strings) and env (map of strings)\n\
+             - server.json (MCP registry): semver version, packages with consistent transports\n\
+             - manifest.json (desktop extension): semver version, unique tool and prompt names\n\
+             - Server manifest: requires name, version and description\n\n\
+             All problems are reported in one pass, including JSON syntax errors. Each\n\
+             diagnostic has a severity, the JSON pointer and line/column of the offending\n\
+             value, a message and, where known, a suggested fix. The structured result is\n\
+             returned as structured tool content alongside a readable summary.\n\n\
              Parameters:\n\
-             - manifest_content: The JSON manifest content as a string (required)\n\n\
+             - manifest_content: The manifest content as a string (JSON, YAML or TOML)\n\
+             - path: Workspace-relative path of a manifest file, instead of manifest_content\n\
+             - syntax: auto, json, yaml or toml (optional, defaults to the file extension\n\
+               or content); line/column refer to the original YAML or TOML text\n\
+             - format: auto, claude_desktop_config, server_json, desktop_extension or\n\
+               server_manifest (optional, defaults to auto)\n\
+             - fix: true to apply automatic fixes (typo'd keys, string args, missing or\n\
+               malformed version) and return the corrected manifest (optional)\n\n\
              Example usage:\n\
              Validate this manifest JSON: {manifest_content}",
         )
@@ -164,6 +177,41 @@ pub fn get_available_prompts() -> HashMap<String, Prompt> {
             "manifest_content",
             "The manifest JSON content to validate",
             true,
+        )
+        .with_argument("format", "Manifest format (defaults to auto)", false)
+        .with_argument("fix", "Apply automatic fixes (true/false)", false),
+    );
+
+    // Verify Manifest prompt
+    prompts.insert(
+        "verify-manifest".to_string(),
+        Prompt::new(
+            "verify-manifest",
+            "Check a manifest against the servers it launches",
+            "Use the verify_manifest tool to start every server the manifest configures\n\
+             and check that it actually works.\n\n\
+             The manifest is validated first. Then each mcpServers entry (or a desktop\n\
+             extension's server.mcp_config) is launched over stdio and must:\n\
+             - complete initialize within the timeout\n\
+             - answer tools/list, resources/list and prompts/list for every capability\n\
+               it advertises\n\
+             - expose every tool and prompt the desktop extension manifest declares\n\n\
+             The report lists each server's protocol version, server info, capabilities,\n\
+             tools, resources and prompts. Failures carry the JSON pointer and line/column\n\
+             of the server entry and the last lines the server wrote to stderr.\n\n\
+             Parameters:\n\
+             - manifest_content or path: The manifest to verify\n\
+             - format: auto, claude_desktop_config or desktop_extension (optional)\n\
+             - timeout_secs: Seconds each server gets to start and answer (optional,\n\
+               defaults to 10)\n\n\
+             Example usage:\n\
+             Verify the servers configured in {path}",
+        )
+        .with_argument("path", "Workspace-relative path of the manifest", true)
+        .with_argument(
+            "timeout_secs",
+            "Seconds each server gets (defaults to 10)",
+            false,
         ),
     );
 
@@ -325,12 +373,13 @@ mod tests {
     #[test]
     fn test_get_available_prompts() {
         let prompts = get_available_prompts();
-        assert!(prompts.len() >= 10);
+        assert!(prompts.len() >= 11);
         assert!(prompts.contains_key("generate-project"));
         assert!(prompts.contains_key("generate-tool"));
         assert!(prompts.contains_key("generate-resource"));
         assert!(prompts.contains_key("generate-readme"));
         assert!(prompts.contains_key("validate-manifest"));
+        assert!(prompts.contains_key("verify-manifest"));
         assert!(prompts.contains_key("advanced-tool-implementation"));
         assert!(prompts.contains_key("error-handling-patterns"));
         assert!(prompts.contains_key("async-patterns"));
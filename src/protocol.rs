@@ -0,0 +1,190 @@
+//! Protocol version negotiation
+//!
+//! MCP Forge advertises the newest protocol version it implements
+//! ([`LATEST`]) and answers `initialize` with the newest version both sides
+//! support: the client's requested version when it is older, otherwise
+//! [`LATEST`]. rmcp applies this rule to any version string, so an older
+//! version MCP Forge doesn't know is answered unchanged, and a session with
+//! it gets none of the newer features. What a session may use depends on
+//! the version:
+//!
+//! | Feature | Since |
+//! |---------|-------|
+//! | Structured tool output (`structuredContent`) | 2025-06-18 |
+//! | Resource links in tool results | 2025-06-18 |
+//! | Elicitation (`elicitation/create`) | 2025-06-18 |
+//!
+//! [`ProtocolFeatures::adapt_tool_result`] rewrites tool results for older
+//! sessions so no information is lost.
+
+use rmcp::model::{CallToolResult, Content, ProtocolVersion, RawContent};
+use rmcp::{RoleServer, service::Peer};
+
+/// Newest protocol version MCP Forge implements
+pub const LATEST: ProtocolVersion = ProtocolVersion::V_2025_06_18;
+
+/// Protocol versions MCP Forge and generated servers support, oldest first
+pub const SUPPORTED: [ProtocolVersion; 3] = [
+    ProtocolVersion::V_2024_11_05,
+    ProtocolVersion::V_2025_03_26,
+    ProtocolVersion::V_2025_06_18,
+];
+
+/// The version to use with a client that requested `requested`
+///
+/// This is the version rmcp answers `initialize` with: the older of the
+/// requested version and [`LATEST`], whether or not the requested version
+/// is known. Versions are dates, so they compare as strings.
+pub fn negotiate(requested: &ProtocolVersion) -> ProtocolVersion {
+    if *requested < LATEST {
+        requested.clone()
+    } else {
+        LATEST
+    }
+}
+
+/// The version negotiated with the client behind `peer`
+///
+/// Before `initialize` completes nothing is known about the client, so the
+/// oldest supported version is assumed.
+pub fn negotiated(peer: &Peer<RoleServer>) -> ProtocolVersion {
+    peer.peer_info().map_or(SUPPORTED[0].clone(), |info| {
+        negotiate(&info.protocol_version)
+    })
+}
+
+/// Parse a supported protocol version such as `2025-06-18`
+///
+/// # Errors
+///
+/// Returns an error naming the supported versions if `version` is not one.
+pub fn parse(version: &str) -> Result<ProtocolVersion, String> {
+    SUPPORTED
+        .iter()
+        .find(|supported| supported.to_string() == version)
+        .cloned()
+        .ok_or_else(|| {
+            let supported: Vec<String> = SUPPORTED.iter().map(ToString::to_string).collect();
+            format!(
+                "Unsupported protocol version '{}': expected one of {}",
+                version,
+                supported.join(", ")
+            )
+        })
+}
+
+/// Name of the `rmcp::model::ProtocolVersion` constant for `version`
+pub fn const_name(version: &ProtocolVersion) -> String {
+    format!("V_{}", version.to_string().replace('-', "_"))
+}
+
+/// Features available in a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolFeatures {
+    /// Tool results may carry `structuredContent`
+    pub structured_output: bool,
+    /// Tool results may contain `resource_link` content
+    pub resource_links: bool,
+    /// The server may send `elicitation/create` requests
+    pub elicitation: bool,
+}
+
+impl ProtocolFeatures {
+    /// Features of a protocol version
+    pub fn of(version: &ProtocolVersion) -> Self {
+        let since_2025_06_18 = *version >= ProtocolVersion::V_2025_06_18;
+        Self {
+            structured_output: since_2025_06_18,
+            resource_links: since_2025_06_18,
+            elicitation: since_2025_06_18,
+        }
+    }
+
+    /// Features of the session with the client behind `peer`
+    pub fn negotiated(peer: &Peer<RoleServer>) -> Self {
+        Self::of(&negotiated(peer))
+    }
+
+    /// Rewrite a tool result using only what the session supports
+    ///
+    /// Structured content becomes a JSON text block and resource links
+    /// become text naming the resource.
+    pub fn adapt_tool_result(&self, mut result: CallToolResult) -> CallToolResult {
+        if !self.resource_links {
+            for content in &mut result.content {
+                if let RawContent::ResourceLink(link) = &content.raw {
+                    *content = Content::text(format!("Resource: {} ({})", link.name, link.uri));
+                }
+            }
+        }
+        if !self.structured_output
+            && let Some(structured) = result.structured_content.take()
+        {
+            result.content.push(Content::text(structured.to_string()));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::RawResource;
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(
+            negotiate(&ProtocolVersion::V_2024_11_05),
+            ProtocolVersion::V_2024_11_05
+        );
+        assert_eq!(negotiate(&ProtocolVersion::V_2025_06_18), LATEST);
+        // Unknown newer versions get the newest supported one, unknown older
+        // ones are kept, as rmcp answers `initialize` with them
+        let newer: ProtocolVersion = serde_json::from_str("\"2099-01-01\"").unwrap();
+        assert_eq!(negotiate(&newer), LATEST);
+        let older: ProtocolVersion = serde_json::from_str("\"2024-01-01\"").unwrap();
+        assert_eq!(negotiate(&older), older);
+        assert_eq!(
+            ProtocolFeatures::of(&older),
+            ProtocolFeatures::of(&SUPPORTED[0])
+        );
+
+        assert_eq!(parse("2025-03-26"), Ok(ProtocolVersion::V_2025_03_26));
+        assert!(parse("2025-01-01").unwrap_err().contains("2024-11-05"));
+        assert_eq!(const_name(&LATEST), "V_2025_06_18");
+    }
+
+    #[test]
+    fn test_adapt_tool_result() {
+        let result = CallToolResult {
+            content: vec![
+                Content::text("Generated"),
+                Content::resource_link(RawResource::new("forge://workspace/demo", "demo")),
+            ],
+            structured_content: Some(serde_json::json!({"valid": true})),
+            is_error: Some(false),
+            meta: None,
+        };
+
+        let current = ProtocolFeatures::of(&LATEST).adapt_tool_result(result.clone());
+        assert_eq!(current, result);
+
+        let old = ProtocolFeatures::of(&ProtocolVersion::V_2025_03_26);
+        assert!(!old.elicitation);
+        let adapted = old.adapt_tool_result(result);
+        assert_eq!(adapted.structured_content, None);
+        let texts: Vec<_> = adapted
+            .content
+            .iter()
+            .filter_map(|content| content.as_text().map(|text| text.text.as_str()))
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                "Generated",
+                "Resource: demo (forge://workspace/demo)",
+                "{\"valid\":true}"
+            ]
+        );
+    }
+}
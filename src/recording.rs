@@ -0,0 +1,758 @@
+//! Recording and replaying MCP sessions
+//!
+//! [`proxy`] sits between a client and a stdio server, forwards every
+//! JSON-RPC message unchanged and writes it to a JSONL recording: one
+//! [`RecordedMessage`] per line, with the time it was seen and which side
+//! sent it.
+//!
+//! [`replay`] sends the client side of a recording to a (usually newer)
+//! server, one message at a time, and compares each response with the
+//! recorded one. Requests the server makes of the client, such as sampling
+//! or elicitation, are answered with the recorded replies. Fields that
+//! legitimately change between runs are left out of the comparison with
+//! ignore patterns:
+//!
+//! - `/result/serverInfo/version`: a JSON pointer; `*` matches any key or
+//!   array index
+//! - `timestamp`: a key name, ignored at any depth
+//!
+//! The new messages can be written back as an updated recording, which
+//! makes recordings usable as golden files.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{Mutex, mpsc};
+
+/// Default time a server gets to answer each replayed request
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fields ignored by default when comparing responses
+pub const DEFAULT_IGNORE: [&str; 1] = ["/result/serverInfo/version"];
+
+/// Which side of the session sent a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Sent by the client to the server
+    Client,
+    /// Sent by the server to the client
+    Server,
+}
+
+/// One line of a recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    /// Milliseconds since the Unix epoch when the message was seen
+    pub timestamp_ms: u64,
+    /// Which side sent the message
+    pub direction: Direction,
+    /// The JSON-RPC message (a string if the line was not JSON)
+    pub message: Value,
+}
+
+impl RecordedMessage {
+    /// Record `line` as sent by `direction` now
+    pub fn now(direction: Direction, line: &str) -> Self {
+        Self {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            direction,
+            message: serde_json::from_str(line).unwrap_or_else(|_| Value::from(line)),
+        }
+    }
+
+    fn method(&self) -> Option<&str> {
+        self.message.get("method").and_then(Value::as_str)
+    }
+
+    fn id(&self) -> Option<&Value> {
+        self.message.get("id")
+    }
+
+    /// A request expects a response: it has both a method and an id
+    fn is_request(&self) -> bool {
+        self.method().is_some() && self.id().is_some()
+    }
+
+    /// A response answers a request: it has an id and no method
+    fn is_response(&self) -> bool {
+        self.method().is_none() && self.id().is_some()
+    }
+}
+
+/// Parse a JSONL recording
+///
+/// # Errors
+///
+/// Returns an error naming the first line that is not a recorded message.
+pub fn parse_recording(text: &str) -> Result<Vec<RecordedMessage>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("Invalid recording at line {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+/// Serialize messages as a JSONL recording
+pub fn to_jsonl(messages: &[RecordedMessage]) -> String {
+    messages
+        .iter()
+        .filter_map(|message| serde_json::to_string(message).ok())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Forward messages between a client and a server, recording each one
+///
+/// Runs until the server closes its output. When the client closes its
+/// input, the server's input is closed too, so a well-behaved server exits
+/// and ends the session. Returns the number of recorded messages.
+///
+/// # Errors
+///
+/// Returns an error if forwarding or writing the recording fails.
+pub async fn proxy<CR, CW, SR, SW, O>(
+    client_in: CR,
+    client_out: CW,
+    server_in: SW,
+    server_out: SR,
+    recording: O,
+) -> Result<usize, String>
+where
+    CR: AsyncRead + Unpin + Send + 'static,
+    CW: AsyncWrite + Unpin + Send + 'static,
+    SR: AsyncRead + Unpin + Send + 'static,
+    SW: AsyncWrite + Unpin + Send + 'static,
+    O: AsyncWrite + Unpin + Send + 'static,
+{
+    // The recording and the number of messages written to it
+    let recording = std::sync::Arc::new(Mutex::new((recording, 0)));
+    let requests = tokio::spawn(forward(
+        client_in,
+        server_in,
+        Direction::Client,
+        recording.clone(),
+    ));
+    let responses = forward(server_out, client_out, Direction::Server, recording.clone()).await;
+
+    // The server is gone; nothing the client still sends can be answered
+    requests.abort();
+    if let Ok(result) = requests.await {
+        result?;
+    }
+    responses?;
+    let (recording, count) = &mut *recording.lock().await;
+    recording
+        .flush()
+        .await
+        .map_err(|e| format!("Failed to write recording: {}", e))?;
+    Ok(*count)
+}
+
+/// Copy lines from `input` to `output`, recording each one
+async fn forward<R, W, O>(
+    input: R,
+    mut output: W,
+    direction: Direction,
+    recording: std::sync::Arc<Mutex<(O, usize)>>,
+) -> Result<(), String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+    O: AsyncWrite + Unpin,
+{
+    let mut lines = BufReader::new(input).lines();
+    while let Some(line) = lines
+        .next_line()
+        .await
+        .map_err(|e| format!("Failed to read {:?} message: {}", direction, e))?
+    {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::to_string(&RecordedMessage::now(direction, &line))
+            .map_err(|e| format!("Failed to record message: {}", e))?;
+        let (recording, count) = &mut *recording.lock().await;
+        recording
+            .write_all(format!("{}\n", entry).as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write recording: {}", e))?;
+        *count += 1;
+
+        // The peer may be gone; keep recording what the other side sends
+        if output
+            .write_all(format!("{}\n", line).as_bytes())
+            .await
+            .is_err()
+            || output.flush().await.is_err()
+        {
+            tracing::warn!("Could not forward {:?} message", direction);
+        }
+    }
+    // Let the peer see the end of input too
+    let _ = output.shutdown().await;
+    Ok(())
+}
+
+/// How a replayed request compared with the recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExchangeOutcome {
+    /// The response matched the recorded one
+    Matched,
+    /// The response differed from the recorded one
+    Mismatched,
+    /// The server did not answer in time
+    Missing,
+}
+
+/// One difference between a recorded and a replayed response
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Difference {
+    /// JSON pointer of the differing value
+    pub pointer: String,
+    /// The recorded value, absent if the field is new
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Value>,
+    /// The replayed value, absent if the field disappeared
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<Value>,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Value>| {
+            value
+                .as_ref()
+                .map_or_else(|| "(absent)".to_string(), Value::to_string)
+        };
+        write!(
+            f,
+            "{}: expected {}, got {}",
+            if self.pointer.is_empty() {
+                "/"
+            } else {
+                &self.pointer
+            },
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+/// A replayed request and how its response compared
+#[derive(Debug, Clone, Serialize)]
+pub struct Exchange {
+    /// Method of the request
+    pub method: String,
+    /// JSON-RPC id of the request
+    pub id: Value,
+    /// How the response compared
+    pub outcome: ExchangeOutcome,
+    /// Differences from the recorded response
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub differences: Vec<Difference>,
+}
+
+/// Result of replaying a recording
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    /// Number of requests whose responses matched
+    pub matched: usize,
+    /// Number of requests whose responses differed
+    pub mismatched: usize,
+    /// Number of requests the server did not answer
+    pub missing: usize,
+    /// Every replayed request, in recording order
+    pub exchanges: Vec<Exchange>,
+    /// The session as replayed, for updating the recording
+    #[serde(skip)]
+    pub recording: Vec<RecordedMessage>,
+}
+
+impl ReplayReport {
+    /// Whether every response matched
+    pub fn success(&self) -> bool {
+        self.mismatched == 0 && self.missing == 0
+    }
+
+    /// Human-readable summary with every difference
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Replayed {} request(s): {} matched, {} mismatched, {} missing.",
+            self.exchanges.len(),
+            self.matched,
+            self.mismatched,
+            self.missing
+        );
+        for exchange in &self.exchanges {
+            match exchange.outcome {
+                ExchangeOutcome::Matched => continue,
+                ExchangeOutcome::Missing => summary.push_str(&format!(
+                    "\n- {} (id {}): no response",
+                    exchange.method, exchange.id
+                )),
+                ExchangeOutcome::Mismatched => {
+                    summary.push_str(&format!(
+                        "\n- {} (id {}): {} difference(s)",
+                        exchange.method,
+                        exchange.id,
+                        exchange.differences.len()
+                    ));
+                    for difference in &exchange.differences {
+                        summary.push_str(&format!("\n  {}", difference));
+                    }
+                }
+            }
+        }
+        summary
+    }
+}
+
+/// Replay the client side of `recording` against a server
+///
+/// Client messages are sent in order; after each request the replay waits
+/// up to `timeout` for its response and compares it with the recorded one,
+/// skipping fields matched by `ignore`. The server's input is closed at the
+/// end.
+///
+/// # Errors
+///
+/// Returns an error if the server's input cannot be written.
+pub async fn replay<SR, SW>(
+    recording: &[RecordedMessage],
+    mut server_in: SW,
+    server_out: SR,
+    ignore: &[String],
+    timeout: Duration,
+) -> Result<ReplayReport, String>
+where
+    SR: AsyncRead + Unpin + Send + 'static,
+    SW: AsyncWrite + Unpin,
+{
+    let (sender, mut incoming) = mpsc::unbounded_channel();
+    let reader = tokio::spawn(async move {
+        let mut lines = BufReader::new(server_out).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if !line.trim().is_empty() && sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut replies = RecordedReplies::new(recording);
+    let mut report = ReplayReport {
+        matched: 0,
+        mismatched: 0,
+        missing: 0,
+        exchanges: Vec::new(),
+        recording: Vec::new(),
+    };
+    let mut early_responses: HashMap<String, Value> = HashMap::new();
+
+    for message in recording {
+        // Replies to server requests are sent when the server asks
+        if message.direction == Direction::Server || message.is_response() {
+            continue;
+        }
+        send(&mut server_in, &message.message, &mut report.recording).await?;
+        let (Some(method), Some(id)) = (message.method(), message.id()) else {
+            continue;
+        };
+
+        let key = id.to_string();
+        let deadline = tokio::time::Instant::now() + timeout;
+        let response = loop {
+            if let Some(response) = early_responses.remove(&key) {
+                break Some(response);
+            }
+            let line = match tokio::time::timeout_at(deadline, incoming.recv()).await {
+                Ok(Some(line)) => line,
+                Ok(None) | Err(_) => break None,
+            };
+            let received = RecordedMessage::now(Direction::Server, &line);
+            report.recording.push(received.clone());
+            if received.is_request() {
+                let reply = replies.reply_to(&received.message);
+                send(&mut server_in, &reply, &mut report.recording).await?;
+            } else if let Some(received_id) = received.id().filter(|_| received.is_response()) {
+                if *received_id == *id {
+                    break Some(received.message);
+                }
+                early_responses.insert(received_id.to_string(), received.message);
+            }
+        };
+
+        let expected = recording
+            .iter()
+            .find(|recorded| {
+                recorded.direction == Direction::Server
+                    && recorded.is_response()
+                    && recorded.id() == Some(id)
+            })
+            .map(|recorded| &recorded.message);
+        let exchange = match (response, expected) {
+            (None, _) => {
+                report.missing += 1;
+                Exchange {
+                    method: method.to_string(),
+                    id: id.clone(),
+                    outcome: ExchangeOutcome::Missing,
+                    differences: Vec::new(),
+                }
+            }
+            (Some(actual), expected) => {
+                let differences = diff(expected.unwrap_or(&Value::Null), &actual, ignore);
+                let outcome = if differences.is_empty() {
+                    report.matched += 1;
+                    ExchangeOutcome::Matched
+                } else {
+                    report.mismatched += 1;
+                    ExchangeOutcome::Mismatched
+                };
+                Exchange {
+                    method: method.to_string(),
+                    id: id.clone(),
+                    outcome,
+                    differences,
+                }
+            }
+        };
+        report.exchanges.push(exchange);
+    }
+
+    let _ = server_in.shutdown().await;
+    reader.abort();
+    Ok(report)
+}
+
+/// Write one message to the server and add it to the new recording
+async fn send<W: AsyncWrite + Unpin>(
+    server_in: &mut W,
+    message: &Value,
+    recording: &mut Vec<RecordedMessage>,
+) -> Result<(), String> {
+    let line = message.to_string();
+    recording.push(RecordedMessage::now(Direction::Client, &line));
+    server_in
+        .write_all(format!("{}\n", line).as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write to the server: {}", e))?;
+    server_in
+        .flush()
+        .await
+        .map_err(|e| format!("Failed to write to the server: {}", e))
+}
+
+/// The client's recorded replies to server requests, by method and order
+struct RecordedReplies {
+    replies: HashMap<String, VecDeque<Value>>,
+}
+
+impl RecordedReplies {
+    fn new(recording: &[RecordedMessage]) -> Self {
+        let mut replies: HashMap<String, VecDeque<Value>> = HashMap::new();
+        for request in recording
+            .iter()
+            .filter(|message| message.direction == Direction::Server && message.is_request())
+        {
+            let reply = recording.iter().find(|message| {
+                message.direction == Direction::Client
+                    && message.is_response()
+                    && message.id() == request.id()
+            });
+            if let (Some(method), Some(reply)) = (request.method(), reply) {
+                replies
+                    .entry(method.to_string())
+                    .or_default()
+                    .push_back(reply.message.clone());
+            }
+        }
+        Self { replies }
+    }
+
+    /// The next recorded reply to a request like `request`, under its id
+    fn reply_to(&mut self, request: &Value) -> Value {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let method = request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        match self.replies.get_mut(method).and_then(VecDeque::pop_front) {
+            Some(mut reply) => {
+                reply["id"] = id;
+                reply
+            }
+            None => serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": -32601,
+                    "message": format!("No recorded reply to {}", method)
+                }
+            }),
+        }
+    }
+}
+
+/// Differences between `expected` and `actual`, skipping ignored fields
+pub fn diff(expected: &Value, actual: &Value, ignore: &[String]) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    let mut path = Vec::new();
+    diff_at(expected, actual, ignore, &mut path, &mut differences);
+    differences
+}
+
+fn diff_at(
+    expected: &Value,
+    actual: &Value,
+    ignore: &[String],
+    path: &mut Vec<String>,
+    differences: &mut Vec<Difference>,
+) {
+    if is_ignored(path, ignore) {
+        return;
+    }
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            let mut keys: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                path.push(key.clone());
+                match (expected.get(key), actual.get(key)) {
+                    (Some(expected), Some(actual)) => {
+                        diff_at(expected, actual, ignore, path, differences)
+                    }
+                    (expected, actual) if !is_ignored(path, ignore) => {
+                        differences.push(Difference {
+                            pointer: pointer(path),
+                            expected: expected.cloned(),
+                            actual: actual.cloned(),
+                        });
+                    }
+                    _ => {}
+                }
+                path.pop();
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                path.push(index.to_string());
+                diff_at(expected, actual, ignore, path, differences);
+                path.pop();
+            }
+        }
+        (expected, actual) if expected != actual => differences.push(Difference {
+            pointer: pointer(path),
+            expected: Some(expected.clone()),
+            actual: Some(actual.clone()),
+        }),
+        _ => {}
+    }
+}
+
+/// JSON pointer of a path of keys
+fn pointer(path: &[String]) -> String {
+    path.iter().fold(String::new(), |pointer, key| {
+        format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"))
+    })
+}
+
+/// Whether `path` matches an ignore pattern
+fn is_ignored(path: &[String], ignore: &[String]) -> bool {
+    ignore
+        .iter()
+        .any(|pattern| match pattern.strip_prefix('/') {
+            Some(pattern) => {
+                let segments: Vec<String> = pattern
+                    .split('/')
+                    .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                    .collect();
+                segments.len() == path.len()
+                    && segments
+                        .iter()
+                        .zip(path)
+                        .all(|(segment, key)| segment == "*" || segment == key)
+            }
+            None => path.last() == Some(pattern),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MCPForgeServer;
+    use rmcp::ServiceExt;
+    use serde_json::json;
+
+    fn ignore(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_diff_ignores_volatile_fields() {
+        let expected = json!({"result": {
+            "serverInfo": {"name": "forge", "version": "0.1.0"},
+            "items": [{"uri": "a", "timestamp": 1}, {"uri": "b/c"}]
+        }});
+        let actual = json!({"result": {
+            "serverInfo": {"name": "forge", "version": "0.2.0"},
+            "items": [{"uri": "a", "timestamp": 2}, {"uri": "b/d", "size": 3}]
+        }});
+
+        let differences = diff(
+            &expected,
+            &actual,
+            &ignore(&["/result/serverInfo/version", "timestamp"]),
+        );
+        assert_eq!(
+            differences
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "/result/items/1/size: expected (absent), got 3",
+                "/result/items/1/uri: expected \"b/c\", got \"b/d\""
+            ]
+        );
+
+        let ignored = ignore(&[
+            "/result/items/*/uri",
+            "/result/items/*/size",
+            "version",
+            "timestamp",
+        ]);
+        assert!(diff(&expected, &actual, &ignored).is_empty());
+        assert_eq!(diff(&json!([1]), &json!([1, 2]), &[]).len(), 1);
+    }
+
+    #[test]
+    fn test_parse_recording() {
+        let messages = [
+            RecordedMessage::now(
+                Direction::Client,
+                r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#,
+            ),
+            RecordedMessage::now(Direction::Server, "not json"),
+        ];
+        let parsed = parse_recording(&to_jsonl(&messages)).unwrap();
+        assert_eq!(parsed, messages);
+        assert!(parsed[0].is_request());
+        assert_eq!(parsed[1].message, "not json");
+
+        let error = parse_recording("\n{\"direction\": \"client\"}").unwrap_err();
+        assert!(error.starts_with("Invalid recording at line 2"));
+    }
+
+    /// Record a session with an in-process server and replay it against another
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let (client_in, mut client_writer) = tokio::io::duplex(64 * 1024);
+        let (mut client_reader, client_out) = tokio::io::duplex(64 * 1024);
+        let (proxy_server_side, server_io) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            let service = MCPForgeServer::default().serve(server_io).await?;
+            service.waiting().await?;
+            anyhow::Ok(())
+        });
+        let (server_out, server_in) = tokio::io::split(proxy_server_side);
+        let (recording_reader, recording_writer) = tokio::io::duplex(64 * 1024);
+        let proxied = tokio::spawn(proxy(
+            client_in,
+            client_out,
+            server_in,
+            server_out,
+            recording_writer,
+        ));
+
+        let session = [
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {"name": "recorder", "version": "1.0"}
+            }}),
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "prompts/get", "params": {"name": "missing"}}),
+        ];
+        let mut responses = BufReader::new(&mut client_reader).lines();
+        for message in &session {
+            client_writer
+                .write_all(format!("{}\n", message).as_bytes())
+                .await
+                .unwrap();
+            if message.get("id").is_some() {
+                responses.next_line().await.unwrap().unwrap();
+            }
+        }
+        drop(client_writer);
+        assert_eq!(proxied.await.unwrap().unwrap(), 5);
+
+        let mut text = String::new();
+        let mut recording_lines = BufReader::new(recording_reader).lines();
+        while let Ok(Some(line)) = recording_lines.next_line().await {
+            text.push_str(&line);
+            text.push('\n');
+        }
+        let mut recording = parse_recording(&text).unwrap();
+        assert_eq!(recording.len(), 5);
+        assert_eq!(recording[1].direction, Direction::Server);
+
+        // Pretend the recorded server was older and answered differently
+        recording[1].message["result"]["serverInfo"]["version"] = json!("0.0.0");
+        recording[4].message["error"]["code"] = json!(-32600);
+
+        let (replay_side, server_io) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            let service = MCPForgeServer::default().serve(server_io).await?;
+            service.waiting().await?;
+            anyhow::Ok(())
+        });
+        let (server_out, server_in) = tokio::io::split(replay_side);
+        let ignored = ignore(&DEFAULT_IGNORE);
+        let report = replay(&recording, server_in, server_out, &ignored, DEFAULT_TIMEOUT)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            (report.matched, report.mismatched, report.missing),
+            (1, 1, 0)
+        );
+        assert_eq!(
+            report.summary(),
+            "Replayed 2 request(s): 1 matched, 1 mismatched, 0 missing.\n\
+             - prompts/get (id 2): 1 difference(s)\n  \
+             /error/code: expected -32600, got -32602"
+        );
+        assert_eq!(report.recording.len(), 5);
+    }
+
+    #[test]
+    fn test_recorded_replies() {
+        let recording = [
+            RecordedMessage::now(
+                Direction::Server,
+                r#"{"jsonrpc":"2.0","id":0,"method":"sampling/createMessage","params":{}}"#,
+            ),
+            RecordedMessage::now(
+                Direction::Client,
+                r#"{"jsonrpc":"2.0","id":0,"result":{"model":"m"}}"#,
+            ),
+        ];
+        let mut replies = RecordedReplies::new(&recording);
+        let request = json!({"jsonrpc": "2.0", "id": 7, "method": "sampling/createMessage"});
+        assert_eq!(
+            replies.reply_to(&request),
+            json!({"jsonrpc": "2.0", "id": 7, "result": {"model": "m"}})
+        );
+        assert_eq!(replies.reply_to(&request)["error"]["code"], -32601);
+    }
+}
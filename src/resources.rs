@@ -9,6 +9,8 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 
 /// Represents a resource available through MCP
 ///
@@ -24,11 +26,39 @@ pub struct Resource {
     /// Resource MIME type
     pub mime_type: String,
     /// Resource content
-    pub content: String,
+    pub content: ResourceContent,
+}
+
+/// Content of a resource
+///
+/// Text content is returned to clients as `text` resource contents, binary
+/// content as base64-encoded `blob` resource contents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceContent {
+    /// UTF-8 text content
+    Text(String),
+    /// Raw binary content
+    Blob(Vec<u8>),
+}
+
+impl ResourceContent {
+    /// Size of the content in bytes
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Text(text) => text.len(),
+            Self::Blob(bytes) => bytes.len(),
+        }
+    }
+
+    /// Whether the content is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 impl Resource {
-    /// Create a new resource
+    /// Create a new text resource
     pub fn new(
         uri: impl Into<String>,
         name: impl Into<String>,
@@ -39,7 +69,38 @@ impl Resource {
             uri: uri.into(),
             name: name.into(),
             mime_type: mime_type.into(),
-            content: content.into(),
+            content: ResourceContent::Text(content.into()),
+        }
+    }
+
+    /// Create a new binary resource
+    pub fn blob(
+        uri: impl Into<String>,
+        name: impl Into<String>,
+        mime_type: impl Into<String>,
+        content: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            uri: uri.into(),
+            name: name.into(),
+            mime_type: mime_type.into(),
+            content: ResourceContent::Blob(content.into()),
+        }
+    }
+
+    /// Get the text content, or `None` for binary resources
+    pub fn text(&self) -> Option<&str> {
+        match &self.content {
+            ResourceContent::Text(text) => Some(text),
+            ResourceContent::Blob(_) => None,
+        }
+    }
+
+    /// Consume the resource and return its text content, or `None` for binary resources
+    pub fn into_text(self) -> Option<String> {
+        match self.content {
+            ResourceContent::Text(text) => Some(text),
+            ResourceContent::Blob(_) => None,
         }
     }
 }
@@ -125,6 +186,17 @@ pub fn get_available_resources() -> HashMap<String, Resource> {
         ),
     );
 
+    // tests.rs template (integration tests of generated projects)
+    resources.insert(
+        "template/tests-rs".to_string(),
+        Resource::new(
+            "forge://templates/tests.rs",
+            "tests.rs Template",
+            "text/plain",
+            include_str!("../templates/tests.rs.template"),
+        ),
+    );
+
     // advanced-tool.rs template
     resources.insert(
         "template/advanced-tool-rs".to_string(),
@@ -158,9 +230,58 @@ pub fn get_available_resources() -> HashMap<String, Resource> {
         ),
     );
 
+    // MCP Forge icon
+    resources.insert(
+        "asset/icon-png".to_string(),
+        Resource::blob(
+            "forge://assets/icon.png",
+            "MCP Forge Icon",
+            "image/png",
+            include_bytes!("../assets/icon.png").as_slice(),
+        ),
+    );
+
     resources
 }
 
+/// Get all available resources, with on-disk template overrides applied
+///
+/// Every `<name>.template` file in `template_dir` replaces the built-in
+/// template with the same file name (e.g. `main.rs.template` overrides
+/// `forge://templates/main.rs`), or adds a new template resource if no
+/// built-in template matches. A missing directory yields the built-in set.
+pub fn get_resources_with_overrides(template_dir: Option<&Path>) -> HashMap<String, Resource> {
+    let mut resources = get_available_resources();
+    if let Some(dir) = template_dir {
+        resources.extend(load_template_overrides(dir));
+    }
+    resources
+}
+
+/// Load template overrides from a directory
+fn load_template_overrides(dir: &Path) -> HashMap<String, Resource> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let name = file_name.strip_suffix(".template")?.to_string();
+            let content = fs::read_to_string(entry.path()).ok()?;
+            let key = format!("template/{}", name.to_lowercase().replace('.', "-"));
+            let resource = Resource::new(
+                format!("forge://templates/{}", name),
+                format!("{} Template (override)", name),
+                "text/plain",
+                content,
+            );
+            Some((key, resource))
+        })
+        .collect()
+}
+
 /// Get a specific resource by key
 pub fn get_resource(key: &str) -> Option<Resource> {
     get_available_resources().get(key).cloned()
@@ -180,6 +301,25 @@ mod tests {
         let resource = Resource::new("test://uri", "Test Resource", "text/plain", "test content");
         assert_eq!(resource.uri, "test://uri");
         assert_eq!(resource.name, "Test Resource");
+        assert_eq!(resource.text(), Some("test content"));
+    }
+
+    #[test]
+    fn test_blob_resource() {
+        let resource = Resource::blob("test://blob", "Blob", "image/png", vec![0x89, 0x50]);
+        assert_eq!(resource.content, ResourceContent::Blob(vec![0x89, 0x50]));
+        assert_eq!(resource.content.len(), 2);
+        assert!(resource.text().is_none());
+    }
+
+    #[test]
+    fn test_icon_resource_is_png() {
+        let icon = get_resource("asset/icon-png").unwrap();
+        assert_eq!(icon.mime_type, "image/png");
+        match icon.content {
+            ResourceContent::Blob(bytes) => assert!(bytes.starts_with(b"\x89PNG")),
+            ResourceContent::Text(_) => panic!("icon should be binary"),
+        }
     }
 
     #[test]
@@ -191,6 +331,28 @@ mod tests {
         assert!(resources.contains_key("template/lib-rs"));
     }
 
+    #[test]
+    fn test_template_overrides() {
+        let dir = std::env::temp_dir().join("mcp_forge_template_overrides");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("main.rs.template"), "fn main() {}").unwrap();
+        fs::write(dir.join("extra.rs.template"), "// extra").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let resources = get_resources_with_overrides(Some(&dir));
+        let main_rs = &resources["template/main-rs"];
+        assert_eq!(main_rs.uri, "forge://templates/main.rs");
+        assert_eq!(main_rs.text(), Some("fn main() {}"));
+        assert_eq!(
+            resources["template/extra-rs"].uri,
+            "forge://templates/extra.rs"
+        );
+        assert_eq!(resources.len(), get_available_resources().len() + 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_list_resource_keys() {
         let keys = list_resource_keys();
@@ -1,14 +1,29 @@
 //! MCP Forge Server Implementation (v0.8.1 compatible)
 //!
-//! Refactored implementation using #[tool_router] and #[tool_handler] macros
-//! for rmcp v0.8.1 compatibility.
+//! Refactored implementation using the #[tool_router] macro for rmcp v0.8.1
+//! compatibility. Tool calls are routed by hand so results can be adapted to
+//! the protocol version negotiated with each client (see [`crate::protocol`]).
 
+use crate::check::{self, CheckReport};
+use crate::config::ForgeConfig;
+use crate::drafting;
+use crate::elicitation;
+use crate::logging::{self, ClientLoggers};
+use crate::protocol::{self, ProtocolFeatures};
+use crate::subscriptions::{SubscriberId, Subscriptions, next_subscriber_id};
 use crate::tool_executor;
+use crate::workspace::{WORKSPACE_URI_PREFIX, Workspace, WorkspaceError};
+use base64::{Engine as _, prelude::BASE64_STANDARD};
 use rmcp::{
-    ErrorData as McpError, RoleServer, ServerHandler, handler::server::router::tool::ToolRouter,
-    model::*, schemars, service::RequestContext, tool, tool_handler, tool_router,
+    ErrorData as McpError, RoleServer, ServerHandler,
+    handler::server::{router::tool::ToolRouter, tool::ToolCallContext},
+    model::*,
+    schemars,
+    service::{NotificationContext, RequestContext},
+    tool, tool_router,
 };
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Request parameters for project generation
 ///
@@ -22,6 +37,13 @@ pub struct GenerateProjectRequest {
     /// Project description (optional, defaults to "A new MCP server project")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Run `cargo check` on the generated project and return its diagnostics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify: Option<bool>,
+    /// Newest MCP protocol version the generated server implements:
+    /// "2024-11-05", "2025-03-26" or "2025-06-18" (default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
 }
 
 /// Request parameters for tool generation
@@ -35,6 +57,9 @@ pub struct GenerateToolRequest {
     pub tool_name: String,
     /// Tool description (displayed in MCP tool list and documentation)
     pub description: String,
+    /// Ask the client's model to draft the implementation (needs sampling support)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draft_implementation: Option<bool>,
 }
 
 /// Request parameters for resource generation
@@ -48,6 +73,7 @@ pub struct GenerateResourceRequest {
     pub resource_name: String,
     /// Type of resource content: "text", "json", or "binary"
     #[serde(rename = "type")]
+    #[schemars(extend("enum" = ["text", "binary", "json"]))]
     pub resource_type: String,
     /// Resource description (optional, displayed in resource listings)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -66,34 +92,189 @@ pub struct GenerateReadmeRequest {
     /// Project description (optional, used in README header)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// Output path for README.md file (optional, defaults to "README.md")
+    /// Workspace-relative output path for README.md (optional, defaults to "README.md")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_path: Option<String>,
 }
 
 /// Request parameters for manifest validation
 ///
-/// Validates an MCP server manifest file (typically claude_desktop_config.json).
-/// Checks for required fields, proper JSON structure, and schema compliance.
+/// Validates an MCP manifest file: claude_desktop_config.json, an MCP registry
+/// server.json, or a desktop extension manifest.json, written in JSON, YAML or
+/// TOML. Checks for required fields, syntax, and schema compliance.
 /// Returns detailed validation errors if issues are found.
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ValidateManifestRequest {
-    /// Contents of the manifest file in JSON format (as a string)
-    pub manifest_content: String,
+    /// Contents of the manifest file (as a string); required unless `path` is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_content: Option<String>,
+    /// Path of the manifest file within the workspace, instead of `manifest_content`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Manifest syntax: "auto" (default, from the file extension or content),
+    /// "json", "yaml" or "toml"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub syntax: Option<String>,
+    /// Manifest format: "auto" (default), "claude_desktop_config", "server_json",
+    /// "desktop_extension" or "server_manifest"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Apply automatic fixes and return the corrected manifest
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<bool>,
+}
+
+/// Request parameters for running the conformance suite against a server
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TestServerRequest {
+    /// Executable that starts the server over stdio
+    pub command: String,
+    /// Command-line arguments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Seconds the server gets for each check (default 10, at most 120)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Also return the report as JUnit XML
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub junit: Option<bool>,
+}
+
+/// Request parameters for fuzzing the tools of a server
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FuzzToolsRequest {
+    /// Executable that starts the server over stdio
+    pub command: String,
+    /// Command-line arguments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Tools to fuzz (default: every listed tool)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Arguments passed unchanged to every tool that declares them, such as
+    /// {"verify": false}
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Random cases per tool, on top of the systematic ones (default 20, at most 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cases: Option<u64>,
+    /// Seed the cases are generated from, to repeat a run (default: random)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Seconds the server gets to answer each call (default 5, at most 120)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Request parameters for verifying a manifest against the servers it launches
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct VerifyManifestRequest {
+    /// Contents of the manifest file (as a string); required unless `path` is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_content: Option<String>,
+    /// Path of the manifest file within the workspace, instead of `manifest_content`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Manifest syntax: "auto" (default), "json", "yaml" or "toml"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub syntax: Option<String>,
+    /// Manifest format: "auto" (default), "claude_desktop_config" or "desktop_extension"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Seconds each server gets to start and answer (default 10, at most 120)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Request parameters for checking that a project compiles
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CheckProjectRequest {
+    /// Path of the project directory within the workspace
+    pub path: String,
 }
 
 /// MCP Forge Server implementation using macro-based routing
 #[derive(Debug, Clone)]
 pub struct MCPForgeServer {
     tool_router: ToolRouter<Self>,
+    /// Defaults for generated projects, template overrides and workspace
+    config: ForgeConfig,
+    /// Sandboxed directory projects are generated into
+    workspace: Workspace,
+    /// Identifier of the client session served by this instance
+    subscriber_id: SubscriberId,
+    /// Resource subscriptions shared across sessions
+    subscriptions: Subscriptions,
+    /// Clients receiving log messages, shared across sessions
+    loggers: ClientLoggers,
 }
 
 #[tool_router]
 impl MCPForgeServer {
     /// Create a new MCP Forge Server
-    pub fn new() -> Self {
+    ///
+    /// Generated projects use the author, license and rmcp version from
+    /// `config`; its workspace root and template directory are served as
+    /// resources.
+    pub fn new(config: ForgeConfig) -> Self {
+        let subscriptions = Subscriptions::new();
+        if let Some(dir) = &config.template_dir {
+            subscriptions.watch_templates(dir);
+        }
         Self {
             tool_router: Self::tool_router(),
+            workspace: Workspace::new(&config.workspace_root),
+            config,
+            subscriber_id: next_subscriber_id(),
+            subscriptions,
+            loggers: logging::client_loggers().clone(),
+        }
+    }
+
+    /// Serve templates from `dir` in place of the built-in ones
+    ///
+    /// Changes to the directory are reported to subscribed clients.
+    pub fn with_template_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        self.subscriptions.watch_templates(&dir);
+        self.config.template_dir = Some(dir);
+        self
+    }
+
+    /// Generate projects into `root` and expose it as `forge://workspace/...`
+    pub fn with_workspace_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.config.workspace_root = root.into();
+        self.workspace = Workspace::new(&self.config.workspace_root);
+        self
+    }
+
+    /// Register clients that set a logging level in `loggers`
+    ///
+    /// Defaults to the process-wide registry read by [`logging::layer`].
+    pub fn with_client_loggers(mut self, loggers: ClientLoggers) -> Self {
+        self.loggers = loggers;
+        self
+    }
+
+    /// Identifier of the client session served by this instance
+    pub fn session_id(&self) -> SubscriberId {
+        self.subscriber_id
+    }
+
+    /// Configuration the server was created with
+    pub fn config(&self) -> &ForgeConfig {
+        &self.config
+    }
+
+    /// Clone this server for a new client session
+    ///
+    /// The clone shares templates, workspace and resource subscriptions but
+    /// has its own subscriber id, so each session of a multi-client
+    /// transport receives only the notifications it subscribed to.
+    pub fn for_session(&self) -> Self {
+        Self {
+            subscriber_id: next_subscriber_id(),
+            ..self.clone()
         }
     }
 
@@ -105,28 +286,70 @@ impl MCPForgeServer {
     ///
     /// # Errors
     ///
-    /// Returns an error if:
+    /// Returns an invalid-params error if:
     /// - project_name is empty or contains invalid characters
+    /// - protocol_version is not supported
     /// - Project directory already exists
-    /// - File system operations fail (permissions, disk space)
+    ///
+    /// Returns an internal error if file system operations fail
+    /// (permissions, disk space).
     #[tool(description = "Generate a new MCP server project structure")]
     async fn generate_project(
         &self,
         rmcp::handler::server::wrapper::Parameters(req): rmcp::handler::server::wrapper::Parameters<
             GenerateProjectRequest,
         >,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         tracing::info!("Generating new MCP project: {}", req.project_name);
 
+        let target = serde_json::json!({
+            "project_name": req.project_name,
+            "protocol_version": req.protocol_version,
+            "workspace_root": self.workspace.root(),
+        });
+        tool_executor::validate_project_args(&target)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        let req = if req.description.is_none() {
+            let message = format!("Describe the MCP server '{}'", req.project_name);
+            elicitation::request_fields(&context, req, &["description"], message).await
+        } else {
+            req
+        };
+
         let args = serde_json::json!({
             "project_name": req.project_name,
             "description": req.description.as_deref().unwrap_or("A new MCP server project"),
+            "verify": req.verify.unwrap_or(false),
+            "protocol_version": req.protocol_version,
         });
 
-        match tool_executor::execute_tool("generate_project", &args).await {
-            Ok(result) => {
+        let (execution, progress) = execution_context(&context);
+        let result = tool_executor::generate_project(&args, &self.config, &execution).await;
+        drop(execution);
+        if let Some(progress) = progress {
+            let _ = progress.await;
+        }
+
+        match result {
+            Ok(project) => {
                 tracing::info!("Project generation completed successfully");
-                Ok(CallToolResult::success(vec![Content::text(result)]))
+                self.subscriptions
+                    .watch_project(project.path.clone(), &req.project_name);
+                let mut link = RawResource::new(
+                    Workspace::uri_for(&req.project_name),
+                    req.project_name.clone(),
+                );
+                link.description = Some("The generated project".to_string());
+                link.mime_type = Some("application/json".to_string());
+
+                let mut result = match &project.check {
+                    Some(check) => check_result(project.summary(), check)?,
+                    None => CallToolResult::success(vec![Content::text(project.message)]),
+                };
+                result.content.push(Content::resource_link(link));
+                Ok(result)
             }
             Err(e) => {
                 tracing::error!("Project generation failed: {}", e);
@@ -146,7 +369,7 @@ impl MCPForgeServer {
     ///
     /// # Errors
     ///
-    /// Returns an error if:
+    /// Returns an invalid-params error if:
     /// - tool_name is empty or contains invalid characters
     /// - description is empty
     #[tool(description = "Generate code for a new MCP tool")]
@@ -155,22 +378,45 @@ impl MCPForgeServer {
         rmcp::handler::server::wrapper::Parameters(req): rmcp::handler::server::wrapper::Parameters<
             GenerateToolRequest,
         >,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         tracing::info!("Generating tool: {} - {}", req.tool_name, req.description);
+        tool_executor::validate_identifier(&req.tool_name, "Tool name")
+            .map_err(|e| McpError::invalid_params(e, None))?;
 
+        let draft = match req.draft_implementation {
+            Some(true) => {
+                Some(drafting::draft_body(&context, &req.tool_name, &req.description).await)
+            }
+            _ => None,
+        };
         let args = serde_json::json!({
             "tool_name": req.tool_name,
             "description": req.description,
+            "draft_body": draft.as_ref().and_then(|draft| draft.as_ref().ok()),
         });
 
         match tool_executor::execute_tool("generate_tool", &args).await {
             Ok(result) => {
                 tracing::info!("Tool generation completed: {}", req.tool_name);
-                Ok(CallToolResult::success(vec![Content::text(result)]))
+                match draft {
+                    Some(Ok(_)) => draft_result(result, &context).await,
+                    Some(Err(reason)) => {
+                        tracing::warn!("No draft implementation: {}", reason);
+                        Ok(CallToolResult::success(vec![
+                            Content::text(result),
+                            Content::text(format!(
+                                "No draft implementation ({}); the placeholder body was generated.",
+                                reason
+                            )),
+                        ]))
+                    }
+                    None => Ok(CallToolResult::success(vec![Content::text(result)])),
+                }
             }
             Err(e) => {
                 tracing::error!("Tool generation failed for {}: {}", req.tool_name, e);
-                Err(McpError::internal_error(
+                Err(McpError::invalid_params(
                     format!("Failed to generate tool: {}", e),
                     None,
                 ))
@@ -185,7 +431,7 @@ impl MCPForgeServer {
     ///
     /// # Errors
     ///
-    /// Returns an error if:
+    /// Returns an invalid-params error if:
     /// - resource_name is empty or contains invalid characters
     /// - resource_type is not one of: text, json, binary
     #[tool(description = "Generate code for a new MCP resource")]
@@ -194,6 +440,7 @@ impl MCPForgeServer {
         rmcp::handler::server::wrapper::Parameters(req): rmcp::handler::server::wrapper::Parameters<
             GenerateResourceRequest,
         >,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         tracing::info!(
             "Generating resource: {} (type: {})",
@@ -201,6 +448,16 @@ impl MCPForgeServer {
             req.resource_type
         );
 
+        let req = if tool_executor::RESOURCE_TYPES.contains(&req.resource_type.as_str()) {
+            req
+        } else {
+            let message = format!(
+                "'{}' is not a resource type. Choose the type of resource '{}'",
+                req.resource_type, req.resource_name
+            );
+            elicitation::request_fields(&context, req, &["type"], message).await
+        };
+
         let args = serde_json::json!({
             "resource_name": req.resource_name,
             "resource_type": req.resource_type,
@@ -218,7 +475,7 @@ impl MCPForgeServer {
                     req.resource_name,
                     e
                 );
-                Err(McpError::internal_error(
+                Err(McpError::invalid_params(
                     format!("Failed to generate resource: {}", e),
                     None,
                 ))
@@ -234,10 +491,9 @@ impl MCPForgeServer {
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - project_name is empty
-    /// - output_path is not writable
-    /// - File system operations fail
+    /// Returns an invalid-params error if project_name is not a valid
+    /// project name or output_path escapes the workspace or is a directory,
+    /// and an internal error if the file cannot be written.
     #[tool(description = "Generate README.md with MCP server setup instructions")]
     async fn generate_readme(
         &self,
@@ -252,10 +508,15 @@ impl MCPForgeServer {
             output_path
         );
 
+        tool_executor::validate_project_name(&req.project_name)
+            .and_then(|()| tool_executor::readme_path(&self.workspace, output_path))
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
         let args = serde_json::json!({
             "project_name": req.project_name,
             "description": req.description.as_deref().unwrap_or("A new MCP server project"),
             "output_path": output_path,
+            "workspace_root": self.workspace.root(),
         });
 
         match tool_executor::execute_tool("generate_readme", &args).await {
@@ -275,17 +536,17 @@ impl MCPForgeServer {
 
     /// Validate an MCP server manifest file
     ///
-    /// Checks manifest.json or claude_desktop_config.json for correctness.
+    /// Checks claude_desktop_config.json, server.json or a desktop extension
+    /// manifest.json for correctness, auto-detecting the format unless given.
     /// Validates required fields, JSON schema compliance, and URI formatting.
     /// Provides detailed error messages for any issues found.
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - manifest_content is not valid JSON
-    /// - Required fields are missing
-    /// - Schema validation fails
-    /// - Manifest structure is incorrect
+    /// Returns an invalid-params error if neither `manifest_content` nor a
+    /// readable `path` inside the workspace is given, or if `syntax` or
+    /// `format` is unknown. An invalid manifest is not a protocol error: the
+    /// diagnostics are returned as structured content with `is_error` set.
     #[tool(description = "Validate an MCP server manifest file")]
     async fn validate_manifest(
         &self,
@@ -297,33 +558,338 @@ impl MCPForgeServer {
 
         let args = serde_json::json!({
             "manifest_content": req.manifest_content,
+            "path": req.path,
+            "workspace_root": self.workspace.root(),
+            "syntax": req.syntax.as_deref().unwrap_or("auto"),
+            "format": req.format.as_deref().unwrap_or("auto"),
+            "fix": req.fix.unwrap_or(false),
         });
 
-        match tool_executor::execute_tool("validate_manifest", &args).await {
-            Ok(result) => {
-                tracing::info!("Manifest validation succeeded");
-                Ok(CallToolResult::success(vec![Content::text(result)]))
+        let (valid, summary, structured) = if req.fix.unwrap_or(false) {
+            let result = tool_executor::fix_manifest(&args)
+                .map_err(|e| McpError::invalid_params(e, None))?;
+            tracing::info!("Applied {} manifest fix(es)", result.fixes.len());
+            (
+                result.report.valid,
+                result.summary(),
+                serde_json::to_value(&result),
+            )
+        } else {
+            let report = tool_executor::validate_manifest_report(&args)
+                .map_err(|e| McpError::invalid_params(e, None))?;
+            (
+                report.valid,
+                report.summary(),
+                serde_json::to_value(&report),
+            )
+        };
+        if valid {
+            tracing::info!("Manifest validation succeeded");
+        } else {
+            tracing::warn!("Manifest validation found errors");
+        }
+
+        let structured = structured.map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize report: {}", e), None)
+        })?;
+        Ok(CallToolResult {
+            content: vec![Content::text(summary)],
+            structured_content: Some(structured),
+            is_error: Some(!valid),
+            meta: None,
+        })
+    }
+
+    /// Launch the servers a manifest configures and check them against it
+    #[tool(
+        description = "Launch each server configured by a claude_desktop_config.json or desktop extension manifest over stdio, run initialize and tools/resources/prompts list, and report protocol version, capabilities and mismatches with the manifest"
+    )]
+    async fn verify_manifest(
+        &self,
+        rmcp::handler::server::wrapper::Parameters(req): rmcp::handler::server::wrapper::Parameters<
+            VerifyManifestRequest,
+        >,
+        cancellation: tokio_util::sync::CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!("Verifying manifest against its servers");
+
+        let args = serde_json::json!({
+            "manifest_content": req.manifest_content,
+            "path": req.path,
+            "workspace_root": self.workspace.root(),
+            "syntax": req.syntax.as_deref().unwrap_or("auto"),
+            "format": req.format.as_deref().unwrap_or("auto"),
+            "timeout_secs": req.timeout_secs,
+        });
+
+        // Dropping the verification stops the launched servers
+        let report = tokio::select! {
+            report = tool_executor::verify_manifest_report(&args) => report,
+            () = cancellation.cancelled() => {
+                tracing::info!("Manifest verification cancelled");
+                return Err(McpError::internal_error("Cancelled by the client", None));
             }
-            Err(e) => {
-                tracing::warn!("Manifest validation failed: {}", e);
-                Err(McpError::internal_error(
-                    format!("Manifest validation error: {}", e),
-                    None,
-                ))
+        };
+        let report = match report {
+            Ok(report) => report,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        if report.valid {
+            tracing::info!("Verified {} server(s)", report.servers.len());
+        } else {
+            tracing::warn!("Manifest verification found {} error(s)", report.errors);
+        }
+
+        let structured = serde_json::to_value(&report).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize report: {}", e), None)
+        })?;
+        Ok(CallToolResult {
+            content: vec![Content::text(report.summary())],
+            structured_content: Some(structured),
+            is_error: Some(!report.valid),
+            meta: None,
+        })
+    }
+
+    /// Check that a project in the workspace compiles
+    #[tool(
+        description = "Run cargo check --offline on a project in the workspace and return the compiler errors and warnings as structured diagnostics"
+    )]
+    async fn check_project(
+        &self,
+        rmcp::handler::server::wrapper::Parameters(req): rmcp::handler::server::wrapper::Parameters<
+            CheckProjectRequest,
+        >,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!("Checking project: {}", req.path);
+
+        let args = serde_json::json!({
+            "path": req.path,
+            "workspace_root": self.workspace.root(),
+        });
+
+        let (execution, progress) = execution_context(&context);
+        let result = tool_executor::check_project_report(&args, &execution).await;
+        drop(execution);
+        if let Some(progress) = progress {
+            let _ = progress.await;
+        }
+
+        match result {
+            Ok(check) => check_result(check.summary(), &check),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e)])),
+        }
+    }
+
+    /// Run the conformance suite against an MCP server
+    #[tool(
+        description = "Launch an MCP server over stdio and check its initialize handshake, capabilities, tool schemas and names, error codes for unknown tools/resources/prompts, pagination and shutdown; returns a pass/fail report, optionally as JUnit XML"
+    )]
+    async fn test_server(
+        &self,
+        rmcp::handler::server::wrapper::Parameters(req): rmcp::handler::server::wrapper::Parameters<
+            TestServerRequest,
+        >,
+        cancellation: tokio_util::sync::CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!("Testing server: {}", req.command);
+
+        let args = serde_json::json!({
+            "command": req.command,
+            "args": req.args,
+            "workspace_root": self.workspace.root(),
+            "timeout_secs": req.timeout_secs,
+        });
+
+        // Dropping the suite stops the server
+        let report = tokio::select! {
+            report = tool_executor::conformance_report(&args) => report,
+            () = cancellation.cancelled() => {
+                tracing::info!("Conformance run cancelled");
+                return Err(McpError::internal_error("Cancelled by the client", None));
+            }
+        };
+        let report = match report {
+            Ok(report) => report,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+        };
+        if !report.success() {
+            tracing::warn!("{} conformance check(s) failed", report.failed);
+        }
+
+        let structured = serde_json::to_value(&report).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize report: {}", e), None)
+        })?;
+        let mut content = vec![Content::text(report.summary())];
+        if req.junit.unwrap_or(false) {
+            content.push(Content::text(report.junit_xml()));
+        }
+        Ok(CallToolResult {
+            content,
+            structured_content: Some(structured),
+            is_error: Some(!report.success()),
+            meta: None,
+        })
+    }
+
+    /// Fuzz the tools of an MCP server
+    #[tool(
+        description = "Launch an MCP server over stdio and call each tool with arguments generated from its input schema: missing required fields, wrong types, boundary and path-traversal strings, extreme numbers, huge payloads and random values; fails on panics, hangs, errors other than invalid params, and invalid arguments that are accepted. Tools really run, so side effects happen in the workspace"
+    )]
+    async fn fuzz_tools(
+        &self,
+        rmcp::handler::server::wrapper::Parameters(req): rmcp::handler::server::wrapper::Parameters<
+            FuzzToolsRequest,
+        >,
+        cancellation: tokio_util::sync::CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!("Fuzzing server: {}", req.command);
+
+        let args = serde_json::json!({
+            "command": req.command,
+            "args": req.args,
+            "workspace_root": self.workspace.root(),
+            "tools": req.tools,
+            "fixed": req.fixed,
+            "cases": req.cases,
+            "seed": req.seed,
+            "timeout_secs": req.timeout_secs,
+        });
+
+        // Dropping the run stops the server
+        let report = tokio::select! {
+            report = tool_executor::fuzz_report(&args) => report,
+            () = cancellation.cancelled() => {
+                tracing::info!("Fuzzing cancelled");
+                return Err(McpError::internal_error("Cancelled by the client", None));
             }
+        };
+        let report = report.map_err(|e| McpError::invalid_params(e, None))?;
+        if !report.success() {
+            tracing::warn!("Fuzzing found {} failure(s)", report.failures.len());
         }
+
+        let structured = serde_json::to_value(&report).map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize report: {}", e), None)
+        })?;
+        Ok(CallToolResult {
+            content: vec![Content::text(report.summary())],
+            structured_content: Some(structured),
+            is_error: Some(!report.success()),
+            meta: None,
+        })
+    }
+}
+
+/// Tool result carrying a check report as structured content
+fn check_result(summary: String, check: &CheckReport) -> Result<CallToolResult, McpError> {
+    if !check.success {
+        tracing::warn!("cargo check found {} error(s)", check.errors);
+    }
+    let structured = serde_json::to_value(check).map_err(|e| {
+        McpError::internal_error(format!("Failed to serialize report: {}", e), None)
+    })?;
+    Ok(CallToolResult {
+        content: vec![Content::text(summary)],
+        structured_content: Some(structured),
+        is_error: Some(!check.success),
+        meta: None,
+    })
+}
+
+/// Tool result for generated code with a drafted implementation
+///
+/// The code is compiled in a scratch crate and the check's diagnostics are
+/// returned with it. A draft that doesn't compile is still returned, since
+/// it is meant to be reviewed and edited anyway.
+async fn draft_result(
+    code: String,
+    context: &RequestContext<RoleServer>,
+) -> Result<CallToolResult, McpError> {
+    let (execution, progress) = execution_context(context);
+    let check = check::check_module(&code, &execution).await;
+    drop(execution);
+    if let Some(progress) = progress {
+        let _ = progress.await;
     }
+
+    let report = match check {
+        Ok(report) => report,
+        Err(e) => {
+            tracing::warn!("Draft compile check unavailable: {}", e);
+            return Ok(CallToolResult::success(vec![
+                Content::text(code),
+                Content::text(format!("Compile check unavailable: {}", e)),
+            ]));
+        }
+    };
+    let structured = serde_json::to_value(&report).map_err(|e| {
+        McpError::internal_error(format!("Failed to serialize report: {}", e), None)
+    })?;
+    Ok(CallToolResult {
+        content: vec![
+            Content::text(code),
+            Content::text(format!("Draft implementation: {}", report.summary())),
+        ],
+        structured_content: Some(structured),
+        is_error: Some(false),
+        meta: None,
+    })
+}
+
+/// Progress reporting and cancellation for a tool call
+///
+/// If the request carries a progress token, progress updates are forwarded
+/// to the client as `notifications/progress` by the returned task, which
+/// finishes once the context is dropped.
+fn execution_context(
+    context: &RequestContext<RoleServer>,
+) -> (
+    tool_executor::ExecutionContext,
+    Option<tokio::task::JoinHandle<()>>,
+) {
+    let execution = tool_executor::ExecutionContext::new(context.ct.clone());
+    let Some(token) = context.meta.get_progress_token() else {
+        return (execution, None);
+    };
+
+    let (sender, mut updates) = tokio::sync::mpsc::unbounded_channel();
+    let peer = context.peer.clone();
+    let forwarder = tokio::spawn(async move {
+        while let Some(update) = updates.recv().await {
+            let tool_executor::Progress {
+                progress,
+                total,
+                message,
+            } = update;
+            let params = ProgressNotificationParam {
+                progress_token: token.clone(),
+                progress: progress.into(),
+                total: total.map(Into::into),
+                message: Some(message),
+            };
+            if let Err(e) = peer.notify_progress(params).await {
+                tracing::warn!("Failed to send progress notification: {}", e);
+            }
+        }
+    });
+    (execution.with_progress(sender), Some(forwarder))
 }
 
-#[tool_handler]
+// Every handler runs in a span naming its session (`logging::SESSION_FIELD`),
+// so the events it logs are forwarded to this session's client only
 impl ServerHandler for MCPForgeServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
-            protocol_version: ProtocolVersion::V_2024_11_05,
+            protocol_version: protocol::LATEST,
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
                 .enable_resources()
+                .enable_resources_subscribe()
+                .enable_resources_list_changed()
                 .enable_prompts()
+                .enable_logging()
                 .build(),
             server_info: Implementation {
                 name: "MCP Forge".to_string(),
@@ -340,17 +906,65 @@ impl ServerHandler for MCPForgeServer {
         }
     }
 
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let features = ProtocolFeatures::negotiated(&context.peer);
+        let result = self
+            .tool_router
+            .call(ToolCallContext::new(self, request, context))
+            .await?;
+        Ok(features.adapt_tool_result(result))
+    }
+
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        // Sorted so listings are stable across runs (and recordings)
+        let mut tools = self.tool_router.list_all();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
     async fn list_resources(
         &self,
         _request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, McpError> {
-        use crate::resources::get_available_resources;
+        use crate::resources::get_resources_with_overrides;
 
         tracing::debug!("Listing available resources");
 
-        let resources = get_available_resources()
-            .into_values()
+        let workspace_root = Resource {
+            raw: RawResource {
+                uri: WORKSPACE_URI_PREFIX.to_string(),
+                name: "Workspace".to_string(),
+                title: None,
+                description: Some(
+                    "Directory listing of the workspace generated projects are written to"
+                        .to_string(),
+                ),
+                mime_type: Some("application/json".to_string()),
+                size: None,
+                icons: None,
+            },
+            annotations: None,
+        };
+
+        let mut templates: Vec<_> =
+            get_resources_with_overrides(self.config.template_dir.as_deref())
+                .into_values()
+                .collect();
+        templates.sort_by(|a, b| a.uri.cmp(&b.uri));
+        let resources = templates
+            .into_iter()
             .map(|resource| {
                 let raw_resource = RawResource {
                     uri: resource.uri.clone(),
@@ -358,7 +972,8 @@ impl ServerHandler for MCPForgeServer {
                     title: None,
                     description: None,
                     mime_type: Some(resource.mime_type.clone()),
-                    size: None,
+                    // Left out rather than wrapped for contents of 4 GiB or more
+                    size: u32::try_from(resource.content.len()).ok(),
                     icons: None,
                 };
                 Resource {
@@ -366,6 +981,7 @@ impl ServerHandler for MCPForgeServer {
                     annotations: None,
                 }
             })
+            .chain(std::iter::once(workspace_root))
             .collect();
 
         Ok(ListResourcesResult {
@@ -374,16 +990,32 @@ impl ServerHandler for MCPForgeServer {
         })
     }
 
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
     async fn read_resource(
         &self,
         request: ReadResourceRequestParam,
         _context: RequestContext<RoleServer>,
     ) -> Result<ReadResourceResult, McpError> {
-        use crate::resources::get_available_resources;
+        use crate::resources::get_resources_with_overrides;
 
         tracing::debug!("Reading resource: {}", request.uri);
 
-        let resources = get_available_resources();
+        if request.uri.starts_with(WORKSPACE_URI_PREFIX) {
+            let resource = self.workspace.read_resource(&request.uri).map_err(|e| {
+                tracing::warn!("Failed to read workspace resource {}: {}", request.uri, e);
+                match e {
+                    WorkspaceError::NotFound(msg) => McpError::resource_not_found(msg, None),
+                    WorkspaceError::InvalidPath(msg) => McpError::invalid_params(msg, None),
+                    WorkspaceError::Io(msg) => McpError::internal_error(msg, None),
+                }
+            })?;
+
+            return Ok(ReadResourceResult {
+                contents: vec![to_resource_contents(&resource)],
+            });
+        }
+
+        let resources = get_resources_with_overrides(self.config.template_dir.as_deref());
 
         let resource = resources
             .values()
@@ -407,10 +1039,91 @@ impl ServerHandler for MCPForgeServer {
         );
 
         Ok(ReadResourceResult {
-            contents: vec![ResourceContents::text(&resource.content, &resource.uri)],
+            contents: vec![to_resource_contents(resource)],
         })
     }
 
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        tracing::debug!("Listing resource templates");
+
+        let workspace_files = RawResourceTemplate {
+            uri_template: format!("{}{{+path}}", WORKSPACE_URI_PREFIX),
+            name: "Workspace File".to_string(),
+            title: None,
+            description: Some(
+                "A file or directory inside the workspace. Directories are returned \
+                 as JSON listings, files with a MIME type guessed from their extension."
+                    .to_string(),
+            ),
+            mime_type: None,
+        };
+
+        Ok(ListResourceTemplatesResult {
+            resource_templates: vec![ResourceTemplate {
+                raw: workspace_files,
+                annotations: None,
+            }],
+            next_cursor: None,
+        })
+    }
+
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        tracing::debug!("Subscribing to resource: {}", request.uri);
+
+        self.subscriptions
+            .register_peer(self.subscriber_id, context.peer);
+        self.subscriptions
+            .subscribe(self.subscriber_id, request.uri);
+        self.subscriptions.start_polling();
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        tracing::debug!("Unsubscribing from resource: {}", request.uri);
+
+        self.subscriptions
+            .unsubscribe(self.subscriber_id, &request.uri);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        tracing::debug!("Client log level set to {:?}", request.level);
+
+        self.loggers
+            .set_level(self.subscriber_id, context.peer, request.level);
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        tracing::info!("Client initialized");
+
+        self.subscriptions
+            .register_peer(self.subscriber_id, context.peer);
+        self.subscriptions.start_polling();
+    }
+
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
     async fn list_prompts(
         &self,
         _request: Option<PaginatedRequestParam>,
@@ -420,8 +1133,10 @@ impl ServerHandler for MCPForgeServer {
 
         tracing::debug!("Listing available prompts");
 
-        let prompts = get_available_prompts()
-            .into_values()
+        let mut prompts: Vec<_> = get_available_prompts().into_values().collect();
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
+        let prompts = prompts
+            .into_iter()
             .map(|prompt| Prompt {
                 name: prompt.name.clone(),
                 title: Some(prompt.name.clone()),
@@ -452,6 +1167,7 @@ impl ServerHandler for MCPForgeServer {
         })
     }
 
+    #[tracing::instrument(skip_all, fields(mcp_session = self.subscriber_id))]
     async fn get_prompt(
         &self,
         request: GetPromptRequestParam,
@@ -463,7 +1179,7 @@ impl ServerHandler for MCPForgeServer {
 
         let prompt = get_prompt(&request.name).ok_or_else(|| {
             tracing::warn!("Prompt not found: {}", request.name);
-            McpError::invalid_request(format!("Prompt not found: {}", request.name), None)
+            McpError::invalid_params(format!("Prompt not found: {}", request.name), None)
         })?;
 
         tracing::debug!("Successfully retrieved prompt: {}", request.name);
@@ -480,27 +1196,182 @@ impl ServerHandler for MCPForgeServer {
     }
 }
 
+/// Convert a forge resource into MCP resource contents
+///
+/// Text content is returned as `text` contents and binary content as
+/// base64-encoded `blob` contents, both tagged with the resource MIME type.
+fn to_resource_contents(resource: &crate::resources::Resource) -> ResourceContents {
+    use crate::resources::ResourceContent;
+
+    match &resource.content {
+        ResourceContent::Text(text) => ResourceContents::TextResourceContents {
+            uri: resource.uri.clone(),
+            mime_type: Some(resource.mime_type.clone()),
+            text: text.clone(),
+            meta: None,
+        },
+        ResourceContent::Blob(bytes) => ResourceContents::BlobResourceContents {
+            uri: resource.uri.clone(),
+            mime_type: Some(resource.mime_type.clone()),
+            blob: BASE64_STANDARD.encode(bytes),
+            meta: None,
+        },
+    }
+}
+
 impl Default for MCPForgeServer {
     fn default() -> Self {
-        Self::new()
+        Self::new(ForgeConfig::default())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::harness::{HarnessClient, TestHarness};
 
     #[test]
     fn test_server_creation() {
-        let server = MCPForgeServer::new();
+        let config = ForgeConfig {
+            workspace_root: PathBuf::from("projects"),
+            ..ForgeConfig::default()
+        };
+        let server = MCPForgeServer::new(config);
+        assert_eq!(server.workspace.root(), std::path::Path::new("projects"));
         let info = server.get_info();
         assert_eq!(info.server_info.name, "MCP Forge");
     }
 
+    #[test]
+    fn test_resource_capabilities() {
+        let info = MCPForgeServer::default().get_info();
+        let resources = info.capabilities.resources.unwrap();
+        assert_eq!(resources.subscribe, Some(true));
+        assert_eq!(resources.list_changed, Some(true));
+    }
+
+    #[test]
+    fn test_advertises_latest_protocol() {
+        let info = MCPForgeServer::default().get_info();
+        assert_eq!(info.protocol_version, protocol::LATEST);
+    }
+
     #[test]
     fn test_default_server() {
         let server = MCPForgeServer::default();
         let info = server.get_info();
         assert!(!info.server_info.version.is_empty());
     }
+
+    /// Call a tool on `server` from `client` over an in-memory transport
+    async fn call(
+        server: MCPForgeServer,
+        client: HarnessClient,
+        tool: &str,
+        arguments: serde_json::Value,
+    ) -> Result<CallToolResult, rmcp::ServiceError> {
+        let harness = TestHarness::start_with(server, client).await.unwrap();
+        let result = harness.call_tool(tool, arguments).await;
+        harness.shutdown().await;
+        result
+    }
+
+    #[tokio::test]
+    async fn test_elicits_invalid_resource_type() {
+        let arguments = serde_json::json!({"resource_name": "settings", "type": "yaml"});
+        let client =
+            HarnessClient::new().with_elicitation_answers(serde_json::json!({"type": "json"}));
+        let result = call(
+            MCPForgeServer::default(),
+            client.clone(),
+            "generate_resource",
+            arguments.clone(),
+        )
+        .await
+        .unwrap();
+        let text = &result.content[0].as_text().unwrap().text;
+        assert!(text.contains("application/json"), "{}", text);
+
+        // Sessions older than 2025-06-18 fail as before
+        let old_client = client.with_protocol_version(ProtocolVersion::V_2025_03_26);
+        let error = call(
+            MCPForgeServer::default(),
+            old_client,
+            "generate_resource",
+            arguments,
+        )
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("Invalid resource_type 'yaml'"));
+    }
+
+    #[tokio::test]
+    async fn test_elicits_missing_description() {
+        let root = std::env::temp_dir().join("mcp_forge_elicit_description");
+        let _ = std::fs::remove_dir_all(&root);
+        let server = MCPForgeServer::new(ForgeConfig {
+            workspace_root: root.clone(),
+            ..ForgeConfig::default()
+        });
+        let client = HarnessClient::new().with_elicitation_answers(
+            serde_json::json!({"description": "Answers questions about the weather"}),
+        );
+
+        let result = call(
+            server,
+            client,
+            "generate_project",
+            serde_json::json!({"project_name": "weather"}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.is_error, Some(false));
+        let cargo_toml = std::fs::read_to_string(root.join("weather/Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("description = \"Answers questions about the weather\""));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_drafts_tool_implementation() {
+        let arguments = serde_json::json!({
+            "tool_name": "word_count",
+            "description": "Count the words in a text",
+            "draft_implementation": true,
+        });
+        let client = HarnessClient::new().with_sampling_reply(
+            "```rust\nlet words = \"a b c\".split_whitespace().count();\nOk(words.to_string())\n```",
+        );
+        let result = call(
+            MCPForgeServer::default(),
+            client,
+            "generate_tool",
+            arguments.clone(),
+        )
+        .await
+        .unwrap();
+        let code = &result.content[0].as_text().unwrap().text;
+        assert!(code.contains(drafting::DRAFT_BEGIN));
+        assert!(code.contains("    Ok(words.to_string())\n    // END DRAFT"));
+        let check = &result.content[1].as_text().unwrap().text;
+        assert!(
+            check.starts_with("Draft implementation: cargo check passed"),
+            "{}",
+            check
+        );
+
+        // Without sampling the placeholder is generated
+        let result = call(
+            MCPForgeServer::default(),
+            HarnessClient::new(),
+            "generate_tool",
+            arguments,
+        )
+        .await
+        .unwrap();
+        let code = &result.content[0].as_text().unwrap().text;
+        assert!(code.contains("Implementation goes here"));
+        let note = &result.content[1].as_text().unwrap().text;
+        assert!(note.contains("does not support sampling"));
+    }
 }
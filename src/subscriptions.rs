@@ -0,0 +1,230 @@
+//! Resource subscriptions
+//!
+//! Tracks which connected clients subscribed to which resource URIs and
+//! forwards changes detected by the [`ResourceWatcher`] as MCP notifications:
+//! - `notifications/resources/updated` to the subscribers of a changed URI
+//! - `notifications/resources/list_changed` to every connected client
+//!
+//! A single [`Subscriptions`] instance is shared by all clones of the server,
+//! so every client session served by the same process sees the same watcher.
+
+use crate::watcher::{ResourceChange, ResourceWatcher, WatchKind};
+use rmcp::{RoleServer, model::ResourceUpdatedNotificationParam, service::Peer};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Interval between two polls of the watched directories
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Identifier of a connected client session
+pub type SubscriberId = u64;
+
+/// Allocate a process-unique subscriber id
+pub fn next_subscriber_id() -> SubscriberId {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Mutable state shared between sessions
+#[derive(Debug, Default)]
+struct SubscriptionState {
+    peers: HashMap<SubscriberId, Peer<RoleServer>>,
+    subscribers: HashMap<String, HashSet<SubscriberId>>,
+    watcher: ResourceWatcher,
+}
+
+/// Shared registry of resource subscriptions and watched directories
+#[derive(Debug, Clone, Default)]
+pub struct Subscriptions {
+    state: Arc<Mutex<SubscriptionState>>,
+    polling: Arc<AtomicBool>,
+}
+
+impl Subscriptions {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a connected client so it receives list-changed notifications
+    pub fn register_peer(&self, id: SubscriberId, peer: Peer<RoleServer>) {
+        self.lock().peers.insert(id, peer);
+    }
+
+    /// Subscribe a client to updates of a resource URI
+    pub fn subscribe(&self, id: SubscriberId, uri: impl Into<String>) {
+        self.lock()
+            .subscribers
+            .entry(uri.into())
+            .or_default()
+            .insert(id);
+    }
+
+    /// Remove a client's subscription to a resource URI
+    pub fn unsubscribe(&self, id: SubscriberId, uri: &str) {
+        let mut state = self.lock();
+        if let Some(ids) = state.subscribers.get_mut(uri) {
+            ids.remove(&id);
+            if ids.is_empty() {
+                state.subscribers.remove(uri);
+            }
+        }
+    }
+
+    /// Get the ids of clients subscribed to a resource URI
+    pub fn subscribers(&self, uri: &str) -> Vec<SubscriberId> {
+        let mut ids: Vec<SubscriberId> = self
+            .lock()
+            .subscribers
+            .get(uri)
+            .map(|ids| ids.iter().copied().collect())
+            .unwrap_or_default();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Watch a template override directory (`forge://templates/...`)
+    pub fn watch_templates(&self, dir: impl Into<PathBuf>) {
+        self.lock()
+            .watcher
+            .watch(dir, "forge://templates", WatchKind::Templates);
+    }
+
+    /// Watch a generated project directory (`forge://workspace/<project>/...`)
+    pub fn watch_project(&self, dir: impl Into<PathBuf>, project_name: &str) {
+        self.lock().watcher.watch(
+            dir,
+            format!("forge://workspace/{}", project_name),
+            WatchKind::Project,
+        );
+    }
+
+    /// Start the background polling task, if it is not already running
+    ///
+    /// The task stops once every registered client has disconnected.
+    pub fn start_polling(&self) {
+        if self.polling.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let subscriptions = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                let changes = subscriptions.lock().watcher.poll();
+                for change in changes {
+                    subscriptions.notify(change).await;
+                }
+                if subscriptions.prune_closed_peers() == 0 {
+                    break;
+                }
+            }
+            subscriptions.polling.store(false, Ordering::SeqCst);
+            tracing::debug!("Resource watcher stopped: no connected clients");
+        });
+    }
+
+    /// Send the notification for a resource change to the interested clients
+    pub async fn notify(&self, change: ResourceChange) {
+        let targets: Vec<Peer<RoleServer>> = {
+            let state = self.lock();
+            match &change {
+                ResourceChange::Updated(uri) => state
+                    .subscribers
+                    .get(uri)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|id| state.peers.get(id).cloned())
+                    .collect(),
+                ResourceChange::ListChanged => state.peers.values().cloned().collect(),
+            }
+        };
+
+        for peer in targets {
+            let result = match &change {
+                ResourceChange::Updated(uri) => {
+                    tracing::debug!("Notifying resource update: {}", uri);
+                    peer.notify_resource_updated(ResourceUpdatedNotificationParam {
+                        uri: uri.clone(),
+                    })
+                    .await
+                }
+                ResourceChange::ListChanged => {
+                    tracing::debug!("Notifying resource list change");
+                    peer.notify_resource_list_changed().await
+                }
+            };
+            if let Err(e) = result {
+                tracing::warn!("Failed to send resource notification: {}", e);
+            }
+        }
+    }
+
+    /// Drop disconnected clients and their subscriptions, returning how many remain
+    fn prune_closed_peers(&self) -> usize {
+        let mut state = self.lock();
+        let closed: Vec<SubscriberId> = state
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.is_transport_closed())
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in &closed {
+            state.peers.remove(id);
+        }
+        state.subscribers.retain(|_, ids| {
+            ids.retain(|id| !closed.contains(id));
+            !ids.is_empty()
+        });
+        state.peers.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SubscriptionState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribe_and_unsubscribe() {
+        let subscriptions = Subscriptions::new();
+        subscriptions.subscribe(2, "forge://templates/main.rs");
+        subscriptions.subscribe(1, "forge://templates/main.rs");
+        subscriptions.subscribe(1, "forge://templates/lib.rs");
+
+        assert_eq!(
+            subscriptions.subscribers("forge://templates/main.rs"),
+            vec![1, 2]
+        );
+
+        subscriptions.unsubscribe(1, "forge://templates/main.rs");
+        assert_eq!(
+            subscriptions.subscribers("forge://templates/main.rs"),
+            vec![2]
+        );
+        assert_eq!(
+            subscriptions.subscribers("forge://templates/lib.rs"),
+            vec![1]
+        );
+
+        subscriptions.unsubscribe(2, "forge://templates/main.rs");
+        assert!(
+            subscriptions
+                .subscribers("forge://templates/main.rs")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_subscriber_ids_are_unique() {
+        assert_ne!(next_subscriber_id(), next_subscriber_id());
+    }
+}
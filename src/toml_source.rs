@@ -0,0 +1,192 @@
+//! Position-tracking TOML parser
+//!
+//! Builds the same [`ParsedJson`] model as [`json_source`] from a TOML
+//! document, recording the line and column of every value and key in the
+//! original TOML text. Tables and arrays of tables become JSON objects and
+//! arrays; dates and times become strings.
+//!
+//! [`json_source`]: crate::json_source
+
+use crate::json_source::{ParsedJson, PositionCursor, SyntaxError, position_at, push_pointer};
+use serde_json::{Map, Number, Value};
+use toml_edit::{ImDocument, InlineTable, Item, Table};
+
+/// Parse TOML text into the common document model
+pub fn parse(text: &str) -> ParsedJson {
+    let mut parsed = ParsedJson::default();
+
+    match ImDocument::parse(text) {
+        Ok(document) => {
+            parsed.record_value("", position_at(text, 0), Some(text.len()));
+            let mut cursor = PositionCursor::new(text);
+            let root = convert_table(&mut cursor, document.as_table(), "", &mut parsed);
+            parsed.value = Some(root);
+        }
+        Err(e) => {
+            let offset = e.span().map_or(0, |span| span.start);
+            parsed.errors.push(SyntaxError {
+                position: position_at(text, offset),
+                pointer: String::new(),
+                message: e.message().trim().replace('\n', "; "),
+                suggestion: None,
+            });
+        }
+    }
+    parsed
+}
+
+fn convert_table(
+    cursor: &mut PositionCursor<'_>,
+    table: &Table,
+    pointer: &str,
+    parsed: &mut ParsedJson,
+) -> Value {
+    let mut map = Map::new();
+    for (key, item) in table.iter() {
+        let member = push_pointer(pointer, key);
+        if let Some(span) = table.key(key).and_then(|k| k.span()) {
+            parsed.record_key(&member, cursor.at_offset(span.start), Some(span.end));
+        }
+        if let Some(value) = convert_item(cursor, item, &member, parsed) {
+            map.insert(key.to_string(), value);
+        }
+    }
+    Value::Object(map)
+}
+
+fn convert_inline_table(
+    cursor: &mut PositionCursor<'_>,
+    table: &InlineTable,
+    pointer: &str,
+    parsed: &mut ParsedJson,
+) -> Value {
+    let mut map = Map::new();
+    for (key, value) in table.iter() {
+        let member = push_pointer(pointer, key);
+        if let Some(span) = table.key(key).and_then(|k| k.span()) {
+            parsed.record_key(&member, cursor.at_offset(span.start), Some(span.end));
+        }
+        map.insert(
+            key.to_string(),
+            convert_value(cursor, value, &member, parsed),
+        );
+    }
+    Value::Object(map)
+}
+
+fn convert_item(
+    cursor: &mut PositionCursor<'_>,
+    item: &Item,
+    pointer: &str,
+    parsed: &mut ParsedJson,
+) -> Option<Value> {
+    match item {
+        Item::None => None,
+        Item::Value(value) => Some(convert_value(cursor, value, pointer, parsed)),
+        Item::Table(table) => {
+            if let Some(span) = table.span() {
+                parsed.record_value(pointer, cursor.at_offset(span.start), None);
+            }
+            Some(convert_table(cursor, table, pointer, parsed))
+        }
+        Item::ArrayOfTables(tables) => {
+            let items = tables
+                .iter()
+                .enumerate()
+                .map(|(index, table)| {
+                    let element = format!("{}/{}", pointer, index);
+                    if let Some(span) = table.span() {
+                        if index == 0 {
+                            parsed.record_value(pointer, cursor.at_offset(span.start), None);
+                        }
+                        parsed.record_value(&element, cursor.at_offset(span.start), None);
+                    }
+                    convert_table(cursor, table, &element, parsed)
+                })
+                .collect();
+            Some(Value::Array(items))
+        }
+    }
+}
+
+fn convert_value(
+    cursor: &mut PositionCursor<'_>,
+    value: &toml_edit::Value,
+    pointer: &str,
+    parsed: &mut ParsedJson,
+) -> Value {
+    if let Some(span) = value.span() {
+        parsed.record_value(pointer, cursor.at_offset(span.start), Some(span.end));
+    }
+
+    match value {
+        toml_edit::Value::String(s) => Value::String(s.value().clone()),
+        toml_edit::Value::Integer(i) => Value::Number((*i.value()).into()),
+        toml_edit::Value::Float(f) => Number::from_f64(*f.value())
+            .map_or_else(|| Value::String(f.value().to_string()), Value::Number),
+        toml_edit::Value::Boolean(b) => Value::Bool(*b.value()),
+        toml_edit::Value::Datetime(d) => Value::String(d.value().to_string()),
+        toml_edit::Value::Array(array) => Value::Array(
+            array
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    convert_value(cursor, item, &format!("{}/{}", pointer, index), parsed)
+                })
+                .collect(),
+        ),
+        toml_edit::Value::InlineTable(table) => {
+            convert_inline_table(cursor, table, pointer, parsed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_toml_with_positions() {
+        let text = "name = \"demo\"\nversion = \"1.0\"\n\n[mcpServers.forge]\ncommand = \"mcp-forge\"\nargs = [\"serve\"]\nenv = { PORT = 8080 }\n";
+        let parsed = parse(text);
+        assert!(parsed.errors.is_empty());
+        assert_eq!(
+            parsed.value,
+            Some(json!({
+                "name": "demo",
+                "version": "1.0",
+                "mcpServers": {"forge": {
+                    "command": "mcp-forge",
+                    "args": ["serve"],
+                    "env": {"PORT": 8080}
+                }}
+            }))
+        );
+
+        let key = parsed.key_position("/mcpServers/forge/args").unwrap();
+        assert_eq!((key.line, key.column), (6, 1));
+        let value = parsed.value_position("/mcpServers/forge/env/PORT").unwrap();
+        assert_eq!((value.line, value.column), (7, 16));
+        let version = parsed.value_position("/version").unwrap();
+        assert_eq!((version.line, version.column), (2, 11));
+    }
+
+    #[test]
+    fn test_array_of_tables() {
+        let parsed = parse("[[packages]]\nname = \"a\"\n\n[[packages]]\nname = \"b\"\n");
+        assert_eq!(
+            parsed.value,
+            Some(json!({"packages": [{"name": "a"}, {"name": "b"}]}))
+        );
+        assert_eq!(parsed.key_position("/packages/1/name").unwrap().line, 5);
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let parsed = parse("name = \"demo\"\nversion = \n");
+        assert!(parsed.value.is_none());
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].position.line, 2);
+    }
+}
@@ -8,16 +8,116 @@
 //! - `generate_resource`: Generates code for a new resource
 //! - `generate_readme`: Generates README.md with setup instructions
 //! - `validate_manifest`: Validates an MCP server manifest file
+//! - `verify_manifest`: Launches the servers a manifest configures and checks them
+//! - `check_project`: Runs `cargo check` on a project
+//! - `test_server`: Runs the conformance suite against a stdio server
+//! - `fuzz_tools`: Calls a stdio server's tools with generated arguments
 //!
 //! All functions accept JSON arguments and return results as strings.
-
+//! Long-running tools also accept an [`ExecutionContext`] to report progress
+//! and observe cancellation.
+
+use crate::autofix::{self, FixResult};
+use crate::check::{self, CheckReport};
+use crate::config::ForgeConfig;
+use crate::conformance::{self, ConformanceReport};
+use crate::drafting;
+use crate::fuzz::{self, FuzzOptions, FuzzReport};
+use crate::manifest::{self, ManifestFormat, SourceSyntax, ValidationReport};
+use crate::protocol;
 use crate::resources;
+use crate::verify::{self, VerificationReport};
+use crate::workspace::{Workspace, validate_relative_path};
+use rmcp::model::ProtocolVersion;
 use serde_json::Value;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
 use tracing::info;
 
+/// A progress update from a running tool
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    /// Steps completed so far
+    pub progress: u32,
+    /// Total number of steps, if known
+    pub total: Option<u32>,
+    /// What was just done
+    pub message: String,
+}
+
+/// Progress reporting and cancellation for a running tool
+///
+/// The default context reports nothing and is never cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionContext {
+    progress: Option<mpsc::UnboundedSender<Progress>>,
+    cancellation: CancellationToken,
+    /// Steps completed by earlier phases of the same call
+    offset: u32,
+}
+
+impl ExecutionContext {
+    /// Create a context that is cancelled with `cancellation`
+    pub fn new(cancellation: CancellationToken) -> Self {
+        Self {
+            progress: None,
+            cancellation,
+            offset: 0,
+        }
+    }
+
+    /// Send progress updates to `sender`
+    pub fn with_progress(mut self, sender: mpsc::UnboundedSender<Progress>) -> Self {
+        self.progress = Some(sender);
+        self
+    }
+
+    /// Context for a later phase that starts counting after `steps`
+    ///
+    /// Progress must keep increasing across one tool call, so the phase's
+    /// steps are reported after the earlier ones.
+    pub fn after_steps(&self, steps: u32) -> Self {
+        Self {
+            offset: self.offset + steps,
+            ..self.clone()
+        }
+    }
+
+    /// Report that `progress` of `total` steps are done
+    pub fn report(&self, progress: u32, total: Option<u32>, message: impl Into<String>) {
+        if let Some(sender) = &self.progress {
+            let _ = sender.send(Progress {
+                progress: self.offset + progress,
+                total: total.map(|total| self.offset + total),
+                message: message.into(),
+            });
+        }
+    }
+
+    /// Whether the tool call was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Complete when the tool call is cancelled
+    pub async fn cancelled(&self) {
+        self.cancellation.cancelled().await;
+    }
+
+    /// Fail with a cancellation error if the tool call was cancelled
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err("Cancelled by the client".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// Execute a tool by name with the given arguments.
 ///
 /// Routes the tool name to the appropriate executor function and handles
@@ -40,6 +140,10 @@ use tracing::info;
 /// - `generate_resource` - Generate resource code template
 /// - `generate_readme` - Generate README.md with setup instructions
 /// - `validate_manifest` - Validate MCP manifest JSON
+/// - `verify_manifest` - Launch configured servers and check them against the manifest
+/// - `check_project` - Run `cargo check` on a project
+/// - `test_server` - Run the conformance suite against a stdio server
+/// - `fuzz_tools` - Fuzz the tools of a stdio server
 pub async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<String, String> {
     debug!("Executing tool: {}", tool_name);
 
@@ -49,6 +153,10 @@ pub async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<String,
         "generate_resource" => execute_generate_resource(arguments).await,
         "generate_readme" => execute_generate_readme(arguments).await,
         "validate_manifest" => execute_validate_manifest(arguments).await,
+        "verify_manifest" => execute_verify_manifest(arguments).await,
+        "check_project" => execute_check_project(arguments).await,
+        "test_server" => execute_test_server(arguments).await,
+        "fuzz_tools" => execute_fuzz_tools(arguments).await,
         _ => Err(format!("Unknown tool: {}", tool_name)),
     }
 }
@@ -65,10 +173,18 @@ pub async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<String,
 ///
 /// * `project_name` - (required) Name of the new project
 /// * `description` - (optional) Project description
+/// * `workspace_root` - (optional) Directory to generate the project in (defaults to ".")
+/// * `author` - (optional) Author written into Cargo.toml
+/// * `license` - (optional) License of the project (defaults to "MIT")
+/// * `rmcp_version` - (optional) rmcp version requirement (defaults to "0.8")
+/// * `template_dir` - (optional) Directory of `<name>.template` overrides
+/// * `verify` - (optional) Run `cargo check` on the generated project
+/// * `target_dir` - (optional) Cargo target directory for the check
 ///
 /// # Returns
 ///
-/// Returns a success message or an error if directory creation fails.
+/// Returns a success message, followed by the check results if `verify` is
+/// set, or an error if directory creation or the check fails.
 ///
 /// # Errors
 ///
@@ -77,30 +193,108 @@ pub async fn execute_tool(tool_name: &str, arguments: &Value) -> Result<String,
 /// - Project directory cannot be created
 /// - Template files cannot be written
 async fn execute_generate_project(arguments: &Value) -> Result<String, String> {
-    info!("Generating new MCP project");
+    let project = generate_project(
+        arguments,
+        &ForgeConfig::default(),
+        &ExecutionContext::default(),
+    )
+    .await?;
+    match &project.check {
+        Some(check) if !check.success => Err(project.summary()),
+        _ => Ok(project.summary()),
+    }
+}
 
-    let project_name = arguments
-        .get("project_name")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing required argument: project_name".to_string())?;
+/// A generated project and, if it was verified, its check result
+#[derive(Debug, Clone)]
+pub struct GeneratedProject {
+    /// Directory the project was written to
+    pub path: PathBuf,
+    /// Success message
+    pub message: String,
+    /// `cargo check` result, if `verify` was set
+    pub check: Option<CheckReport>,
+}
 
-    let description = arguments
-        .get("description")
-        .and_then(|v| v.as_str())
-        .unwrap_or("A new MCP server project");
+impl GeneratedProject {
+    /// The success message followed by the check summary
+    pub fn summary(&self) -> String {
+        match &self.check {
+            Some(check) => format!("{}\n{}", self.message, check.summary()),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// Generate a new MCP server project, reporting progress to `execution`
+///
+/// Files are staged in a hidden directory beside the project and renamed
+/// into place once all are written, so the project appears complete or not
+/// at all. Reports one progress step per written file plus one for the
+/// rename, and checks for cancellation before each one. Takes the same arguments as
+/// `execute_generate_project`; `config` supplies the author, license, rmcp
+/// version, workspace root and template directory the arguments leave out.
+///
+/// # Errors
+///
+/// Returns an error if arguments are missing, the project directory exists
+/// and is not empty, a step fails (naming the step) or the call is
+/// cancelled. A project that fails the check is still generated; its
+/// report has `success: false`.
+pub async fn generate_project(
+    arguments: &Value,
+    config: &ForgeConfig,
+    execution: &ExecutionContext,
+) -> Result<GeneratedProject, String> {
+    info!("Generating new MCP project");
+    let workspace = Workspace::new(
+        arguments
+            .get("workspace_root")
+            .and_then(|v| v.as_str())
+            .map_or_else(|| config.workspace_root.clone(), PathBuf::from),
+    );
+    validate_project_target(arguments, &workspace)?;
+
+    let context = template_context(arguments, config)?;
 
     debug!(
         "Project name: {}, Description: {}",
-        project_name, description
+        context.project_name, context.description
     );
 
     // Create project directory structure
-    create_project_structure(project_name, description).await?;
-
-    Ok(format!(
-        "Project '{}' generated successfully in directory '{}'",
-        project_name, project_name
-    ))
+    let path = create_project_structure(
+        &workspace,
+        &context,
+        template_dir(arguments, config),
+        execution,
+    )
+    .await?;
+
+    let check = if arguments
+        .get("verify")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+    {
+        let target_dir = arguments
+            .get("target_dir")
+            .and_then(|v| v.as_str())
+            .map(Path::new);
+        let execution = execution.after_steps(GENERATION_STEPS);
+        Some(check::check_project(&path, target_dir, &execution).await?)
+    } else {
+        None
+    };
+
+    Ok(GeneratedProject {
+        message: format!(
+            "Project '{}' generated successfully in '{}'",
+            context.project_name,
+            path.display()
+        ),
+        path,
+        check,
+    })
 }
 
 /// Generate code template for a new MCP tool.
@@ -115,6 +309,8 @@ async fn execute_generate_project(arguments: &Value) -> Result<String, String> {
 ///
 /// * `tool_name` - (required) Name of the tool
 /// * `description` - (required) Tool description
+/// * `draft_body` - (optional) Drafted function body to use instead of the
+///   placeholder, inserted between draft markers
 ///
 /// # Returns
 ///
@@ -140,16 +336,21 @@ async fn execute_generate_tool(arguments: &Value) -> Result<String, String> {
         .get("tool_name")
         .and_then(|v| v.as_str())
         .ok_or_else(|| "Missing required argument: tool_name".to_string())?;
+    validate_identifier(tool_name, "Tool name")?;
 
     let description = arguments
         .get("description")
         .and_then(|v| v.as_str())
         .ok_or_else(|| "Missing required argument: description".to_string())?;
+    if description.trim().is_empty() {
+        return Err("Tool description cannot be empty".to_string());
+    }
 
     debug!("Tool name: {}", tool_name);
 
     // Generate tool code
-    let tool_code = generate_tool_code(tool_name, description);
+    let draft_body = arguments.get("draft_body").and_then(|v| v.as_str());
+    let tool_code = generate_tool_code(tool_name, description, draft_body);
 
     Ok(tool_code)
 }
@@ -173,7 +374,9 @@ async fn execute_generate_tool(arguments: &Value) -> Result<String, String> {
 ///
 /// # Errors
 ///
-/// Returns an error if required arguments are missing.
+/// Returns an error if:
+/// - Required arguments are missing
+/// - `resource_type` is not one of: text, binary, json
 ///
 /// # Example
 ///
@@ -192,12 +395,21 @@ async fn execute_generate_resource(arguments: &Value) -> Result<String, String>
         .get("resource_name")
         .and_then(|v| v.as_str())
         .ok_or_else(|| "Missing required argument: resource_name".to_string())?;
+    validate_identifier(resource_name, "Resource name")?;
 
     let resource_type = arguments
         .get("resource_type")
         .and_then(|v| v.as_str())
         .ok_or_else(|| "Missing required argument: resource_type".to_string())?;
 
+    if !RESOURCE_TYPES.contains(&resource_type) {
+        return Err(format!(
+            "Invalid resource_type '{}': expected one of {}",
+            resource_type,
+            RESOURCE_TYPES.join(", ")
+        ));
+    }
+
     let description = arguments
         .get("description")
         .and_then(|v| v.as_str())
@@ -226,7 +438,9 @@ async fn execute_generate_resource(arguments: &Value) -> Result<String, String>
 ///
 /// * `project_name` - (required) Name of the MCP server project
 /// * `description` - (optional) Project description
-/// * `output_path` - (optional) Path to write README.md (defaults to "README.md")
+/// * `output_path` - (optional) Workspace-relative path to write README.md
+///   (defaults to "README.md")
+/// * `workspace_root` - (optional) Workspace sandbox for `output_path` (defaults to ".")
 ///
 /// # Returns
 ///
@@ -235,7 +449,8 @@ async fn execute_generate_resource(arguments: &Value) -> Result<String, String>
 /// # Errors
 ///
 /// Returns an error if:
-/// - `project_name` argument is missing
+/// - `project_name` argument is missing or not a valid project name
+/// - `output_path` escapes the workspace or is a directory
 /// - README.md cannot be written to the specified path
 ///
 /// # Example
@@ -244,7 +459,7 @@ async fn execute_generate_resource(arguments: &Value) -> Result<String, String>
 /// let args = json!({
 ///     "project_name": "my_server",
 ///     "description": "My MCP server",
-///     "output_path": "./README.md"
+///     "output_path": "docs/README.md"
 /// });
 /// let result = execute_generate_readme(&args).await?;
 /// ```
@@ -255,6 +470,7 @@ async fn execute_generate_readme(arguments: &Value) -> Result<String, String> {
         .get("project_name")
         .and_then(|v| v.as_str())
         .ok_or_else(|| "Missing required argument: project_name".to_string())?;
+    validate_project_name(project_name)?;
 
     let description = arguments
         .get("description")
@@ -266,14 +482,25 @@ async fn execute_generate_readme(arguments: &Value) -> Result<String, String> {
         .and_then(|v| v.as_str())
         .unwrap_or("README.md");
 
+    let workspace = Workspace::new(
+        arguments
+            .get("workspace_root")
+            .and_then(|v| v.as_str())
+            .unwrap_or("."),
+    );
+    let path = readme_path(&workspace, output_path)?;
+
     debug!("Generating README for: {}", project_name);
 
     // Generate README content
     let readme_content = generate_readme_content(project_name, description);
 
     // Write to file
-    fs::write(output_path, readme_content)
-        .map_err(|e| format!("Failed to write README.md: {}", e))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, readme_content).map_err(|e| format!("Failed to write README.md: {}", e))?;
 
     Ok(format!(
         "README.md generated successfully at '{}'",
@@ -281,77 +508,552 @@ async fn execute_generate_readme(arguments: &Value) -> Result<String, String> {
     ))
 }
 
+/// Resolve the README output path inside the workspace
+///
+/// # Errors
+///
+/// Returns an error if `output_path` escapes the workspace, has a component
+/// longer than file systems allow, or names a directory or a location below
+/// an existing file.
+pub fn readme_path(workspace: &Workspace, output_path: &str) -> Result<PathBuf, String> {
+    if output_path
+        .split('/')
+        .any(|component| component.len() > MAX_FILE_NAME_BYTES)
+    {
+        return Err(format!(
+            "Output path components cannot be longer than {} bytes",
+            MAX_FILE_NAME_BYTES
+        ));
+    }
+    let path = workspace.resolve(output_path)?;
+    if path.is_dir() {
+        return Err(format!("Output path '{}' is a directory", output_path));
+    }
+    if let Some(file) = path
+        .ancestors()
+        .skip(1)
+        .take_while(|ancestor| ancestor.starts_with(workspace.root()))
+        .find(|ancestor| ancestor.is_file())
+    {
+        return Err(format!(
+            "Output path '{}' is below the file '{}'",
+            output_path,
+            file.display()
+        ));
+    }
+    Ok(path)
+}
+
 /// Validate an MCP server manifest file.
 ///
-/// Performs validation checks on manifest JSON:
-/// - Validates JSON syntax
-/// - Checks for required fields (name, version, description)
-/// - Ensures manifest structure is correct
+/// Performs validation checks on a JSON, YAML or TOML manifest:
+/// - Validates the syntax, recovering from JSON syntax errors
+/// - Uses the requested format, or detects it from the top-level keys
+///   (`claude_desktop_config.json`, `server.json`, desktop extension
+///   `manifest.json`, or a basic server manifest)
+/// - Validates the manifest against the bundled JSON Schema for that format
+/// - Runs semantic checks (semver versions, package/transport consistency,
+///   tool lists matching declared capabilities)
 ///
 /// # Arguments
 ///
-/// * `manifest_content` - (required) JSON string containing the manifest
+/// * `manifest_content` - Manifest text (required unless `path` is given)
+/// * `path` - Workspace-relative path of the manifest file (instead of `manifest_content`)
+/// * `workspace_root` - (optional) Workspace sandbox for `path` (defaults to ".")
+/// * `syntax` - (optional) json, yaml, toml, or "auto" to use the file
+///   extension or content (default)
+/// * `format` - (optional) Manifest format, or "auto" to detect it (default)
+/// * `fix` - (optional) Apply automatic fixes and return the corrected manifest
 ///
 /// # Returns
 ///
-/// Returns a validation result message or a detailed error description.
+/// Returns the validation summary, or an error listing every diagnostic
+/// with its line/column, JSON pointer and suggested fix. With `fix: true`
+/// the summary also lists the applied fixes and the corrected manifest, and
+/// is an error only if problems remain after fixing.
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - `manifest_content` argument is missing
-/// - JSON is invalid or malformed
-/// - Required fields are missing (name, version, description)
+/// - Neither `manifest_content` nor `path` is given, or `path` cannot be read
+/// - `syntax` or `format` is not known
+/// - The manifest has syntax, schema or semantic errors
 ///
 /// # Example
 ///
 /// ```ignore
 /// let args = json!({
-///     "manifest_content": r#"{"name": "my_server", "version": "0.1.0", "description": "test"}"#
+///     "manifest_content": r#"{"mcpServers": {"forge": {"command": "mcp-forge"}}}"#
 /// });
 /// let result = execute_validate_manifest(&args).await?;
 /// ```
 async fn execute_validate_manifest(arguments: &Value) -> Result<String, String> {
+    let (valid, summary) = if arguments.get("fix").and_then(|v| v.as_bool()) == Some(true) {
+        let result = fix_manifest(arguments)?;
+        (result.report.valid, result.summary())
+    } else {
+        let report = validate_manifest_report(arguments)?;
+        (report.valid, report.summary())
+    };
+
+    if valid { Ok(summary) } else { Err(summary) }
+}
+
+/// Validate a manifest and return the full diagnostic report
+///
+/// Syntax errors do not stop validation: the manifest is parsed with error
+/// recovery and every syntax, schema and semantic problem is reported in one
+/// pass, each with its severity, JSON pointer, line/column and suggested fix.
+///
+/// # Errors
+///
+/// Returns an error only if the manifest cannot be read or `syntax` or
+/// `format` is not known; an invalid manifest yields a report with
+/// `valid: false`.
+pub fn validate_manifest_report(arguments: &Value) -> Result<ValidationReport, String> {
     info!("Validating manifest");
 
-    let manifest_content = arguments
-        .get("manifest_content")
+    let input = manifest_arguments(arguments)?;
+    let mut report = manifest::validate_document(&input.content, input.syntax, input.format);
+    if let Some(path) = input.path {
+        report = report.with_path(path);
+    }
+    debug!(
+        "Validated manifest as {}: {} error(s), {} warning(s)",
+        report.format, report.errors, report.warnings
+    );
+    Ok(report)
+}
+
+/// Validate a manifest and apply every available automatic fix
+///
+/// Fixes are minimal, order-preserving text edits: typo'd keys are renamed,
+/// single-string `args` are wrapped in an array, near-semver versions are
+/// normalized and a missing `version` is added. The corrected manifest is
+/// validated again and its report returned alongside the applied fixes.
+///
+/// # Errors
+///
+/// Returns an error if the manifest cannot be read, `syntax` or `format` is
+/// not known, or the manifest is not JSON.
+pub fn fix_manifest(arguments: &Value) -> Result<FixResult, String> {
+    info!("Fixing manifest");
+
+    let input = manifest_arguments(arguments)?;
+    if input.syntax != SourceSyntax::Json {
+        return Err(format!(
+            "Automatic fixes are only supported for JSON manifests, not {}",
+            input.syntax
+        ));
+    }
+    let mut result = autofix::fix_source(&input.content, input.format);
+    if let Some(path) = input.path {
+        result.report = result.report.with_path(path);
+    }
+    debug!(
+        "Applied {} fix(es); {} error(s) remain",
+        result.fixes.len(),
+        result.report.errors
+    );
+    Ok(result)
+}
+
+/// Verify a manifest against the servers it launches.
+///
+/// Validates the manifest first, then starts every server it configures
+/// over stdio and checks that each one:
+/// - completes `initialize` within the timeout
+/// - answers `tools/list`, `resources/list` and `prompts/list` for the
+///   capabilities it advertises
+/// - exposes the tools and prompts a desktop extension manifest declares
+///
+/// # Arguments
+///
+/// * `manifest_content` - Manifest text (required unless `path` is given)
+/// * `path` - Workspace-relative path of the manifest file (instead of `manifest_content`)
+/// * `workspace_root` - (optional) Workspace sandbox for `path` (defaults to ".")
+/// * `syntax` - (optional) json, yaml, toml, or "auto" (default)
+/// * `format` - (optional) Manifest format, or "auto" to detect it (default)
+/// * `timeout_secs` - (optional) Seconds each server gets to start and
+///   answer (default 10, at most 120)
+///
+/// # Returns
+///
+/// Returns a summary with each server's protocol version, server info and
+/// tool/resource/prompt counts, or an error listing every mismatch.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The manifest cannot be read or fails validation
+/// - The format does not describe how to launch a server
+/// - A server fails to start, initialize or list its capabilities, or
+///   does not match the manifest
+async fn execute_verify_manifest(arguments: &Value) -> Result<String, String> {
+    let report = verify_manifest_report(arguments).await?;
+    if report.valid {
+        Ok(report.summary())
+    } else {
+        Err(report.summary())
+    }
+}
+
+/// Verify a manifest and return the full report
+///
+/// Servers are started with the manifest's directory as working directory
+/// (the workspace root for inline manifests).
+///
+/// # Errors
+///
+/// Returns an error if the manifest cannot be read, is invalid, or its
+/// format does not describe how to launch a server; servers that fail
+/// yield a report with `valid: false`.
+pub async fn verify_manifest_report(arguments: &Value) -> Result<VerificationReport, String> {
+    info!("Verifying manifest");
+
+    let input = manifest_arguments(arguments)?;
+    let mut validation = manifest::validate_document(&input.content, input.syntax, input.format);
+    if let Some(path) = &input.path {
+        validation = validation.with_path(path.clone());
+    }
+    if !validation.valid {
+        return Err(validation.summary());
+    }
+
+    let workspace = Workspace::new(
+        arguments
+            .get("workspace_root")
+            .and_then(|v| v.as_str())
+            .unwrap_or("."),
+    );
+    let manifest_dir = match &input.path {
+        Some(path) => workspace
+            .resolve(path)?
+            .parent()
+            .map_or_else(|| workspace.root().to_path_buf(), Path::to_path_buf),
+        None => workspace.root().to_path_buf(),
+    };
+    let manifest_dir = fs::canonicalize(&manifest_dir).unwrap_or(manifest_dir);
+
+    let timeout = arguments
+        .get("timeout_secs")
+        .and_then(|v| v.as_u64())
+        .map_or(verify::DEFAULT_TIMEOUT, |secs| {
+            Duration::from_secs(secs.clamp(1, 120))
+        });
+
+    let parsed = input.syntax.parse(&input.content);
+    let report = verify::verify(&parsed, validation.format, &manifest_dir, timeout).await?;
+    debug!(
+        "Verified {} server(s): {} error(s), {} warning(s)",
+        report.servers.len(),
+        report.errors,
+        report.warnings
+    );
+    Ok(report)
+}
+
+/// Run `cargo check` on a project and report the compiler diagnostics.
+///
+/// The check runs offline, so the project's dependencies must already be in
+/// the local cargo cache or vendored.
+///
+/// # Arguments
+///
+/// * `path` - (required) Workspace-relative path of the project directory
+/// * `workspace_root` - (optional) Workspace sandbox for `path` (defaults to ".")
+/// * `target_dir` - (optional) Cargo target directory (defaults to the project's)
+///
+/// # Returns
+///
+/// Returns a summary listing every warning, or an error listing every
+/// compiler error.
+///
+/// # Errors
+///
+/// Returns an error if the path is invalid, has no `Cargo.toml`, cargo
+/// cannot be run, or the project does not compile.
+async fn execute_check_project(arguments: &Value) -> Result<String, String> {
+    let report = check_project_report(arguments, &ExecutionContext::default()).await?;
+    if report.success {
+        Ok(report.summary())
+    } else {
+        Err(report.summary())
+    }
+}
+
+/// Check a project and return the full report
+///
+/// Reports one progress step per compiled crate to `execution`.
+///
+/// # Errors
+///
+/// Returns an error if the path is invalid, has no `Cargo.toml`, cargo
+/// cannot be run or the call is cancelled; compile errors yield a report
+/// with `success: false`.
+pub async fn check_project_report(
+    arguments: &Value,
+    execution: &ExecutionContext,
+) -> Result<CheckReport, String> {
+    let path = arguments
+        .get("path")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing required argument: manifest_content".to_string())?;
-
-    // Parse and validate manifest JSON
-    match serde_json::from_str::<Value>(manifest_content) {
-        Ok(manifest) => {
-            debug!("Manifest parsed successfully");
-
-            // Validate required fields
-            let required_fields = ["name", "version", "description"];
-            let missing_fields: Vec<&str> = required_fields
-                .iter()
-                .filter(|field| manifest.get(*field).is_none())
-                .copied()
-                .collect();
+        .ok_or_else(|| "Missing required argument: path".to_string())?;
+    let workspace = Workspace::new(
+        arguments
+            .get("workspace_root")
+            .and_then(|v| v.as_str())
+            .unwrap_or("."),
+    );
+    let project_dir = workspace.resolve(path)?;
+    if !project_dir.join("Cargo.toml").is_file() {
+        return Err(format!("No Cargo.toml found in '{}'", path));
+    }
+    let target_dir = arguments
+        .get("target_dir")
+        .and_then(|v| v.as_str())
+        .map(Path::new);
 
-            if missing_fields.is_empty() {
-                Ok("Manifest is valid.".to_string())
-            } else {
-                Err(format!(
-                    "Manifest is invalid. Missing fields: {}",
-                    missing_fields.join(", ")
-                ))
-            }
-        }
-        Err(e) => Err(format!("Invalid JSON in manifest: {}", e)),
+    info!("Checking project {}", path);
+    let report = check::check_project(&project_dir, target_dir, execution).await?;
+    debug!(
+        "cargo check: {} error(s), {} warning(s)",
+        report.errors, report.warnings
+    );
+    Ok(report)
+}
+
+/// Run the conformance suite against an MCP server launched over stdio.
+///
+/// # Arguments
+///
+/// * `command` - (required) Executable that starts the server
+/// * `args` - (optional) Command-line arguments
+/// * `workspace_root` - (optional) Working directory of the server (defaults to ".")
+/// * `timeout_secs` - (optional) Seconds the server gets for each check
+///   (default 10, at most 120)
+/// * `junit` - (optional) Also return the report as JUnit XML
+///
+/// # Returns
+///
+/// Returns the report summary, followed by the JUnit XML if `junit` is set.
+///
+/// # Errors
+///
+/// Returns an error if a check fails; the error carries the report.
+async fn execute_test_server(arguments: &Value) -> Result<String, String> {
+    let report = conformance_report(arguments).await?;
+    let mut output = report.summary();
+    if arguments
+        .get("junit")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+    {
+        output.push_str("\n\n");
+        output.push_str(&report.junit_xml());
+    }
+    if report.success() {
+        Ok(output)
+    } else {
+        Err(output)
     }
 }
 
+/// Run the conformance suite and return the full report
+///
+/// See [`conformance`] for the checks.
+///
+/// # Errors
+///
+/// Returns an error if `command` is missing; a server that fails checks
+/// yields a report with failures.
+pub async fn conformance_report(arguments: &Value) -> Result<ConformanceReport, String> {
+    let command = arguments
+        .get("command")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Missing required argument: command".to_string())?;
+    let args: Vec<String> = arguments
+        .get("args")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect();
+    let cwd = arguments
+        .get("workspace_root")
+        .and_then(|v| v.as_str())
+        .unwrap_or(".");
+    let timeout = arguments
+        .get("timeout_secs")
+        .and_then(|v| v.as_u64())
+        .map_or(conformance::DEFAULT_TIMEOUT, |secs| {
+            Duration::from_secs(secs.clamp(1, 120))
+        });
+
+    let report = conformance::test_server(command, &args, Path::new(cwd), timeout).await;
+    debug!(
+        "Conformance: {} passed, {} failed, {} skipped",
+        report.passed, report.failed, report.skipped
+    );
+    Ok(report)
+}
+
+/// Fuzz the tools of an MCP server launched over stdio.
+///
+/// # Arguments
+///
+/// * `command` - (required) Executable that starts the server
+/// * `args` - (optional) Command-line arguments
+/// * `workspace_root` - (optional) Working directory of the server (defaults to ".")
+/// * `tools` - (optional) Names of the tools to fuzz (defaults to all)
+/// * `fixed` - (optional) Object of arguments passed unchanged to every
+///   tool that declares them
+/// * `cases` - (optional) Random cases per tool (default 20, at most 1000)
+/// * `seed` - (optional) Seed the cases are generated from (defaults to a
+///   random seed)
+/// * `timeout_secs` - (optional) Seconds the server gets to answer each
+///   call (default 5, at most 120)
+///
+/// # Returns
+///
+/// Returns the report summary.
+///
+/// # Errors
+///
+/// Returns an error if a case fails; the error carries the report.
+async fn execute_fuzz_tools(arguments: &Value) -> Result<String, String> {
+    let report = fuzz_report(arguments).await?;
+    if report.success() {
+        Ok(report.summary())
+    } else {
+        Err(report.summary())
+    }
+}
+
+/// Fuzz a server's tools and return the full report
+///
+/// See [`fuzz`] for the generated cases and what counts as a failure.
+///
+/// # Errors
+///
+/// Returns an error if `command` is missing or `fixed` is not an object; a
+/// server that fails cases yields a report with failures.
+pub async fn fuzz_report(arguments: &Value) -> Result<FuzzReport, String> {
+    let command = arguments
+        .get("command")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Missing required argument: command".to_string())?;
+    let strings = |name: &str| -> Vec<String> {
+        arguments
+            .get(name)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect()
+    };
+    let cwd = arguments
+        .get("workspace_root")
+        .and_then(|v| v.as_str())
+        .unwrap_or(".");
+
+    let mut options = FuzzOptions::default().with_tools(strings("tools"));
+    match arguments.get("fixed") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(fixed)) => options.fixed = fixed.clone(),
+        Some(_) => return Err("fixed must be an object of arguments".to_string()),
+    }
+    if let Some(cases) = arguments.get("cases").and_then(|v| v.as_u64()) {
+        options = options.with_random_cases(cases.min(1000) as usize);
+    }
+    let seed = arguments
+        .get("seed")
+        .and_then(|v| v.as_u64())
+        .unwrap_or_else(random_seed);
+    options = options.with_seed(seed);
+    if let Some(secs) = arguments.get("timeout_secs").and_then(|v| v.as_u64()) {
+        options = options.with_timeout(Duration::from_secs(secs.clamp(1, 120)));
+    }
+
+    let report = fuzz::fuzz_server(command, &strings("args"), Path::new(cwd), &options).await;
+    debug!(
+        "Fuzzing: {} case(s), {} failure(s)",
+        report.cases,
+        report.failures.len()
+    );
+    Ok(report)
+}
+
+/// A seed that differs between runs
+fn random_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+/// Manifest text and options extracted from tool arguments
+struct ManifestInput {
+    content: String,
+    syntax: SourceSyntax,
+    format: Option<ManifestFormat>,
+    path: Option<String>,
+}
+
+/// Extract the manifest text, syntax and requested format from tool arguments
+///
+/// The manifest is either given inline as `manifest_content` or read from
+/// `path` inside the workspace sandbox (`workspace_root`, default "."). The
+/// syntax comes from `syntax`, else the file extension, else the content.
+fn manifest_arguments(arguments: &Value) -> Result<ManifestInput, String> {
+    let path = arguments.get("path").and_then(|v| v.as_str());
+    let content = match (
+        arguments.get("manifest_content").and_then(|v| v.as_str()),
+        path,
+    ) {
+        (Some(_), Some(_)) => {
+            return Err("Provide either manifest_content or path, not both".to_string());
+        }
+        (Some(content), None) => content.to_string(),
+        (None, Some(path)) => {
+            let workspace = Workspace::new(
+                arguments
+                    .get("workspace_root")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("."),
+            );
+            workspace.read_to_string(path).map_err(|e| e.to_string())?
+        }
+        (None, None) => {
+            return Err("Missing required argument: manifest_content or path".to_string());
+        }
+    };
+
+    let syntax = match arguments.get("syntax").and_then(|v| v.as_str()) {
+        None | Some("auto") => path
+            .and_then(|path| SourceSyntax::from_path(Path::new(path)))
+            .unwrap_or_else(|| SourceSyntax::detect(&content)),
+        Some(name) => name.parse::<SourceSyntax>()?,
+    };
+
+    let format = match arguments.get("format").and_then(|v| v.as_str()) {
+        None | Some("auto") => None,
+        Some(name) => Some(name.parse::<ManifestFormat>()?),
+    };
+
+    Ok(ManifestInput {
+        content,
+        syntax,
+        format,
+        path: path.map(str::to_string),
+    })
+}
+
 /// Validate project name to prevent path traversal attacks
 ///
-/// Checks that the project name:
-/// - Does not contain path traversal sequences (../, .., ./, etc.)
-/// - Does not start with / (absolute paths)
-/// - Does not contain null bytes
-/// - Is a valid UTF-8 string
+/// Rejects empty names and applies the workspace sandbox rules from
+/// [`validate_relative_path`] (no `..`, absolute paths, null bytes or
+/// Windows drive letters). The name must also be a valid package name: at
+/// most 64 ASCII letters, digits, `-` and `_`, starting with a letter.
 ///
 /// # Arguments
 ///
@@ -360,140 +1062,469 @@ async fn execute_validate_manifest(arguments: &Value) -> Result<String, String>
 /// # Returns
 ///
 /// Returns Ok(()) if the name is safe, or an error message if validation fails
-fn validate_project_name(project_name: &str) -> Result<(), String> {
+pub fn validate_project_name(project_name: &str) -> Result<(), String> {
     // Check for empty name
     if project_name.is_empty() {
         return Err("Project name cannot be empty".to_string());
     }
 
-    // Check for path traversal patterns
-    if project_name.contains("..") {
-        return Err("Project name cannot contain '..' (path traversal)".to_string());
+    validate_relative_path(project_name, "Project name")?;
+
+    if project_name.len() > MAX_NAME_LENGTH {
+        return Err(format!(
+            "Project name cannot be longer than {} characters",
+            MAX_NAME_LENGTH
+        ));
+    }
+    if !project_name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err("Project name must start with a letter".to_string());
+    }
+    if let Some(c) = project_name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_')))
+    {
+        return Err(format!(
+            "Project name cannot contain {:?}; use letters, digits, '-' and '_'",
+            c
+        ));
     }
 
-    // Check for absolute paths
-    if project_name.starts_with('/') {
-        return Err("Project name cannot be an absolute path".to_string());
+    debug!("Project name '{}' passed validation", project_name);
+    Ok(())
+}
+
+/// Validate a name used as a Rust identifier in generated code
+///
+/// Accepts at most 64 ASCII letters, digits and `_`, not starting with a
+/// digit. `label` names the value in error messages (e.g. "Tool name").
+pub fn validate_identifier(name: &str, label: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err(format!("{} cannot be empty", label));
+    }
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(format!(
+            "{} cannot be longer than {} characters",
+            label, MAX_NAME_LENGTH
+        ));
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!("{} cannot start with a digit", label));
     }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '_'))
+    {
+        return Err(format!(
+            "{} cannot contain {:?}; use letters, digits and '_'",
+            label, c
+        ));
+    }
+    Ok(())
+}
+
+/// Check the arguments of `generate_project` without writing anything
+///
+/// Covers what the caller controls: the project name, the protocol version
+/// and whether the project directory is free. Takes the same arguments as
+/// [`generate_project`], which also runs these checks.
+///
+/// # Errors
+///
+/// Returns an error describing the first invalid argument.
+pub fn validate_project_args(arguments: &Value) -> Result<(), String> {
+    let workspace = Workspace::new(
+        arguments
+            .get("workspace_root")
+            .and_then(|v| v.as_str())
+            .unwrap_or("."),
+    );
+    validate_project_target(arguments, &workspace)
+}
+
+/// Check the project name and protocol version, and that the project
+/// directory in `workspace` is free
+fn validate_project_target(arguments: &Value, workspace: &Workspace) -> Result<(), String> {
+    let project_name = arguments
+        .get("project_name")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Missing required argument: project_name".to_string())?;
+    validate_project_name(project_name)?;
 
-    // Check for null bytes
-    if project_name.contains('\0') {
-        return Err("Project name cannot contain null bytes".to_string());
+    if let Some(version) = arguments.get("protocol_version").and_then(|v| v.as_str()) {
+        protocol::parse(version)?;
     }
 
-    // Check for suspicious patterns
-    if project_name.contains("./") || project_name.contains("/./") || project_name.ends_with("/.") {
-        return Err("Project name cannot contain path traversal patterns".to_string());
+    let base_dir = workspace.resolve(project_name)?;
+    if base_dir.exists() && !is_empty_dir(&base_dir) {
+        return Err(format!(
+            "Project directory '{}' already exists and is not empty",
+            project_name
+        ));
     }
+    Ok(())
+}
+
+/// Render the files of a new project without writing them
+///
+/// Takes the template arguments of `generate_project` (`project_name`,
+/// `description`, `author`, `license`, `rmcp_version`, `protocol_version`
+/// and `template_dir`), with `config` filling in the ones left out, and
+/// returns the project-relative path and contents of every file, in the
+/// order they are written.
+///
+/// # Errors
+///
+/// Returns an error if `project_name` is missing or invalid, the protocol
+/// version is unsupported or a template is missing.
+pub fn render_project(
+    arguments: &Value,
+    config: &ForgeConfig,
+) -> Result<Vec<(&'static str, String)>, String> {
+    let context = template_context(arguments, config)?;
+    validate_project_name(context.project_name)?;
+    Ok(render_project_files(&context, template_dir(arguments, config))?.into())
+}
+
+/// Template values from generator arguments, with `config` filled in
+fn template_context<'a>(
+    arguments: &'a Value,
+    config: &'a ForgeConfig,
+) -> Result<TemplateContext<'a>, String> {
+    Ok(TemplateContext {
+        project_name: arguments
+            .get("project_name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "Missing required argument: project_name".to_string())?,
+        description: arguments
+            .get("description")
+            .and_then(|v| v.as_str())
+            .unwrap_or("A new MCP server project"),
+        author: arguments
+            .get("author")
+            .and_then(|v| v.as_str())
+            .or(config.author.as_deref()),
+        license: arguments
+            .get("license")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&config.license),
+        rmcp_version: arguments
+            .get("rmcp_version")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&config.rmcp_version),
+        protocol_version: arguments
+            .get("protocol_version")
+            .and_then(|v| v.as_str())
+            .map(protocol::parse)
+            .transpose()?
+            .unwrap_or(protocol::LATEST),
+    })
+}
+
+/// Directory of template overrides named by the arguments or `config`
+fn template_dir<'a>(arguments: &'a Value, config: &'a ForgeConfig) -> Option<&'a Path> {
+    arguments
+        .get("template_dir")
+        .and_then(|v| v.as_str())
+        .map(Path::new)
+        .or(config.template_dir.as_deref())
+}
 
-    // Check for Windows drive letters (e.g., "C:", "D:")
-    if project_name.len() > 1 && project_name.chars().nth(1) == Some(':') {
-        return Err("Project name cannot contain Windows drive letter".to_string());
+/// Values substituted into generated project files
+///
+/// Templates refer to them as `{project_name}`, `{project_name_snake}`,
+/// `{description}`, `{author}`, `{license}` and `{rmcp_version}`;
+/// `{description_doc}` is the description as `//!` doc comment lines. Other
+/// braces (Rust format strings) are left alone.
+struct TemplateContext<'a> {
+    project_name: &'a str,
+    description: &'a str,
+    author: Option<&'a str>,
+    license: &'a str,
+    rmcp_version: &'a str,
+    protocol_version: ProtocolVersion,
+}
+
+impl TemplateContext<'_> {
+    /// Substitute the context values into a template
+    fn render(&self, template: &str) -> String {
+        [
+            ("{project_name_snake}", self.project_name.replace('-', "_")),
+            (
+                "{project_name_pascal}",
+                to_pascal_case(&self.project_name.replace('-', "_")),
+            ),
+            ("{project_name}", self.project_name.to_string()),
+            ("{description_doc}", doc_comment(self.description)),
+            ("{description}", self.description.to_string()),
+            ("{author}", self.author.unwrap_or_default().to_string()),
+            ("{license}", self.license.to_string()),
+            ("{rmcp_version}", self.rmcp_version.to_string()),
+            (
+                "{protocol_version_const}",
+                protocol::const_name(&self.protocol_version),
+            ),
+        ]
+        .iter()
+        .fold(template.to_string(), |text, (placeholder, value)| {
+            text.replace(placeholder, value)
+        })
     }
+}
 
-    debug!("Project name '{}' passed validation", project_name);
-    Ok(())
+/// `text` as inner doc comment lines, one `//!` line per line of text
+fn doc_comment(text: &str) -> String {
+    if text.trim().is_empty() {
+        return "//!".to_string();
+    }
+    text.lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                "//!".to_string()
+            } else {
+                format!("//! {}", line.trim_end())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Create project directory structure
 ///
 /// Uses the 2-stage calling pattern: retrieves templates from resources
-/// instead of using include_str directly, enabling better separation of
-/// concerns and error visibility when templates are missing.
-async fn create_project_structure(project_name: &str, description: &str) -> Result<(), String> {
+/// (with overrides from `template_dir` applied) instead of using
+/// include_str directly, enabling better separation of concerns and error
+/// visibility when templates are missing. All templates are rendered before
+/// anything is written; written files are rolled back on failure.
+async fn create_project_structure(
+    workspace: &Workspace,
+    context: &TemplateContext<'_>,
+    template_dir: Option<&Path>,
+    execution: &ExecutionContext,
+) -> Result<PathBuf, String> {
+    let project_name = context.project_name;
+
     // Validate project name for security
     validate_project_name(project_name)?;
 
-    let base_dir = Path::new(project_name);
-
-    // Create base directory
-    fs::create_dir_all(base_dir)
-        .map_err(|e| format!("Failed to create project directory: {}", e))?;
-
-    // Create src directory
-    fs::create_dir_all(base_dir.join("src"))
-        .map_err(|e| format!("Failed to create src directory: {}", e))?;
-
-    // Create Cargo.toml
-    let cargo_toml = generate_cargo_toml(project_name, description);
-    fs::write(base_dir.join("Cargo.toml"), cargo_toml)
-        .map_err(|e| format!("Failed to write Cargo.toml: {}", e))?;
-
-    // Get main.rs from resources
-    let main_rs = resources::get_resource("template/main-rs")
-        .ok_or_else(|| "Template 'main.rs' not found in resources".to_string())?
-        .content;
-    fs::write(base_dir.join("src/main.rs"), main_rs)
-        .map_err(|e| format!("Failed to write main.rs: {}", e))?;
-
-    // Get lib.rs from resources
-    let lib_rs = resources::get_resource("template/lib-rs")
-        .ok_or_else(|| "Template 'lib.rs' not found in resources".to_string())?
-        .content;
-    fs::write(base_dir.join("src/lib.rs"), lib_rs)
-        .map_err(|e| format!("Failed to write lib.rs: {}", e))?;
-
-    // Get error.rs from resources
-    let error_rs = resources::get_resource("template/error-rs")
-        .ok_or_else(|| "Template 'error.rs' not found in resources".to_string())?
-        .content;
-    fs::write(base_dir.join("src/error.rs"), error_rs)
-        .map_err(|e| format!("Failed to write error.rs: {}", e))?;
-
-    // Get server.rs from resources
-    let server_rs = resources::get_resource("template/server-rs")
-        .ok_or_else(|| "Template 'server.rs' not found in resources".to_string())?
-        .content;
-    fs::write(base_dir.join("src/server.rs"), server_rs)
-        .map_err(|e| format!("Failed to write server.rs: {}", e))?;
-
-    // Get tools.rs from resources
-    let tools_rs = resources::get_resource("template/tools-rs")
-        .ok_or_else(|| "Template 'tools.rs' not found in resources".to_string())?
-        .content;
-    fs::write(base_dir.join("src/tools.rs"), tools_rs)
-        .map_err(|e| format!("Failed to write tools.rs: {}", e))?;
-
-    // Get resources.rs from resources
-    let resources_rs = resources::get_resource("template/resources-rs")
-        .ok_or_else(|| "Template 'resources.rs' not found in resources".to_string())?
-        .content;
-    fs::write(base_dir.join("src/resources.rs"), resources_rs)
-        .map_err(|e| format!("Failed to write resources.rs: {}", e))?;
-
-    // Create .gitignore
-    let gitignore = "/target\n/Cargo.lock\n.env\n*.swp\n*.swo\n";
-    fs::write(base_dir.join(".gitignore"), gitignore)
-        .map_err(|e| format!("Failed to write .gitignore: {}", e))?;
+    let files = render_project_files(context, template_dir)?;
+
+    let base_dir = workspace.resolve(project_name)?;
+    if base_dir.exists() && !is_empty_dir(&base_dir) {
+        return Err(format!(
+            "Project directory '{}' already exists and is not empty",
+            project_name
+        ));
+    }
+
+    let staging = StagingDir::create(&base_dir)
+        .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+    let total = GENERATION_STEPS;
+    for (step, (relative, content)) in files.iter().enumerate() {
+        let step = step as u32 + 1;
+        let failed = |e: String| {
+            format!(
+                "Project generation failed at step {}/{} (write {}): {}",
+                step, total, relative, e
+            )
+        };
+        execution.check_cancelled().map_err(failed)?;
+        staging
+            .write(relative, content)
+            .map_err(|e| failed(e.to_string()))?;
+
+        execution.report(step, Some(total), format!("Wrote {}", relative));
+        // Let a pending cancellation be delivered between files
+        tokio::task::yield_now().await;
+    }
+
+    execution.check_cancelled()?;
+    staging.commit(&base_dir).map_err(|e| {
+        format!(
+            "Project generation failed at step {}/{} (move into {}): {}",
+            total,
+            total,
+            base_dir.display(),
+            e
+        )
+    })?;
+    execution.report(total, Some(total), format!("Created {}", project_name));
 
     debug!("Project structure created successfully");
-    Ok(())
+    Ok(base_dir)
+}
+
+/// Render every file of a project from the templates
+///
+/// Templates come from the built-in resources with overrides from
+/// `template_dir` applied.
+fn render_project_files(
+    context: &TemplateContext<'_>,
+    template_dir: Option<&Path>,
+) -> Result<[(&'static str, String); PROJECT_FILES], String> {
+    let templates = resources::get_resources_with_overrides(template_dir);
+    let template = |key: &str, name: &str| {
+        templates
+            .get(key)
+            .and_then(resources::Resource::text)
+            .map(|text| context.render(text))
+            .ok_or_else(|| format!("Template '{}' not found in resources", name))
+    };
+
+    Ok([
+        ("Cargo.toml", generate_cargo_toml(context)),
+        ("src/main.rs", template("template/main-rs", "main.rs")?),
+        ("src/lib.rs", template("template/lib-rs", "lib.rs")?),
+        ("src/error.rs", template("template/error-rs", "error.rs")?),
+        (
+            "src/server.rs",
+            template("template/server-rs", "server.rs")?,
+        ),
+        ("src/tools.rs", template("template/tools-rs", "tools.rs")?),
+        (
+            "src/resources.rs",
+            template("template/resources-rs", "resources.rs")?,
+        ),
+        (
+            "tests/server.rs",
+            template("template/tests-rs", "tests.rs")?,
+        ),
+        (".gitignore", GITIGNORE.to_string()),
+    ])
+}
+
+/// Longest project, tool or resource name
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Longest file name most file systems accept
+const MAX_FILE_NAME_BYTES: usize = 255;
+
+/// Resource types `generate_resource` accepts
+pub const RESOURCE_TYPES: [&str; 3] = ["text", "binary", "json"];
+
+/// Number of files in a generated project
+const PROJECT_FILES: usize = 9;
+
+/// Progress steps of a generation: one per file and the final rename
+const GENERATION_STEPS: u32 = PROJECT_FILES as u32 + 1;
+
+/// Contents of the generated .gitignore
+const GITIGNORE: &str = "/target\n/Cargo.lock\n.env\n*.swp\n*.swo\n";
+
+/// Whether `dir` is a directory without entries
+fn is_empty_dir(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false)
+}
+
+/// Hidden directory beside a project that its files are written into first
+///
+/// The staging directory is removed on drop unless it was committed, so a
+/// failed, cancelled or aborted generation leaves nothing behind.
+struct StagingDir {
+    path: PathBuf,
+    committed: bool,
+}
+
+impl StagingDir {
+    /// Create a fresh staging directory next to `target`
+    fn create(target: &Path) -> std::io::Result<Self> {
+        let parent = target.parent().unwrap_or(Path::new("."));
+        let name = target
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        fs::create_dir_all(parent)?;
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or_default();
+        let path = parent.join(format!(
+            ".{}.staging-{}-{}",
+            name,
+            std::process::id(),
+            nanos
+        ));
+        fs::create_dir(&path)?;
+        Ok(Self {
+            path,
+            committed: false,
+        })
+    }
+
+    /// Write `content` to `relative`, creating parent directories
+    fn write(&self, relative: &str, content: &str) -> std::io::Result<()> {
+        let path = self.path.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)
+    }
+
+    /// Atomically move the staged files to `target`
+    ///
+    /// An empty directory at `target` is replaced.
+    fn commit(mut self, target: &Path) -> std::io::Result<()> {
+        if is_empty_dir(target) {
+            fs::remove_dir(target)?;
+        }
+        fs::rename(&self.path, target)?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        if !self.committed {
+            debug!("Removing staging directory {}", self.path.display());
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
 }
 
 /// Generate Cargo.toml content
-fn generate_cargo_toml(project_name: &str, description: &str) -> String {
+///
+/// Context values are written as TOML strings encoded by `toml_edit`, so
+/// quotes, backslashes and line breaks in them stay valid TOML. The
+/// `Cargo.toml.template` resource is a reference only and not used here.
+fn generate_cargo_toml(context: &TemplateContext<'_>) -> String {
+    let authors = context
+        .author
+        .map(|author| {
+            let authors = toml_edit::Array::from_iter([author]);
+            format!("authors = {}\n", authors)
+        })
+        .unwrap_or_default();
     format!(
         r#"[package]
-name = "{}"
+name = {}
 version = "0.1.0"
 edition = "2021"
-rust-version = "1.75"
-description = "{}"
-license = "MIT"
-
+rust-version = "1.85"
+description = {}
+license = {}
+{}
 [dependencies]
-rmcp = {{ version = "0.8", features = ["server"] }}
+rmcp = {{ version = {}, features = ["server", "macros", "transport-io"] }}
 tokio = {{ version = "1.40", features = ["full"] }}
+anyhow = "1.0"
+thiserror = "2.0"
+base64 = "0.22"
+chrono = "0.4"
+schemars = "1.0"
 serde = {{ version = "1.0", features = ["derive"] }}
 serde_json = "1.0"
 tracing = "0.1"
-tracing-subscriber = {{ version = "0.3", features = ["env-filter"] }}
+tracing-subscriber = {{ version = "0.3", features = ["env-filter", "json"] }}
 
 [dev-dependencies]
+rmcp = {{ version = {}, features = ["client"] }}
 tokio-test = "0.4"
 
 [[bin]]
-name = "{}"
+name = {}
 path = "src/main.rs"
 
 [profile.release]
@@ -502,20 +1533,69 @@ lto = true
 codegen-units = 1
 strip = true
 "#,
-        project_name, description, project_name
+        toml_string(context.project_name),
+        toml_string(context.description),
+        toml_string(context.license),
+        authors,
+        toml_string(context.rmcp_version),
+        toml_string(context.rmcp_version),
+        toml_string(context.project_name)
     )
 }
 
+/// `value` as a TOML string
+fn toml_string(value: &str) -> String {
+    toml_edit::Value::from(value).to_string()
+}
+
 /// Generate tool Rust code template
-fn generate_tool_code(tool_name: &str, description: &str) -> String {
+fn generate_tool_code(tool_name: &str, description: &str, draft_body: Option<&str>) -> String {
     let tool_name_snake = tool_name.to_lowercase();
+    let body = match draft_body {
+        Some(draft) => {
+            // Re-indent the draft to the function body, keeping its nesting.
+            // Only the space and tab indentation all lines share is removed.
+            let margin = draft
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+                .reduce(|common, indent| {
+                    let shared = common
+                        .bytes()
+                        .zip(indent.bytes())
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    &common[..shared]
+                })
+                .unwrap_or_default();
+            let indented: Vec<String> = draft
+                .lines()
+                .map(|line| {
+                    if line.trim().is_empty() {
+                        String::new()
+                    } else {
+                        format!("    {}", line.strip_prefix(margin).unwrap_or(line))
+                    }
+                })
+                .collect();
+            format!(
+                "    {}\n{}\n    {}",
+                drafting::DRAFT_BEGIN,
+                indented.join("\n"),
+                drafting::DRAFT_END
+            )
+        }
+        None => {
+            "    // Implementation goes here\n    Ok(\"Tool executed successfully\".to_string())"
+                .to_string()
+        }
+    };
     format!(
         r#"/// {tool_name} Tool
 ///
 /// {description}
 pub async fn execute_{tool_name_snake}() -> Result<String, String> {{
-    // Implementation goes here
-    Ok("Tool executed successfully".to_string())
+{body}
 }}
 
 #[cfg(test)]
@@ -531,14 +1611,79 @@ mod tests {{
 "#,
         tool_name = tool_name,
         tool_name_snake = tool_name_snake,
-        description = description
+        description = description,
+        body = body
     )
 }
 
 /// Generate resource Rust code template
+///
+/// Text and JSON resources read into `text` resource contents; binary
+/// resources read into base64-encoded `blob` resource contents.
 fn generate_resource_code(resource_name: &str, resource_type: &str, description: &str) -> String {
+    let (mime_type, content_impl) = match resource_type {
+        "binary" => (
+            "application/octet-stream",
+            r#"    /// Get resource content as raw bytes
+    pub fn get_content(&self) -> Vec<u8> {
+        // Return resource bytes
+        Vec::new()
+    }
+
+    /// Read the resource as MCP resource contents (base64 blob)
+    pub fn read(&self) -> ResourceContents {
+        use base64::Engine as _;
+
+        ResourceContents::BlobResourceContents {
+            uri: Self::URI.to_string(),
+            mime_type: Some(Self::MIME_TYPE.to_string()),
+            blob: base64::engine::general_purpose::STANDARD.encode(self.get_content()),
+            meta: None,
+        }
+    }"#,
+        ),
+        "json" => (
+            "application/json",
+            r#"    /// Get resource content as a JSON document
+    pub fn get_content(&self) -> String {
+        // Return resource JSON
+        "{}".to_string()
+    }
+
+    /// Read the resource as MCP resource contents
+    pub fn read(&self) -> ResourceContents {
+        ResourceContents::TextResourceContents {
+            uri: Self::URI.to_string(),
+            mime_type: Some(Self::MIME_TYPE.to_string()),
+            text: self.get_content(),
+            meta: None,
+        }
+    }"#,
+        ),
+        _ => (
+            "text/plain",
+            r#"    /// Get resource content
+    pub fn get_content(&self) -> String {
+        // Return resource content
+        String::new()
+    }
+
+    /// Read the resource as MCP resource contents
+    pub fn read(&self) -> ResourceContents {
+        ResourceContents::TextResourceContents {
+            uri: Self::URI.to_string(),
+            mime_type: Some(Self::MIME_TYPE.to_string()),
+            text: self.get_content(),
+            meta: None,
+        }
+    }"#,
+        ),
+    };
+
     format!(
-        r#"/// {resource_name} Resource
+        r#"use rmcp::model::ResourceContents;
+
+/// {resource_name} Resource
 ///
 /// Type: {resource_type}
 /// {description}
@@ -547,20 +1692,35 @@ pub struct {resource_name_pascal} {{
 }}
 
 impl {resource_name_pascal} {{
-    /// Get resource content
-    pub fn get_content(&self) -> String {{
-        // Return resource content
-        String::new()
-    }}
+    /// Resource URI
+    pub const URI: &'static str = "resource://{resource_name}";
+
+    /// Resource MIME type
+    pub const MIME_TYPE: &'static str = "{mime_type}";
+
+{content_impl}
 }}
 "#,
         resource_name = resource_name,
         resource_type = resource_type,
         description = description,
-        resource_name_pascal = to_pascal_case(resource_name)
+        resource_name_pascal = to_pascal_case(resource_name),
+        mime_type = mime_type,
+        content_impl = content_impl
     )
 }
 
+/// Fill a tool's name into a tool template such as `advanced-tool.rs`
+///
+/// Replaces `{tool_name_snake}` and `{tool_name_pascal}` with the names
+/// `generate_tool` derives from `tool_name`.
+pub fn render_tool_template(template: &str, tool_name: &str) -> String {
+    let tool_name_snake = tool_name.to_lowercase();
+    template
+        .replace("{tool_name_pascal}", &to_pascal_case(&tool_name_snake))
+        .replace("{tool_name_snake}", &tool_name_snake)
+}
+
 /// Convert snake_case to PascalCase
 fn to_pascal_case(s: &str) -> String {
     s.split('_')
@@ -621,6 +1781,13 @@ This starts the MCP server, which communicates via stdio with MCP clients.
 cargo test
 ```
 
+Record the `tools/list` snapshot the tests compare against once, and again
+after changing a tool on purpose:
+
+```bash
+UPDATE_SNAPSHOTS=1 cargo test --test server
+```
+
 ### Running Tests with Output
 
 ```bash
@@ -741,6 +1908,183 @@ mod tests {
         let _ = std::fs::remove_dir_all("test_project_example");
     }
 
+    #[tokio::test]
+    async fn test_generate_project_uses_config_values() {
+        let root = std::env::temp_dir().join("mcp_forge_generate_with_config");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("templates")).unwrap();
+        fs::write(
+            root.join("templates/lib.rs.template"),
+            "//! {project_name} by {author}, {license}\n",
+        )
+        .unwrap();
+
+        let config = ForgeConfig {
+            author: Some("Jane Doe <jane@example.com>".to_string()),
+            license: "Apache-2.0".to_string(),
+            rmcp_version: "0.9".to_string(),
+            workspace_root: root.clone(),
+            template_dir: Some(root.join("templates")),
+            log_level: None,
+        };
+        let args = json!({"project_name": "configured-server"});
+        let project = generate_project(&args, &config, &ExecutionContext::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            project.message,
+            format!(
+                "Project 'configured-server' generated successfully in '{}'",
+                root.join("configured-server").display()
+            )
+        );
+
+        let project = root.join("configured-server");
+        let cargo_toml = fs::read_to_string(project.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("license = \"Apache-2.0\""));
+        assert!(cargo_toml.contains("authors = [\"Jane Doe <jane@example.com>\"]"));
+        assert!(cargo_toml.contains("rmcp = { version = \"0.9\""));
+        assert_eq!(
+            fs::read_to_string(project.join("src/lib.rs")).unwrap(),
+            "//! configured-server by Jane Doe <jane@example.com>, Apache-2.0\n"
+        );
+        let main_rs = fs::read_to_string(project.join("src/main.rs")).unwrap();
+        assert!(main_rs.contains("use configured_server::server::create_server;"));
+        assert!(main_rs.contains("configured-server v{}"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_description_with_quotes_and_line_breaks() {
+        let defaults = ForgeConfig::default();
+        let args = json!({
+            "project_name": "quoted",
+            "description": "Say \"hi\" to C:\\Users\n\nand 'bye'",
+            "author": "Jane \"JD\" Doe",
+            "license": "MIT OR Apache-2.0",
+        });
+        let context = template_context(&args, &defaults).unwrap();
+
+        let cargo_toml: toml_edit::DocumentMut = generate_cargo_toml(&context).parse().unwrap();
+        let package = &cargo_toml["package"];
+        assert_eq!(
+            package["description"].as_str(),
+            Some("Say \"hi\" to C:\\Users\n\nand 'bye'")
+        );
+        assert_eq!(package["authors"][0].as_str(), Some("Jane \"JD\" Doe"));
+        assert_eq!(package["license"].as_str(), Some("MIT OR Apache-2.0"));
+
+        assert_eq!(
+            context.render("//! {project_name}\n//!\n{description_doc}\n"),
+            "//! quoted\n//!\n//! Say \"hi\" to C:\\Users\n//!\n//! and 'bye'\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_project_reports_progress() {
+        let root = std::env::temp_dir().join("mcp_forge_generate_progress");
+        let _ = fs::remove_dir_all(&root);
+        let args = json!({
+            "project_name": "progress-server",
+            "workspace_root": root.to_str().unwrap()
+        });
+
+        let (sender, mut updates) = mpsc::unbounded_channel();
+        let execution = ExecutionContext::default().with_progress(sender);
+        generate_project(&args, &ForgeConfig::default(), &execution)
+            .await
+            .unwrap();
+        drop(execution);
+
+        let mut steps = Vec::new();
+        while let Some(update) = updates.recv().await {
+            assert_eq!(update.total, Some(10));
+            steps.push(update.progress);
+        }
+        assert_eq!(steps, (1..=10).collect::<Vec<_>>());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_generate_project_cancelled() {
+        let root = std::env::temp_dir().join("mcp_forge_generate_cancelled");
+        let _ = fs::remove_dir_all(&root);
+        let args = json!({
+            "project_name": "cancelled-server",
+            "workspace_root": root.to_str().unwrap()
+        });
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let error = generate_project(
+            &args,
+            &ForgeConfig::default(),
+            &ExecutionContext::new(cancellation),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("step 1/10 (write Cargo.toml): Cancelled"));
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_generate_project_leaves_nothing_on_failure() {
+        let root = std::env::temp_dir().join("mcp_forge_generate_failure");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("existing-server")).unwrap();
+        fs::write(root.join("existing-server/notes.txt"), "keep").unwrap();
+
+        let args = json!({
+            "project_name": "existing-server",
+            "workspace_root": root.to_str().unwrap()
+        });
+        let error = execute_generate_project(&args).await.unwrap_err();
+        assert!(error.contains("already exists"));
+        assert_eq!(
+            fs::read_to_string(root.join("existing-server/notes.txt")).unwrap(),
+            "keep"
+        );
+
+        // A failed write discards everything staged before it
+        let staging = StagingDir::create(&root.join("failed-server")).unwrap();
+        staging.write("Cargo.toml", "[package]").unwrap();
+        assert!(staging.write("Cargo.toml/nested.rs", "").is_err());
+        drop(staging);
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 1);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_generate_project_protocol_version() {
+        let root = std::env::temp_dir().join("mcp_forge_generate_protocol_version");
+        let _ = fs::remove_dir_all(&root);
+
+        let args = json!({
+            "project_name": "older-server",
+            "workspace_root": root.to_str().unwrap(),
+            "protocol_version": "2025-03-26"
+        });
+        execute_generate_project(&args).await.unwrap();
+        let server_rs = fs::read_to_string(root.join("older-server/src/server.rs")).unwrap();
+        assert!(server_rs.contains("protocol_version: ProtocolVersion::V_2025_03_26,"));
+
+        let args = json!({
+            "project_name": "future-server",
+            "workspace_root": root.to_str().unwrap(),
+            "protocol_version": "2099-01-01"
+        });
+        let error = execute_generate_project(&args).await.unwrap_err();
+        assert!(error.starts_with("Unsupported protocol version '2099-01-01'"));
+        assert!(!root.join("future-server").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[tokio::test]
     async fn test_generate_project_missing_args() {
         let args = json!({});
@@ -758,6 +2102,70 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_generate_tool_with_draft() {
+        let args = json!({
+            "tool_name": "echo",
+            "description": "Echo the input",
+            "draft_body": "if true {\n    return Ok(\"echo\".into());\n}\n\nErr(\"unreachable\".into())"
+        });
+        let code = execute_generate_tool(&args).await.unwrap();
+        let expected = format!(
+            "pub async fn execute_echo() -> Result<String, String> {{\n    {}\n    if true {{\n        return Ok(\"echo\".into());\n    }}\n\n    Err(\"unreachable\".into())\n    {}\n}}\n",
+            drafting::DRAFT_BEGIN,
+            drafting::DRAFT_END
+        );
+        assert!(code.contains(&expected), "{}", code);
+        assert!(!code.contains("Implementation goes here"));
+    }
+
+    #[test]
+    fn test_generate_tool_with_non_ascii_indented_draft() {
+        // A tab, a no-break space and an ideographic space
+        let draft = "\tlet a = 1;\n\u{a0}let b = 2;\n\u{3000}Ok((a + b).to_string())";
+        let code = generate_tool_code("sum", "Add numbers", Some(draft));
+        assert!(code.contains("\n    \tlet a = 1;\n"), "{}", code);
+        assert!(code.contains("\n    \u{a0}let b = 2;\n"), "{}", code);
+        assert!(
+            code.contains("\n    \u{3000}Ok((a + b).to_string())\n"),
+            "{}",
+            code
+        );
+
+        // Only the indentation every line shares is removed
+        let draft = "\t\tlet a = 1;\n\t  Ok(a.to_string())";
+        let code = generate_tool_code("one", "Return one", Some(draft));
+        assert!(
+            code.contains("\n    \tlet a = 1;\n      Ok(a.to_string())\n"),
+            "{}",
+            code
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_resource_binary() {
+        let args = json!({
+            "resource_name": "app_icon",
+            "resource_type": "binary",
+            "description": "Application icon"
+        });
+        let code = execute_generate_resource(&args).await.unwrap();
+        assert!(code.contains("pub struct AppIcon"));
+        assert!(code.contains("BlobResourceContents"));
+        assert!(code.contains("general_purpose::STANDARD.encode"));
+        assert!(code.contains("application/octet-stream"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_resource_invalid_type() {
+        let args = json!({
+            "resource_name": "app_icon",
+            "resource_type": "video"
+        });
+        let result = execute_generate_resource(&args).await;
+        assert!(result.unwrap_err().contains("Invalid resource_type"));
+    }
+
     #[tokio::test]
     async fn test_validate_manifest_valid() {
         let args = json!({
@@ -773,8 +2181,165 @@ mod tests {
         let args = json!({
             "manifest_content": "not valid json"
         });
-        let result = execute_validate_manifest(&args).await;
-        assert!(result.is_err());
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("1:1: error /: unexpected token 'not', expected a value"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_manifest_report_continues_after_syntax_errors() {
+        let args = json!({
+            "manifest_content": "{\n  name: \"test\",\n  \"version\": \"1.0\"\n}"
+        });
+        let report = validate_manifest_report(&args).unwrap();
+        assert!(!report.valid);
+
+        let found: Vec<(usize, &str)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.line.unwrap(), d.pointer.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(1, "/description"), (2, "/name"), (3, "/version")]
+        );
+        assert_eq!(
+            report.diagnostics[2].suggestion.as_deref(),
+            Some("use \"1.0.0\"")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_manifest_fix() {
+        let args = json!({
+            "manifest_content": r#"{"mcpServers": {"forge": {"comand": "mcp-forge", "args": "serve"}}}"#,
+            "fix": true
+        });
+        let summary = execute_validate_manifest(&args).await.unwrap();
+        assert!(summary.contains("Applied 2 fix(es)"));
+        assert!(
+            summary.contains(
+                r#"{"mcpServers": {"forge": {"command": "mcp-forge", "args": ["serve"]}}}"#
+            )
+        );
+
+        let args = json!({
+            "manifest_content": r#"{"name": "test"}"#,
+            "fix": true
+        });
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("added \"version\": \"0.1.0\""));
+        assert!(error.contains("missing required property \"description\""));
+    }
+
+    #[tokio::test]
+    async fn test_validate_manifest_from_yaml_path() {
+        let root = std::env::temp_dir().join("mcp_forge_validate_yaml_path");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("config")).unwrap();
+        fs::write(
+            root.join("config/claude.yaml"),
+            "mcpServers:\n  forge:\n    comand: mcp-forge\n    args: serve\n",
+        )
+        .unwrap();
+
+        let args = json!({
+            "path": "config/claude.yaml",
+            "workspace_root": root.to_str().unwrap()
+        });
+        let report = validate_manifest_report(&args).unwrap();
+        assert_eq!(report.syntax, SourceSyntax::Yaml);
+        assert_eq!(report.format, ManifestFormat::ClaudeDesktopConfig);
+
+        let positions: Vec<(usize, usize, &str)> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.line.unwrap(), d.column.unwrap(), d.pointer.as_str()))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                (2, 3, "/mcpServers/forge/command"),
+                (3, 5, "/mcpServers/forge/comand"),
+                (4, 5, "/mcpServers/forge/args"),
+            ]
+        );
+        assert!(
+            report
+                .summary()
+                .contains("- config/claude.yaml:4:5: error /mcpServers/forge/args")
+        );
+
+        let args = json!({
+            "path": "../outside.json",
+            "workspace_root": root.to_str().unwrap()
+        });
+        assert!(validate_manifest_report(&args).is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_validate_manifest_inline_toml() {
+        let args = json!({
+            "manifest_content": "name = \"demo\"\ndescription = \"Demo\"\nversion = \"1.0\"\n"
+        });
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("(server manifest, TOML source)"));
+        assert!(error.contains("3:1: error /version"));
+
+        let args = json!({"manifest_content": "name = \"demo\"", "fix": true});
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("only supported for JSON"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_manifest_source_arguments() {
+        let error = execute_validate_manifest(&json!({})).await.unwrap_err();
+        assert!(error.contains("manifest_content or path"));
+
+        let args = json!({"manifest_content": "{}", "path": "manifest.json"});
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("not both"));
+
+        let args = json!({"manifest_content": "{}", "syntax": "xml"});
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("Unknown manifest syntax"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_manifest() {
+        let args = json!({"manifest_content": r#"{"mcpServers": {"forge": {"args": []}}}"#});
+        let error = execute_verify_manifest(&args).await.unwrap_err();
+        assert!(error.contains("Manifest is invalid"));
+
+        let args = json!({
+            "manifest_content": r#"{"name": "demo", "version": "1.0.0", "description": "Demo"}"#
+        });
+        let error = execute_verify_manifest(&args).await.unwrap_err();
+        assert!(error.contains("Cannot verify a server manifest"));
+
+        let args = json!({
+            "manifest_content": r#"{"mcpServers": {"forge": {"command": "mcp-forge-missing"}}}"#,
+            "timeout_secs": 2
+        });
+        let report = verify_manifest_report(&args).await.unwrap();
+        assert!(!report.valid);
+        assert_eq!(report.diagnostics[0].pointer, "/mcpServers/forge/command");
+    }
+
+    #[tokio::test]
+    async fn test_check_project_requires_cargo_toml() {
+        let root = std::env::temp_dir().join("mcp_forge_check_project");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("not-a-crate")).unwrap();
+
+        let args = json!({"path": "not-a-crate", "workspace_root": root.to_str().unwrap()});
+        let error = execute_check_project(&args).await.unwrap_err();
+        assert_eq!(error, "No Cargo.toml found in 'not-a-crate'");
+        let args = json!({"path": "../escape", "workspace_root": root.to_str().unwrap()});
+        assert!(execute_check_project(&args).await.is_err());
+
+        let _ = fs::remove_dir_all(&root);
     }
 
     #[tokio::test]
@@ -786,17 +2351,79 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_validate_desktop_config_valid() {
+        let args = json!({
+            "manifest_content": r#"{"mcpServers": {"forge": {"command": "mcp-forge", "args": ["serve"]}}}"#
+        });
+        let result = execute_validate_manifest(&args).await.unwrap();
+        assert!(result.contains("claude_desktop_config.json"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_desktop_config_invalid() {
+        let args = json!({
+            "manifest_content": r#"{"mcpServers": {"forge": {"args": "serve"}}}"#
+        });
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("Found 2 error(s) and 0 warning(s)"));
+        assert!(error.contains("/mcpServers/forge/command"));
+        assert!(error.contains("/mcpServers/forge/args"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_manifest_explicit_format() {
+        let args = json!({
+            "manifest_content": r#"{"name": "test", "version": "0.1.0", "description": "test"}"#,
+            "format": "server_json"
+        });
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("server.json"));
+        assert!(error.contains("no packages or remotes"));
+
+        let args = json!({
+            "manifest_content": "{}",
+            "format": "yaml"
+        });
+        let error = execute_validate_manifest(&args).await.unwrap_err();
+        assert!(error.contains("Unknown manifest format"));
+    }
+
     #[tokio::test]
     async fn test_generate_readme() {
+        let root = std::env::temp_dir().join("mcp_forge_generate_readme");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
         let args = json!({
             "project_name": "test_project_example",
             "description": "A test project",
-            "output_path": "/tmp/test_readme.md"
+            "output_path": "docs/README.md",
+            "workspace_root": root.to_str().unwrap()
         });
         let result = execute_generate_readme(&args).await;
         assert!(result.is_ok());
-        // Clean up
-        let _ = std::fs::remove_file("/tmp/test_readme.md");
+        assert!(root.join("docs/README.md").is_file());
+
+        for output_path in [
+            "/tmp/test_readme.md",
+            "../README.md",
+            "docs",
+            "docs/README.md/x",
+        ] {
+            let args = json!({
+                "project_name": "test_project_example",
+                "output_path": output_path,
+                "workspace_root": root.to_str().unwrap()
+            });
+            assert!(
+                execute_generate_readme(&args).await.is_err(),
+                "{}",
+                output_path
+            );
+        }
+
+        let _ = fs::remove_dir_all(&root);
     }
 
     #[test]
@@ -827,6 +2454,29 @@ mod tests {
         assert!(validate_project_name("/etc/passwd").is_err());
     }
 
+    #[test]
+    fn test_validate_project_name_package_name() {
+        assert!(validate_project_name(&"a".repeat(64)).is_ok());
+        assert!(validate_project_name(&"a".repeat(65)).is_err());
+        assert!(validate_project_name("1project").is_err());
+        assert!(validate_project_name("-project").is_err());
+        assert!(validate_project_name("my project").is_err());
+        assert!(validate_project_name("proj\u{e9}ct").is_err());
+        assert!(validate_project_name("{project_name}").is_err());
+    }
+
+    #[test]
+    fn test_validate_identifier() {
+        assert!(validate_identifier("word_count", "Tool name").is_ok());
+        assert!(validate_identifier("_private", "Tool name").is_ok());
+        assert!(validate_identifier("", "Tool name").is_err());
+        assert!(validate_identifier("2fast", "Tool name").is_err());
+        assert!(validate_identifier("word-count", "Tool name").is_err());
+        assert!(validate_identifier("../evil", "Tool name").is_err());
+        let error = validate_identifier(&"a".repeat(65), "Resource name").unwrap_err();
+        assert!(error.starts_with("Resource name cannot be longer"));
+    }
+
     #[test]
     fn test_validate_project_name_empty() {
         assert!(validate_project_name("").is_err());
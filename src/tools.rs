@@ -5,6 +5,8 @@
 //! - Tool code generation
 //! - Resource creation
 //! - Manifest validation
+//! - Manifest verification against live servers
+//! - Compile checks of generated projects
 
 use serde::{Deserialize, Serialize};
 
@@ -56,6 +58,15 @@ pub fn get_available_tools() -> Vec<ToolDefinition> {
                 "description": {
                     "type": "string",
                     "description": "Project description"
+                },
+                "verify": {
+                    "type": "boolean",
+                    "description": "Run cargo check on the generated project (defaults to false)"
+                },
+                "protocol_version": {
+                    "type": "string",
+                    "enum": ["2024-11-05", "2025-03-26", "2025-06-18"],
+                    "description": "Newest MCP protocol version the generated server implements (defaults to 2025-06-18)"
                 }
             },
             "required": ["project_name"]
@@ -71,6 +82,10 @@ pub fn get_available_tools() -> Vec<ToolDefinition> {
                     "description": {
                         "type": "string",
                         "description": "Tool description"
+                    },
+                    "draft_implementation": {
+                        "type": "boolean",
+                        "description": "Ask the client's model to draft the implementation (defaults to false)"
                     }
                 },
                 "required": ["tool_name", "description"]
@@ -124,11 +139,156 @@ pub fn get_available_tools() -> Vec<ToolDefinition> {
                 "properties": {
                     "manifest_content": {
                         "type": "string",
-                        "description": "Contents of the manifest file (JSON format)"
+                        "description": "Contents of the manifest file (JSON, YAML or TOML)"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Workspace-relative path of the manifest file (instead of manifest_content)"
+                    },
+                    "syntax": {
+                        "type": "string",
+                        "enum": ["auto", "json", "yaml", "toml"],
+                        "description": "Manifest syntax (defaults to the file extension or content)"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": [
+                            "auto",
+                            "claude_desktop_config",
+                            "server_json",
+                            "desktop_extension",
+                            "server_manifest"
+                        ],
+                        "description": "Manifest format (defaults to auto-detection)"
+                    },
+                    "fix": {
+                        "type": "boolean",
+                        "description": "Apply automatic fixes and return the corrected manifest"
                     }
                 },
-                "required": ["manifest_content"]
+                "oneOf": [
+                    {"required": ["manifest_content"]},
+                    {"required": ["path"]}
+                ]
             })),
+        ToolDefinition::new(
+            "verify_manifest",
+            "Launch the servers a manifest configures and check them against it",
+        )
+        .with_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "manifest_content": {
+                    "type": "string",
+                    "description": "Contents of the manifest file (JSON, YAML or TOML)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Workspace-relative path of the manifest file (instead of manifest_content)"
+                },
+                "syntax": {
+                    "type": "string",
+                    "enum": ["auto", "json", "yaml", "toml"],
+                    "description": "Manifest syntax (defaults to the file extension or content)"
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["auto", "claude_desktop_config", "desktop_extension"],
+                    "description": "Manifest format (defaults to auto-detection)"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": 120,
+                    "description": "Seconds each server gets to start and answer (defaults to 10)"
+                }
+            },
+            "oneOf": [
+                {"required": ["manifest_content"]},
+                {"required": ["path"]}
+            ]
+        })),
+        ToolDefinition::new(
+            "check_project",
+            "Run cargo check on a project and return the compiler diagnostics",
+        )
+        .with_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Workspace-relative path of the project directory"
+                }
+            },
+            "required": ["path"]
+        })),
+        ToolDefinition::new(
+            "test_server",
+            "Launch an MCP server over stdio and run the conformance suite against it",
+        )
+        .with_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "command": {
+                    "type": "string",
+                    "description": "Executable that starts the server"
+                },
+                "args": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Command-line arguments"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Seconds the server gets for each check (defaults to 10)"
+                },
+                "junit": {
+                    "type": "boolean",
+                    "description": "Also return the report as JUnit XML (defaults to false)"
+                }
+            },
+            "required": ["command"]
+        })),
+        ToolDefinition::new(
+            "fuzz_tools",
+            "Launch an MCP server over stdio and call its tools with random and adversarial arguments generated from their input schemas",
+        )
+        .with_schema(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "command": {
+                    "type": "string",
+                    "description": "Executable that starts the server"
+                },
+                "args": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Command-line arguments"
+                },
+                "tools": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Tools to fuzz (defaults to all)"
+                },
+                "fixed": {
+                    "type": "object",
+                    "description": "Arguments passed unchanged to every tool that declares them"
+                },
+                "cases": {
+                    "type": "integer",
+                    "description": "Random cases per tool (defaults to 20)"
+                },
+                "seed": {
+                    "type": "integer",
+                    "description": "Seed the cases are generated from (defaults to a random seed)"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Seconds the server gets to answer each call (defaults to 5)"
+                }
+            },
+            "required": ["command"]
+        })),
     ]
 }
 
@@ -139,7 +299,7 @@ mod tests {
     #[test]
     fn test_get_available_tools() {
         let tools = get_available_tools();
-        assert_eq!(tools.len(), 5);
+        assert_eq!(tools.len(), 9);
         assert_eq!(tools[0].name, "generate_project");
     }
 
@@ -159,5 +319,9 @@ mod tests {
         assert!(tool_names.contains(&"generate_resource"));
         assert!(tool_names.contains(&"generate_readme"));
         assert!(tool_names.contains(&"validate_manifest"));
+        assert!(tool_names.contains(&"verify_manifest"));
+        assert!(tool_names.contains(&"check_project"));
+        assert!(tool_names.contains(&"test_server"));
+        assert!(tool_names.contains(&"fuzz_tools"));
     }
 }
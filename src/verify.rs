@@ -0,0 +1,718 @@
+//! Manifest verification against live servers
+//!
+//! A manifest can be schema-valid yet point at a server that doesn't start
+//! or doesn't expose what it claims. Verification launches every server the
+//! manifest configures over stdio and checks that it speaks MCP:
+//! - the server must complete `initialize` within the timeout
+//! - `tools/list`, `resources/list` and `prompts/list` must answer for every
+//!   capability the server advertises
+//! - tools and prompts declared by a desktop extension manifest must match
+//!   what the server exposes
+//!
+//! Problems are reported as [`Diagnostic`]s pointing at the server entry in
+//! the manifest, so they carry the same line/column information as
+//! validation diagnostics.
+
+use crate::json_source::{ParsedJson, push_pointer};
+use crate::manifest::{Diagnostic, ManifestFormat, Severity};
+use rmcp::model::{Implementation, ServerCapabilities};
+use rmcp::transport::{IntoTransport, TokioChildProcess};
+use rmcp::{RoleClient, ServiceExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{ChildStderr, Command};
+use tokio::task::JoinSet;
+
+/// Default time a server gets to initialize and answer the list requests
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of trailing stderr lines quoted when a server fails
+const STDERR_TAIL_LINES: usize = 5;
+
+/// Time to wait for a failed server's stderr to close after it was stopped
+pub(crate) const STDERR_GRACE: Duration = Duration::from_millis(500);
+
+/// Names a manifest declares for tools or prompts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredNames {
+    /// Declared names with the JSON pointer of each declaration
+    pub names: Vec<(String, String)>,
+    /// Whether the list is complete; false when the manifest says more
+    /// entries are generated at runtime (`tools_generated`, `prompts_generated`)
+    pub complete: bool,
+}
+
+/// How to launch one server configured by a manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchSpec {
+    /// Server name (the `mcpServers` key or the extension name)
+    pub name: String,
+    /// JSON pointer of the server's launch configuration in the manifest
+    pub pointer: String,
+    /// Executable to run
+    pub command: String,
+    /// Command-line arguments
+    pub args: Vec<String>,
+    /// Extra environment variables
+    pub env: BTreeMap<String, String>,
+    /// Tools the manifest declares, if it lists any
+    pub declared_tools: Option<DeclaredNames>,
+    /// Prompts the manifest declares, if it lists any
+    pub declared_prompts: Option<DeclaredNames>,
+}
+
+impl LaunchSpec {
+    /// The command line, for display
+    pub fn command_line(&self) -> String {
+        std::iter::once(&self.command)
+            .chain(&self.args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// What a launched server reported about itself
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerVerification {
+    /// Server name from the manifest
+    pub name: String,
+    /// Command line that was launched
+    pub command: String,
+    /// Whether `initialize` completed
+    pub initialized: bool,
+    /// Protocol version the server negotiated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+    /// Name and version the server reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_info: Option<Implementation>,
+    /// Capabilities the server advertised
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<ServerCapabilities>,
+    /// Names of the tools the server exposes
+    pub tools: Vec<String>,
+    /// URIs of the resources the server exposes
+    pub resources: Vec<String>,
+    /// Names of the prompts the server exposes
+    pub prompts: Vec<String>,
+}
+
+/// Result of verifying every server of a manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationReport {
+    /// Format of the verified manifest
+    pub format: ManifestFormat,
+    /// Whether every server started and matched the manifest
+    pub valid: bool,
+    /// Number of errors
+    pub errors: usize,
+    /// Number of warnings
+    pub warnings: usize,
+    /// One entry per launched server, in manifest order
+    pub servers: Vec<ServerVerification>,
+    /// All problems, in source order
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl VerificationReport {
+    /// Human-readable summary of every server and problem
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Verified {} server(s) from {}: {}.",
+            self.servers.len(),
+            self.format,
+            if self.valid {
+                "all passed"
+            } else {
+                "problems found"
+            }
+        );
+
+        for server in &self.servers {
+            summary.push_str(&format!("\n- {}: `{}` ", server.name, server.command));
+            match (&server.server_info, &server.protocol_version) {
+                (Some(info), Some(version)) => summary.push_str(&format!(
+                    "-> {} {}, protocol {}; {} tool(s), {} resource(s), {} prompt(s)",
+                    info.name,
+                    info.version,
+                    version,
+                    server.tools.len(),
+                    server.resources.len(),
+                    server.prompts.len()
+                )),
+                _ => summary.push_str("did not initialize"),
+            }
+        }
+
+        if !self.diagnostics.is_empty() {
+            summary.push_str(&format!(
+                "\nFound {} error(s) and {} warning(s):",
+                self.errors, self.warnings
+            ));
+            for diagnostic in &self.diagnostics {
+                summary.push_str(&format!("\n- {}", diagnostic));
+            }
+        }
+        summary
+    }
+}
+
+/// Launch every server of a parsed manifest and check it against the manifest
+///
+/// Servers run concurrently with `manifest_dir` as working directory; each
+/// one gets `timeout` to initialize and answer the list requests.
+///
+/// # Errors
+///
+/// Returns an error if the manifest could not be parsed or its format does
+/// not describe how to launch a server.
+pub async fn verify(
+    parsed: &ParsedJson,
+    format: ManifestFormat,
+    manifest_dir: &Path,
+    timeout: Duration,
+) -> Result<VerificationReport, String> {
+    let manifest = parsed
+        .value
+        .as_ref()
+        .ok_or_else(|| "Manifest could not be parsed".to_string())?;
+    let specs = launch_specs(manifest, format, manifest_dir)?;
+
+    let mut tasks = JoinSet::new();
+    for (index, spec) in specs.into_iter().enumerate() {
+        let cwd = manifest_dir.to_path_buf();
+        tasks.spawn(async move { (index, verify_server(spec, cwd, timeout).await) });
+    }
+
+    let mut results = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        results.push(result.map_err(|e| format!("Verification task failed: {}", e))?);
+    }
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut servers = Vec::new();
+    let mut diagnostics = Vec::new();
+    for (_, (server, server_diagnostics)) in results {
+        servers.push(server);
+        diagnostics.extend(server_diagnostics.into_iter().map(|d| d.located(parsed)));
+    }
+    diagnostics.sort_by_key(|d| (d.line.is_none(), d.line, d.column, d.severity));
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    Ok(VerificationReport {
+        format,
+        valid: errors == 0,
+        errors,
+        warnings: diagnostics.len() - errors,
+        servers,
+        diagnostics,
+    })
+}
+
+/// Collect the servers a manifest configures
+///
+/// - `claude_desktop_config.json`: every `mcpServers` entry with a command
+/// - desktop extension `manifest.json`: `server.mcp_config`, with
+///   `${__dirname}` expanded to `manifest_dir`, plus the declared tools and
+///   prompts
+///
+/// # Errors
+///
+/// Returns an error for formats that don't say how to launch a server.
+pub fn launch_specs(
+    manifest: &Value,
+    format: ManifestFormat,
+    manifest_dir: &Path,
+) -> Result<Vec<LaunchSpec>, String> {
+    match format {
+        ManifestFormat::ClaudeDesktopConfig => {
+            let servers = manifest
+                .get("mcpServers")
+                .and_then(Value::as_object)
+                .ok_or_else(|| "Manifest has no mcpServers entries".to_string())?;
+            Ok(servers
+                .iter()
+                .filter_map(|(name, entry)| {
+                    Some(LaunchSpec {
+                        name: name.clone(),
+                        pointer: push_pointer("/mcpServers", name),
+                        command: entry.get("command")?.as_str()?.to_string(),
+                        args: string_list(entry.get("args")),
+                        env: string_map(entry.get("env")),
+                        declared_tools: None,
+                        declared_prompts: None,
+                    })
+                })
+                .collect())
+        }
+        ManifestFormat::DesktopExtension => {
+            let config = manifest
+                .pointer("/server/mcp_config")
+                .ok_or_else(|| "Manifest has no server.mcp_config".to_string())?;
+            let command = config
+                .get("command")
+                .and_then(Value::as_str)
+                .ok_or_else(|| "server.mcp_config has no command".to_string())?;
+
+            let dirname = manifest_dir.to_string_lossy();
+            let expand = |value: &str| value.replace("${__dirname}", &dirname);
+            Ok(vec![LaunchSpec {
+                name: manifest
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or("extension")
+                    .to_string(),
+                pointer: "/server/mcp_config".to_string(),
+                command: expand(command),
+                args: string_list(config.get("args"))
+                    .iter()
+                    .map(|arg| expand(arg))
+                    .collect(),
+                env: string_map(config.get("env"))
+                    .into_iter()
+                    .map(|(key, value)| (key, expand(&value)))
+                    .collect(),
+                declared_tools: declared_names(manifest, "tools", "tools_generated"),
+                declared_prompts: declared_names(manifest, "prompts", "prompts_generated"),
+            }])
+        }
+        other => Err(format!(
+            "Cannot verify a {}: only claude_desktop_config.json and desktop extension \
+             manifest.json describe how to launch a server",
+            other
+        )),
+    }
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect()
+}
+
+fn string_map(value: Option<&Value>) -> BTreeMap<String, String> {
+    value
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+        .collect()
+}
+
+fn declared_names(manifest: &Value, key: &str, generated: &str) -> Option<DeclaredNames> {
+    let items = manifest.get(key)?.as_array()?;
+    Some(DeclaredNames {
+        names: items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let name = item.get("name")?.as_str()?;
+                Some((name.to_string(), format!("/{}/{}/name", key, index)))
+            })
+            .collect(),
+        complete: manifest.get(generated).and_then(Value::as_bool) != Some(true),
+    })
+}
+
+/// Launch one server and inspect it
+async fn verify_server(
+    spec: LaunchSpec,
+    cwd: PathBuf,
+    timeout: Duration,
+) -> (ServerVerification, Vec<Diagnostic>) {
+    let mut verification = ServerVerification {
+        name: spec.name.clone(),
+        command: spec.command_line(),
+        ..Default::default()
+    };
+    let mut diagnostics = Vec::new();
+
+    let mut command = Command::new(&spec.command);
+    command
+        .args(&spec.args)
+        .envs(&spec.env)
+        .current_dir(&cwd)
+        .kill_on_drop(true);
+
+    let (transport, stderr) = match TokioChildProcess::builder(command)
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(spawned) => spawned,
+        Err(e) => {
+            diagnostics.push(
+                Diagnostic::error(
+                    push_pointer(&spec.pointer, "command"),
+                    format!("failed to start `{}`: {}", spec.command, e),
+                )
+                .with_suggestion("check that the command is installed and on PATH"),
+            );
+            return (verification, diagnostics);
+        }
+    };
+    let stderr_tail = Arc::new(Mutex::new(VecDeque::with_capacity(STDERR_TAIL_LINES)));
+    let stderr_task =
+        stderr.map(|stderr| tokio::spawn(capture_stderr(stderr, stderr_tail.clone())));
+
+    tracing::debug!("Verifying server '{}': {}", spec.name, verification.command);
+    let failure = match tokio::time::timeout(
+        timeout,
+        inspect(transport, &spec, &mut verification, &mut diagnostics),
+    )
+    .await
+    {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e),
+        Err(_) if verification.initialized => Some(format!(
+            "server did not answer the list requests within {}s",
+            timeout.as_secs_f32()
+        )),
+        Err(_) => Some(format!(
+            "server did not complete initialize within {}s",
+            timeout.as_secs_f32()
+        )),
+    };
+
+    if let Some(message) = failure {
+        // Descendants of the server may keep stderr open, so don't wait for EOF
+        if let Some(task) = stderr_task {
+            let _ = tokio::time::timeout(STDERR_GRACE, task).await;
+        }
+        let tail = stderr_tail.lock().map(|t| t.clone()).unwrap_or_default();
+        let message = if tail.is_empty() {
+            message
+        } else {
+            format!(
+                "{}; stderr: {}",
+                message,
+                tail.into_iter().collect::<Vec<_>>().join(" | ")
+            )
+        };
+        diagnostics.push(Diagnostic::error(&spec.pointer, message));
+    }
+
+    (verification, diagnostics)
+}
+
+/// Keep the last lines a server writes to stderr
+pub(crate) async fn capture_stderr(stderr: ChildStderr, tail: Arc<Mutex<VecDeque<String>>>) {
+    let mut lines = BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Ok(mut tail) = tail.lock() else {
+            return;
+        };
+        if tail.len() == STDERR_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+}
+
+/// Initialize a server over `transport`, list what it exposes and compare
+/// it with the manifest
+///
+/// Results are written into `verification` as they arrive, so a timeout
+/// still leaves what was learned so far. Returns an error if `initialize`
+/// fails.
+async fn inspect<T, E, A>(
+    transport: T,
+    spec: &LaunchSpec,
+    verification: &mut ServerVerification,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<(), String>
+where
+    T: IntoTransport<RoleClient, E, A>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let client = ().serve(transport).await.map_err(|e| format!("initialize failed: {}", e))?;
+
+    let capabilities = match client.peer_info() {
+        Some(info) => {
+            verification.initialized = true;
+            verification.protocol_version = Some(info.protocol_version.to_string());
+            verification.server_info = Some(info.server_info.clone());
+            verification.capabilities = Some(info.capabilities.clone());
+            info.capabilities.clone()
+        }
+        None => ServerCapabilities::default(),
+    };
+
+    if capabilities.tools.is_some() {
+        match client.list_all_tools().await {
+            Ok(tools) => {
+                verification.tools = tools.into_iter().map(|t| t.name.to_string()).collect();
+            }
+            Err(e) => diagnostics.push(Diagnostic::error(
+                &spec.pointer,
+                format!("tools/list failed: {}", e),
+            )),
+        }
+    }
+    if capabilities.resources.is_some() {
+        match client.list_all_resources().await {
+            Ok(resources) => {
+                verification.resources = resources.into_iter().map(|r| r.uri.clone()).collect();
+            }
+            Err(e) => diagnostics.push(Diagnostic::error(
+                &spec.pointer,
+                format!("resources/list failed: {}", e),
+            )),
+        }
+    }
+    if capabilities.prompts.is_some() {
+        match client.list_all_prompts().await {
+            Ok(prompts) => {
+                verification.prompts = prompts.into_iter().map(|p| p.name).collect();
+            }
+            Err(e) => diagnostics.push(Diagnostic::error(
+                &spec.pointer,
+                format!("prompts/list failed: {}", e),
+            )),
+        }
+    }
+
+    compare_declared(
+        "tools",
+        spec.declared_tools.as_ref(),
+        capabilities.tools.is_some(),
+        &verification.tools,
+        diagnostics,
+    );
+    compare_declared(
+        "prompts",
+        spec.declared_prompts.as_ref(),
+        capabilities.prompts.is_some(),
+        &verification.prompts,
+        diagnostics,
+    );
+
+    if let Err(e) = client.cancel().await {
+        tracing::warn!("Failed to stop server '{}': {}", spec.name, e);
+    }
+    Ok(())
+}
+
+/// Report differences between declared and exposed tools or prompts
+fn compare_declared(
+    kind: &str,
+    declared: Option<&DeclaredNames>,
+    advertised: bool,
+    actual: &[String],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(declared) = declared else {
+        return;
+    };
+    let singular = kind.trim_end_matches('s');
+
+    if !declared.names.is_empty() && !advertised {
+        diagnostics.push(Diagnostic::error(
+            format!("/{}", kind),
+            format!(
+                "manifest declares {} but the server does not advertise the {} capability",
+                kind, kind
+            ),
+        ));
+        return;
+    }
+
+    for (name, pointer) in &declared.names {
+        if !actual.contains(name) {
+            diagnostics.push(
+                Diagnostic::error(
+                    pointer.clone(),
+                    format!(
+                        "{} \"{}\" is declared but the server does not expose it",
+                        singular, name
+                    ),
+                )
+                .with_suggestion(format!("remove the {} or implement it", singular)),
+            );
+        }
+    }
+
+    if declared.complete {
+        for name in actual {
+            if !declared.names.iter().any(|(declared, _)| declared == name) {
+                diagnostics.push(
+                    Diagnostic::warning(
+                        format!("/{}", kind),
+                        format!(
+                            "server exposes {} \"{}\" that the manifest does not declare",
+                            singular, name
+                        ),
+                    )
+                    .with_suggestion(format!("add \"{}\" to {}", name, kind)),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MCPForgeServer;
+    use serde_json::json;
+
+    fn extension_manifest() -> Value {
+        json!({
+            "manifest_version": "0.2",
+            "name": "forge",
+            "server": {
+                "type": "binary",
+                "entry_point": "server/mcp-forge",
+                "mcp_config": {
+                    "command": "${__dirname}/server/mcp-forge",
+                    "args": ["--root", "${__dirname}"],
+                    "env": {"MCP_FORGE_WORKSPACE": "${__dirname}/work"}
+                }
+            },
+            "tools": [{"name": "generate_project"}, {"name": "deploy"}]
+        })
+    }
+
+    #[test]
+    fn test_launch_specs() {
+        let config = json!({"mcpServers": {
+            "forge": {"command": "mcp-forge", "args": ["serve"], "env": {"RUST_LOG": "debug"}},
+            "remote": {"url": "https://example.com/mcp"}
+        }});
+        let specs =
+            launch_specs(&config, ManifestFormat::ClaudeDesktopConfig, Path::new(".")).unwrap();
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].pointer, "/mcpServers/forge");
+        assert_eq!(specs[0].command_line(), "mcp-forge serve");
+        assert_eq!(specs[0].env["RUST_LOG"], "debug");
+
+        let specs = launch_specs(
+            &extension_manifest(),
+            ManifestFormat::DesktopExtension,
+            Path::new("/opt/ext"),
+        )
+        .unwrap();
+        assert_eq!(
+            specs[0].command_line(),
+            "/opt/ext/server/mcp-forge --root /opt/ext"
+        );
+        assert_eq!(specs[0].env["MCP_FORGE_WORKSPACE"], "/opt/ext/work");
+        assert_eq!(
+            specs[0].declared_tools.as_ref().unwrap().names[1],
+            ("deploy".to_string(), "/tools/1/name".to_string())
+        );
+
+        let error = launch_specs(&json!({}), ManifestFormat::ServerJson, Path::new("."));
+        assert!(error.unwrap_err().contains("Cannot verify a server.json"));
+    }
+
+    #[tokio::test]
+    async fn test_inspect_in_process_server() {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            let service = MCPForgeServer::default().serve(server_io).await?;
+            service.waiting().await?;
+            anyhow::Ok(())
+        });
+
+        let mut spec = launch_specs(
+            &extension_manifest(),
+            ManifestFormat::DesktopExtension,
+            Path::new("."),
+        )
+        .unwrap()
+        .remove(0);
+        spec.declared_prompts = Some(DeclaredNames {
+            names: Vec::new(),
+            complete: false,
+        });
+
+        let mut verification = ServerVerification::default();
+        let mut diagnostics = Vec::new();
+        inspect(client_io, &spec, &mut verification, &mut diagnostics)
+            .await
+            .unwrap();
+
+        assert!(verification.initialized);
+        assert_eq!(verification.server_info.unwrap().name, "MCP Forge");
+        assert!(
+            verification
+                .tools
+                .contains(&"validate_manifest".to_string())
+        );
+        assert!(!verification.resources.is_empty());
+        assert!(!verification.prompts.is_empty());
+
+        let errors: Vec<&str> = diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["tool \"deploy\" is declared but the server does not expose it"]
+        );
+        assert!(diagnostics.iter().any(|d| d.severity == Severity::Warning
+            && d.message
+                == "server exposes tool \"validate_manifest\" that the manifest does not declare"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_reports_launch_failures() {
+        let text = r#"{"mcpServers": {"missing": {"command": "mcp-forge-does-not-exist"}}}"#;
+        let parsed = crate::json_source::parse(text);
+        let report = verify(
+            &parsed,
+            ManifestFormat::ClaudeDesktopConfig,
+            Path::new("."),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+        assert!(!report.valid);
+        assert!(!report.servers[0].initialized);
+        assert_eq!(report.diagnostics[0].pointer, "/mcpServers/missing/command");
+        assert_eq!(report.diagnostics[0].column, Some(29));
+        assert!(
+            report
+                .summary()
+                .contains("missing: `mcp-forge-does-not-exist` did not initialize")
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_verify_times_out_and_quotes_stderr() {
+        let text = r#"{"mcpServers": {"silent": {"command": "sh", "args": ["-c", "echo starting up >&2; sleep 5"]}}}"#;
+        let parsed = crate::json_source::parse(text);
+        let report = verify(
+            &parsed,
+            ManifestFormat::ClaudeDesktopConfig,
+            Path::new("."),
+            Duration::from_millis(500),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.errors, 1);
+        assert_eq!(report.diagnostics[0].pointer, "/mcpServers/silent");
+        assert_eq!(
+            report.diagnostics[0].message,
+            "server did not complete initialize within 0.5s; stderr: starting up"
+        );
+    }
+}
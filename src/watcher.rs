@@ -0,0 +1,259 @@
+//! Resource change watcher
+//!
+//! Polls on-disk directories that back MCP resources (template overrides and
+//! generated projects) and reports which resource URIs changed since the last
+//! poll. Polling keeps the watcher portable and dependency-free; the server
+//! turns the reported changes into `notifications/resources/updated` and
+//! `notifications/resources/list_changed` messages.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A change to the resources exposed by the server
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ResourceChange {
+    /// The content of the resource with the given URI changed
+    Updated(String),
+    /// Resources were added or removed
+    ListChanged,
+}
+
+/// What a watched directory contains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    /// Template overrides (`<name>.template` files replacing built-in templates)
+    Templates,
+    /// Files of a generated project
+    Project,
+}
+
+/// A directory watched for changes
+#[derive(Debug, Clone)]
+struct WatchRoot {
+    dir: PathBuf,
+    uri_prefix: String,
+    kind: WatchKind,
+}
+
+/// Modification stamp of a single file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// Polling watcher over a set of directories
+#[derive(Debug, Default)]
+pub struct ResourceWatcher {
+    roots: Vec<WatchRoot>,
+    snapshot: HashMap<PathBuf, FileStamp>,
+}
+
+impl ResourceWatcher {
+    /// Create a watcher with no watched directories
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Watch a directory, reporting changes under URIs starting with `uri_prefix`
+    ///
+    /// Files already present are recorded in the snapshot so that only later
+    /// changes are reported. Watching the same directory twice is a no-op.
+    pub fn watch(
+        &mut self,
+        dir: impl Into<PathBuf>,
+        uri_prefix: impl Into<String>,
+        kind: WatchKind,
+    ) {
+        let dir = dir.into();
+        if self.roots.iter().any(|root| root.dir == dir) {
+            return;
+        }
+
+        let root = WatchRoot {
+            dir,
+            uri_prefix: uri_prefix.into().trim_end_matches('/').to_string(),
+            kind,
+        };
+        self.snapshot.extend(scan_dir(&root.dir));
+        self.roots.push(root);
+    }
+
+    /// Whether any directory is being watched
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Rescan all watched directories and report changes since the last poll
+    pub fn poll(&mut self) -> Vec<ResourceChange> {
+        let mut changes = Vec::new();
+        let mut list_changed = false;
+        let mut snapshot = HashMap::new();
+
+        for root in &self.roots {
+            let current = scan_dir(&root.dir);
+
+            for (path, stamp) in &current {
+                match self.snapshot.get(path) {
+                    Some(previous) if previous == stamp => {}
+                    Some(_) => changes.push(ResourceChange::Updated(root.uri_for(path))),
+                    None => {
+                        list_changed |= root.kind == WatchKind::Templates;
+                        changes.push(ResourceChange::Updated(root.uri_for(path)));
+                    }
+                }
+            }
+
+            for path in self.snapshot.keys() {
+                if path.starts_with(&root.dir) && !current.contains_key(path) {
+                    list_changed |= root.kind == WatchKind::Templates;
+                    changes.push(ResourceChange::Updated(root.uri_for(path)));
+                }
+            }
+
+            snapshot.extend(current);
+        }
+
+        self.snapshot = snapshot;
+        changes.sort();
+        if list_changed {
+            changes.push(ResourceChange::ListChanged);
+        }
+        changes
+    }
+}
+
+impl WatchRoot {
+    /// Map a file path under this root to its resource URI
+    fn uri_for(&self, path: &Path) -> String {
+        let relative = path.strip_prefix(&self.dir).unwrap_or(path);
+        let mut relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if self.kind == WatchKind::Templates
+            && let Some(stem) = relative.strip_suffix(".template")
+        {
+            relative = stem.to_string();
+        }
+
+        format!("{}/{}", self.uri_prefix, relative)
+    }
+}
+
+/// Recursively collect file stamps under a directory
+///
+/// Missing or unreadable directories yield an empty snapshot; `target` and
+/// hidden directories are skipped so builds inside generated projects don't
+/// flood clients with notifications.
+fn scan_dir(dir: &Path) -> HashMap<PathBuf, FileStamp> {
+    let mut files = HashMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if name != "target" && !name.starts_with('.') {
+                    pending.push(path);
+                }
+            } else {
+                files.insert(
+                    path,
+                    FileStamp {
+                        modified: metadata.modified().ok(),
+                        len: metadata.len(),
+                    },
+                );
+            }
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mcp_forge_watcher_{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_no_changes_after_watch() {
+        let dir = temp_dir("no_changes");
+        fs::write(dir.join("main.rs.template"), "fn main() {}").unwrap();
+
+        let mut watcher = ResourceWatcher::new();
+        watcher.watch(&dir, "forge://templates", WatchKind::Templates);
+        assert!(watcher.poll().is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_template_modified_and_added() {
+        let dir = temp_dir("templates");
+        fs::write(dir.join("main.rs.template"), "fn main() {}").unwrap();
+
+        let mut watcher = ResourceWatcher::new();
+        watcher.watch(&dir, "forge://templates/", WatchKind::Templates);
+
+        fs::write(dir.join("main.rs.template"), "fn main() { println!(); }").unwrap();
+        assert_eq!(
+            watcher.poll(),
+            vec![ResourceChange::Updated(
+                "forge://templates/main.rs".to_string()
+            )]
+        );
+
+        fs::write(dir.join("extra.rs.template"), "// extra").unwrap();
+        assert_eq!(
+            watcher.poll(),
+            vec![
+                ResourceChange::Updated("forge://templates/extra.rs".to_string()),
+                ResourceChange::ListChanged,
+            ]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_project_files_changed() {
+        let dir = temp_dir("project");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+
+        let mut watcher = ResourceWatcher::new();
+        watcher.watch(&dir, "forge://workspace/demo", WatchKind::Project);
+
+        fs::remove_file(dir.join("src/main.rs")).unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("target/build.log"), "ignored").unwrap();
+        assert_eq!(
+            watcher.poll(),
+            vec![ResourceChange::Updated(
+                "forge://workspace/demo/src/main.rs".to_string()
+            )]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
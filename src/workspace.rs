@@ -0,0 +1,520 @@
+//! Workspace sandbox
+//!
+//! The workspace is the directory MCP Forge generates projects into. Every
+//! path that tools write to or that clients read through the
+//! `forge://workspace/...` resource namespace is resolved relative to the
+//! workspace root and checked against the same sandbox rules, so neither
+//! reads nor writes can escape it.
+
+use crate::resources::Resource;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// URI prefix of the workspace resource namespace
+pub const WORKSPACE_URI_PREFIX: &str = "forge://workspace/";
+
+/// Largest file served through the workspace namespace (10 MiB)
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Validate a workspace-relative path to prevent path traversal attacks
+///
+/// Checks that the path:
+/// - Does not contain path traversal sequences (../, .., ./, etc.)
+/// - Does not start with / (absolute paths)
+/// - Does not contain null bytes
+/// - Does not start with a Windows drive letter
+///
+/// `label` names the value in error messages (e.g. "Project name").
+pub fn validate_relative_path(path: &str, label: &str) -> Result<(), String> {
+    // Check for path traversal patterns
+    if path.contains("..") {
+        return Err(format!("{} cannot contain '..' (path traversal)", label));
+    }
+
+    // Check for absolute paths
+    if path.starts_with('/') || path.starts_with('\\') {
+        return Err(format!("{} cannot be an absolute path", label));
+    }
+
+    // Check for null bytes
+    if path.contains('\0') {
+        return Err(format!("{} cannot contain null bytes", label));
+    }
+
+    // Check for suspicious patterns
+    if path.contains("./") || path.ends_with("/.") || path == "." {
+        return Err(format!("{} cannot contain path traversal patterns", label));
+    }
+
+    // Check for Windows drive letters (e.g., "C:", "D:")
+    if path.len() > 1 && path.chars().nth(1) == Some(':') {
+        return Err(format!("{} cannot contain Windows drive letter", label));
+    }
+
+    Ok(())
+}
+
+/// Kind of a directory entry in a workspace listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    /// A regular file
+    File,
+    /// A directory
+    Directory,
+}
+
+/// A single entry of a workspace directory listing
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryEntry {
+    /// File or directory name
+    pub name: String,
+    /// Resource URI of the entry
+    pub uri: String,
+    /// Entry kind
+    #[serde(rename = "type")]
+    pub kind: EntryKind,
+    /// File size in bytes (files only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Guessed MIME type (files only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Directory listing returned for `forge://workspace/<dir>` resources
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryListing {
+    /// Resource URI of the directory
+    pub uri: String,
+    /// Workspace-relative path of the directory ("" for the root)
+    pub path: String,
+    /// Directory entries, directories first, each group sorted by name
+    pub entries: Vec<DirectoryEntry>,
+}
+
+/// Sandboxed view of the workspace directory
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    root: PathBuf,
+}
+
+impl Workspace {
+    /// Create a workspace rooted at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Workspace root directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve a workspace-relative path, enforcing the sandbox
+    ///
+    /// An empty path resolves to the workspace root. The path may not go
+    /// through a symlink below the root, and its deepest existing ancestor is
+    /// canonicalized and must stay inside the workspace, so paths that do not
+    /// exist yet cannot be written through a link out of the sandbox either.
+    pub fn resolve(&self, relative: &str) -> Result<PathBuf, String> {
+        let relative = relative.trim_end_matches('/');
+        if relative.is_empty() {
+            return Ok(self.root.clone());
+        }
+
+        validate_relative_path(relative, "Path")?;
+        let path = self.root.join(relative);
+
+        // Deepest existing ancestor of the path, checking each component
+        let mut existing = None;
+        let mut current = self.root.clone();
+        for component in Path::new(relative).components() {
+            current.push(component);
+            match fs::symlink_metadata(&current) {
+                Ok(metadata) if metadata.file_type().is_symlink() => {
+                    return Err(format!(
+                        "Path '{}' goes through the symlink '{}'",
+                        relative,
+                        current.display()
+                    ));
+                }
+                Ok(_) => existing = Some(current.clone()),
+                Err(_) => break,
+            }
+        }
+
+        if let Some(existing) = existing {
+            let root = self
+                .root
+                .canonicalize()
+                .map_err(|e| format!("Failed to resolve workspace root: {}", e))?;
+            let canonical = existing
+                .canonicalize()
+                .map_err(|e| format!("Failed to resolve path '{}': {}", relative, e))?;
+            if !canonical.starts_with(&root) {
+                return Err(format!("Path '{}' escapes the workspace", relative));
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Build the resource URI for a workspace-relative path
+    pub fn uri_for(relative: &str) -> String {
+        format!("{}{}", WORKSPACE_URI_PREFIX, relative.trim_matches('/'))
+    }
+
+    /// Read a `forge://workspace/...` resource
+    ///
+    /// Directories are returned as a JSON [`DirectoryListing`]; files are
+    /// returned as text when they are valid UTF-8 and as binary otherwise,
+    /// with a MIME type guessed from the file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkspaceError::InvalidPath`] for URIs outside the namespace
+    /// or paths rejected by the sandbox, and [`WorkspaceError::NotFound`] for
+    /// missing files.
+    pub fn read_resource(&self, uri: &str) -> Result<Resource, WorkspaceError> {
+        let relative = uri
+            .strip_prefix(WORKSPACE_URI_PREFIX)
+            .ok_or_else(|| WorkspaceError::InvalidPath(format!("Not a workspace URI: {}", uri)))?;
+        let relative = relative.trim_end_matches('/');
+        let path = self
+            .resolve(relative)
+            .map_err(WorkspaceError::InvalidPath)?;
+
+        let metadata = fs::metadata(&path)
+            .map_err(|_| WorkspaceError::NotFound(format!("Resource not found: {}", uri)))?;
+
+        if metadata.is_dir() {
+            let listing = self.list_directory(relative)?;
+            let json = serde_json::to_string_pretty(&listing)
+                .map_err(|e| WorkspaceError::Io(format!("Failed to serialize listing: {}", e)))?;
+            return Ok(Resource::new(
+                Self::uri_for(relative),
+                display_name(relative),
+                "application/json",
+                json,
+            ));
+        }
+
+        if metadata.len() > MAX_FILE_SIZE {
+            return Err(WorkspaceError::InvalidPath(format!(
+                "File '{}' is too large to read ({} bytes, limit {})",
+                relative,
+                metadata.len(),
+                MAX_FILE_SIZE
+            )));
+        }
+
+        let bytes = fs::read(&path)
+            .map_err(|e| WorkspaceError::Io(format!("Failed to read '{}': {}", relative, e)))?;
+        let mime_type = guess_mime_type(&path);
+        let name = display_name(relative);
+
+        Ok(match String::from_utf8(bytes) {
+            Ok(text) if !is_binary_mime_type(mime_type) => {
+                Resource::new(Self::uri_for(relative), name, mime_type, text)
+            }
+            Ok(text) => Resource::blob(Self::uri_for(relative), name, mime_type, text),
+            Err(e) => Resource::blob(Self::uri_for(relative), name, mime_type, e.into_bytes()),
+        })
+    }
+
+    /// Read a workspace file as UTF-8 text
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WorkspaceError::InvalidPath`] for paths rejected by the
+    /// sandbox, directories and files over the size limit,
+    /// [`WorkspaceError::NotFound`] for missing files and
+    /// [`WorkspaceError::Io`] for unreadable or non-UTF-8 files.
+    pub fn read_to_string(&self, relative: &str) -> Result<String, WorkspaceError> {
+        let path = self
+            .resolve(relative)
+            .map_err(WorkspaceError::InvalidPath)?;
+        let metadata = fs::metadata(&path)
+            .map_err(|_| WorkspaceError::NotFound(format!("File not found: {}", relative)))?;
+
+        if metadata.is_dir() {
+            return Err(WorkspaceError::InvalidPath(format!(
+                "'{}' is a directory",
+                relative
+            )));
+        }
+        if metadata.len() > MAX_FILE_SIZE {
+            return Err(WorkspaceError::InvalidPath(format!(
+                "File '{}' is too large to read ({} bytes, limit {})",
+                relative,
+                metadata.len(),
+                MAX_FILE_SIZE
+            )));
+        }
+
+        fs::read_to_string(&path)
+            .map_err(|e| WorkspaceError::Io(format!("Failed to read '{}': {}", relative, e)))
+    }
+
+    /// List the entries of a workspace directory
+    pub fn list_directory(&self, relative: &str) -> Result<DirectoryListing, WorkspaceError> {
+        let relative = relative.trim_matches('/');
+        let path = self
+            .resolve(relative)
+            .map_err(WorkspaceError::InvalidPath)?;
+
+        let read_dir = fs::read_dir(&path).map_err(|_| {
+            WorkspaceError::NotFound(format!("Resource not found: {}", Self::uri_for(relative)))
+        })?;
+
+        let mut entries: Vec<DirectoryEntry> = read_dir
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let metadata = entry.metadata().ok()?;
+                let child = if relative.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", relative, name)
+                };
+                let entry = if metadata.is_dir() {
+                    DirectoryEntry {
+                        uri: format!("{}/", Self::uri_for(&child)),
+                        name,
+                        kind: EntryKind::Directory,
+                        size: None,
+                        mime_type: None,
+                    }
+                } else {
+                    DirectoryEntry {
+                        uri: Self::uri_for(&child),
+                        mime_type: Some(guess_mime_type(&entry.path()).to_string()),
+                        name,
+                        kind: EntryKind::File,
+                        size: Some(metadata.len()),
+                    }
+                };
+                Some(entry)
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            (a.kind != EntryKind::Directory, &a.name)
+                .cmp(&(b.kind != EntryKind::Directory, &b.name))
+        });
+
+        Ok(DirectoryListing {
+            uri: Self::uri_for(relative),
+            path: relative.to_string(),
+            entries,
+        })
+    }
+}
+
+/// Errors raised when reading workspace resources
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceError {
+    /// The URI or path is malformed or rejected by the sandbox
+    InvalidPath(String),
+    /// The file or directory does not exist
+    NotFound(String),
+    /// The file exists but could not be read
+    Io(String),
+}
+
+impl std::fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPath(msg) | Self::NotFound(msg) | Self::Io(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceError {}
+
+/// Guess a MIME type from a file extension
+pub fn guess_mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("rs") => "text/x-rust",
+        Some("toml") => "application/toml",
+        Some("json") => "application/json",
+        Some("yaml" | "yml") => "application/yaml",
+        Some("md") => "text/markdown",
+        Some("html" | "htm") => "text/html",
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        Some("ts") => "text/typescript",
+        Some("py") => "text/x-python",
+        Some("sh") => "text/x-shellscript",
+        Some("txt" | "lock" | "template") => "text/plain",
+        Some("xml") => "application/xml",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("ico") => "image/x-icon",
+        Some("webp") => "image/webp",
+        Some("pdf") => "application/pdf",
+        Some("zip") => "application/zip",
+        Some("gz" | "tgz") => "application/gzip",
+        Some("tar") => "application/x-tar",
+        Some("wasm") => "application/wasm",
+        None if path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.')) =>
+        {
+            "text/plain"
+        }
+        _ => "application/octet-stream",
+    }
+}
+
+/// Whether content of this MIME type should always be served as a blob
+fn is_binary_mime_type(mime_type: &str) -> bool {
+    !(mime_type.starts_with("text/")
+        || mime_type.ends_with("json")
+        || mime_type.ends_with("toml")
+        || mime_type.ends_with("yaml")
+        || mime_type.ends_with("xml")
+        || mime_type == "application/octet-stream")
+}
+
+/// Human-readable resource name for a workspace-relative path
+fn display_name(relative: &str) -> String {
+    if relative.is_empty() {
+        "Workspace".to_string()
+    } else {
+        relative.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::ResourceContent;
+
+    fn temp_workspace(name: &str) -> Workspace {
+        let dir = std::env::temp_dir().join(format!("mcp_forge_workspace_{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("demo/src")).unwrap();
+        fs::write(dir.join("demo/Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+        fs::write(dir.join("demo/src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("demo/icon.png"), [0x89, b'P', b'N', b'G']).unwrap();
+        Workspace::new(dir)
+    }
+
+    #[test]
+    fn test_validate_relative_path() {
+        assert!(validate_relative_path("demo/src/main.rs", "Path").is_ok());
+        assert!(validate_relative_path("../evil", "Path").is_err());
+        assert!(validate_relative_path("/etc/passwd", "Path").is_err());
+        assert!(validate_relative_path("./demo", "Path").is_err());
+        assert!(validate_relative_path("C:/demo", "Path").is_err());
+        assert_eq!(
+            validate_relative_path("a\0b", "Project name").unwrap_err(),
+            "Project name cannot contain null bytes"
+        );
+    }
+
+    #[test]
+    fn test_read_directory_listing() {
+        let workspace = temp_workspace("listing");
+        let resource = workspace.read_resource("forge://workspace/demo").unwrap();
+        assert_eq!(resource.mime_type, "application/json");
+
+        let listing: serde_json::Value = serde_json::from_str(resource.text().unwrap()).unwrap();
+        assert_eq!(listing["path"], "demo");
+        let entries = listing["entries"].as_array().unwrap();
+        assert_eq!(entries[0]["name"], "src");
+        assert_eq!(entries[0]["type"], "directory");
+        assert_eq!(entries[0]["uri"], "forge://workspace/demo/src/");
+        assert_eq!(entries[1]["name"], "Cargo.toml");
+        assert_eq!(entries[1]["mimeType"], "application/toml");
+
+        let _ = fs::remove_dir_all(workspace.root());
+    }
+
+    #[test]
+    fn test_read_files() {
+        let workspace = temp_workspace("files");
+
+        let main_rs = workspace
+            .read_resource("forge://workspace/demo/src/main.rs")
+            .unwrap();
+        assert_eq!(main_rs.mime_type, "text/x-rust");
+        assert_eq!(main_rs.text(), Some("fn main() {}\n"));
+
+        let icon = workspace
+            .read_resource("forge://workspace/demo/icon.png")
+            .unwrap();
+        assert_eq!(icon.mime_type, "image/png");
+        assert!(matches!(icon.content, ResourceContent::Blob(_)));
+
+        let _ = fs::remove_dir_all(workspace.root());
+    }
+
+    #[test]
+    fn test_read_rejects_traversal_and_missing() {
+        let workspace = temp_workspace("sandbox");
+
+        assert!(matches!(
+            workspace.read_resource("forge://workspace/../etc/passwd"),
+            Err(WorkspaceError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            workspace.read_resource("forge://workspace/demo/missing.rs"),
+            Err(WorkspaceError::NotFound(_))
+        ));
+        assert!(matches!(
+            workspace.read_resource("forge://templates/main.rs"),
+            Err(WorkspaceError::InvalidPath(_))
+        ));
+
+        let _ = fs::remove_dir_all(workspace.root());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_rejects_symlink_escape() {
+        let workspace = temp_workspace("symlink");
+        std::os::unix::fs::symlink("/etc", workspace.root().join("demo/etc")).unwrap();
+
+        assert!(matches!(
+            workspace.read_resource("forge://workspace/demo/etc/hostname"),
+            Err(WorkspaceError::InvalidPath(_))
+        ));
+
+        let _ = fs::remove_dir_all(workspace.root());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_rejects_new_paths_below_symlinks() {
+        let workspace = temp_workspace("symlink_parent");
+        let outside = std::env::temp_dir().join("mcp_forge_workspace_symlink_parent_outside");
+        let _ = fs::remove_dir_all(&outside);
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, workspace.root().join("link")).unwrap();
+        std::os::unix::fs::symlink(
+            outside.join("missing"),
+            workspace.root().join("demo/dangling"),
+        )
+        .unwrap();
+
+        assert!(workspace.resolve("link/README.md").is_err());
+        assert!(workspace.resolve("link/new/README.md").is_err());
+        assert!(workspace.resolve("demo/dangling").is_err());
+        assert!(workspace.resolve("demo/dangling/README.md").is_err());
+        assert!(workspace.resolve("demo/new/README.md").is_ok());
+
+        let _ = fs::remove_dir_all(workspace.root());
+        let _ = fs::remove_dir_all(&outside);
+    }
+}
@@ -0,0 +1,346 @@
+//! Position-tracking YAML parser
+//!
+//! Builds the same [`ParsedJson`] model as [`json_source`] from a YAML
+//! document, recording the line and column of every value and mapping key
+//! in the original YAML text, so YAML manifests go through the same schema
+//! validation and report positions in the file the user wrote.
+//!
+//! Plain scalars are resolved with the YAML core schema (`true`, `1.5`,
+//! `~`, ...); quoted scalars are always strings. Anchors and aliases are
+//! expanded. Only the first document of a stream is read.
+//!
+//! [`json_source`]: crate::json_source
+
+use crate::json_source::{
+    MAX_DEPTH, ParsedJson, Position, PositionCursor, SyntaxError, position_at, push_pointer,
+};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use yaml_rust2::Yaml;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+
+/// Parse YAML text into the common document model
+pub fn parse(text: &str) -> ParsedJson {
+    let mut builder = Builder {
+        cursor: PositionCursor::new(text),
+        end: position_at(text, text.len()),
+        parsed: ParsedJson::default(),
+        stack: Vec::new(),
+        anchors: HashMap::new(),
+        root: None,
+        truncated: false,
+    };
+
+    let mut parser = Parser::new_from_str(text);
+    if let Err(e) = parser.load(&mut builder, false) {
+        let position = builder.position(e.marker());
+        builder.parsed.errors.push(SyntaxError {
+            position,
+            pointer: builder.current_pointer(),
+            message: e.info().to_string(),
+            suggestion: None,
+        });
+        // Keep what was parsed before the error, closing open collections
+        while !builder.stack.is_empty() {
+            builder.close();
+        }
+    }
+
+    let mut parsed = builder.parsed;
+    match builder.root {
+        Some(root) => parsed.value = Some(root),
+        None if parsed.errors.is_empty() => parsed.errors.push(SyntaxError {
+            position: position_at(text, 0),
+            pointer: String::new(),
+            message: "document is empty".to_string(),
+            suggestion: Some("provide a YAML mapping".to_string()),
+        }),
+        None => {}
+    }
+    parsed
+}
+
+/// A collection being built
+enum Frame {
+    Mapping {
+        pointer: String,
+        anchor: usize,
+        map: Map<String, Value>,
+        /// Key waiting for its value
+        key: Option<String>,
+        /// Whether the first key has not been seen yet; block mappings are
+        /// reported after their first key, so its position is used instead
+        unplaced: bool,
+    },
+    Sequence {
+        pointer: String,
+        anchor: usize,
+        items: Vec<Value>,
+    },
+}
+
+struct Builder<'a> {
+    cursor: PositionCursor<'a>,
+    /// Position of the end of the text
+    end: Position,
+    parsed: ParsedJson,
+    stack: Vec<Frame>,
+    anchors: HashMap<usize, Value>,
+    root: Option<Value>,
+    /// Whether events are ignored after nesting too deeply
+    truncated: bool,
+}
+
+impl Builder<'_> {
+    /// Convert a YAML marker (character index, 0-based column) to a position
+    fn position(&mut self, marker: &Marker) -> Position {
+        self.cursor.at_char(marker.index())
+    }
+
+    /// Pointer of the value that the next event starts
+    fn current_pointer(&self) -> String {
+        match self.stack.last() {
+            None => String::new(),
+            Some(Frame::Mapping {
+                pointer,
+                key: Some(key),
+                ..
+            }) => push_pointer(pointer, key),
+            Some(Frame::Mapping { pointer, .. }) => pointer.clone(),
+            Some(Frame::Sequence { pointer, items, .. }) => format!("{}/{}", pointer, items.len()),
+        }
+    }
+
+    /// Whether the next scalar is a mapping key
+    fn expects_key(&self) -> bool {
+        matches!(self.stack.last(), Some(Frame::Mapping { key: None, .. }))
+    }
+
+    /// Attach a completed value to the enclosing collection
+    fn attach(&mut self, value: Value, anchor: usize) {
+        if anchor != 0 {
+            self.anchors.insert(anchor, value.clone());
+        }
+        match self.stack.last_mut() {
+            None => self.root = Some(value),
+            Some(Frame::Mapping { map, key, .. }) => {
+                if let Some(key) = key.take() {
+                    map.insert(key, value);
+                }
+            }
+            Some(Frame::Sequence { items, .. }) => items.push(value),
+        }
+    }
+
+    /// Use a scalar (or collection, stringified) as the pending mapping key
+    fn set_key(&mut self, key: String, position: Position) {
+        let Some(Frame::Mapping {
+            pointer,
+            map,
+            key: pending,
+            unplaced,
+            ..
+        }) = self.stack.last_mut()
+        else {
+            return;
+        };
+
+        let member = push_pointer(pointer, &key);
+        if *unplaced {
+            *unplaced = false;
+            let pointer = pointer.clone();
+            if self
+                .parsed
+                .value_position(&pointer)
+                .is_none_or(|start| position.offset < start.offset)
+            {
+                self.parsed.record_value(&pointer, position, None);
+            }
+        }
+        if map.contains_key(&key) {
+            self.parsed.errors.push(SyntaxError {
+                position,
+                pointer: member.clone(),
+                message: format!("duplicate key \"{}\"", key),
+                suggestion: Some("remove one of the duplicate entries".to_string()),
+            });
+        }
+        *pending = Some(key);
+        self.parsed.record_key(&member, position, None);
+    }
+
+    /// Pop the innermost collection and attach it to its parent
+    fn close(&mut self) {
+        let (value, anchor) = match self.stack.pop() {
+            Some(Frame::Mapping { map, anchor, .. }) => (Value::Object(map), anchor),
+            Some(Frame::Sequence { items, anchor, .. }) => (Value::Array(items), anchor),
+            None => return,
+        };
+        if self.expects_key() {
+            // Complex keys are not valid in JSON; use their JSON text as the key
+            let key = value.to_string();
+            self.set_key(key, self.end);
+        } else {
+            self.attach(value, anchor);
+        }
+    }
+}
+
+impl MarkedEventReceiver for Builder<'_> {
+    fn on_event(&mut self, event: Event, marker: Marker) {
+        if self.truncated {
+            return;
+        }
+        let position = self.position(&marker);
+        if matches!(event, Event::MappingStart(..) | Event::SequenceStart(..))
+            && self.stack.len() == MAX_DEPTH
+        {
+            self.parsed.errors.push(SyntaxError {
+                position,
+                pointer: self.current_pointer(),
+                message: format!("values are nested more than {} levels deep", MAX_DEPTH),
+                suggestion: Some("flatten the document".to_string()),
+            });
+            // Keep the collections opened so far
+            self.truncated = true;
+            while !self.stack.is_empty() {
+                self.close();
+            }
+            return;
+        }
+        match event {
+            Event::Scalar(text, style, anchor, tag) => {
+                if self.expects_key() {
+                    self.set_key(text, position);
+                    return;
+                }
+                let value = if style == TScalarStyle::Plain && tag.is_none() {
+                    resolve_plain(&text)
+                } else {
+                    Value::String(text)
+                };
+                let pointer = self.current_pointer();
+                self.parsed.record_value(&pointer, position, None);
+                self.attach(value, anchor);
+            }
+            Event::Alias(anchor) => {
+                let value = self.anchors.get(&anchor).cloned().unwrap_or(Value::Null);
+                if self.expects_key() {
+                    self.set_key(
+                        value
+                            .as_str()
+                            .map_or_else(|| value.to_string(), str::to_string),
+                        position,
+                    );
+                    return;
+                }
+                let pointer = self.current_pointer();
+                self.parsed.record_value(&pointer, position, None);
+                self.attach(value, 0);
+            }
+            Event::MappingStart(anchor, _) => {
+                let pointer = self.current_pointer();
+                self.parsed.record_value(&pointer, position, None);
+                self.stack.push(Frame::Mapping {
+                    pointer,
+                    anchor,
+                    map: Map::new(),
+                    key: None,
+                    unplaced: true,
+                });
+            }
+            Event::SequenceStart(anchor, _) => {
+                let pointer = self.current_pointer();
+                self.parsed.record_value(&pointer, position, None);
+                self.stack.push(Frame::Sequence {
+                    pointer,
+                    anchor,
+                    items: Vec::new(),
+                });
+            }
+            Event::MappingEnd | Event::SequenceEnd => self.close(),
+            _ => {}
+        }
+    }
+}
+
+/// Resolve a plain scalar with the YAML core schema
+fn resolve_plain(text: &str) -> Value {
+    match Yaml::from_str(text) {
+        Yaml::Null => Value::Null,
+        Yaml::Boolean(b) => Value::Bool(b),
+        Yaml::Integer(i) => Value::Number(i.into()),
+        Yaml::Real(real) => real
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map_or_else(|| Value::String(text.to_string()), Value::Number),
+        _ => Value::String(text.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_yaml_with_positions() {
+        let text =
+            "name: demo\nversion: 1.0\nservers:\n  - command: \"forge\"\n    args: [serve]\n";
+        let parsed = parse(text);
+        assert!(parsed.errors.is_empty());
+        assert_eq!(
+            parsed.value,
+            Some(json!({
+                "name": "demo",
+                "version": 1.0,
+                "servers": [{"command": "forge", "args": ["serve"]}]
+            }))
+        );
+
+        let key = parsed.key_position("/servers/0/args").unwrap();
+        assert_eq!((key.line, key.column), (5, 5));
+        let value = parsed.value_position("/servers/0/command").unwrap();
+        assert_eq!((value.line, value.column), (4, 14));
+        let mapping = parsed.value_position("/servers/0").unwrap();
+        assert_eq!((mapping.line, mapping.column), (4, 5));
+    }
+
+    #[test]
+    fn test_anchors_and_duplicates() {
+        let parsed = parse("base: &b {x: 1}\ncopy: *b\ncopy: 2\n");
+        assert_eq!(parsed.value, Some(json!({"base": {"x": 1}, "copy": 2})));
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].message, "duplicate key \"copy\"");
+        assert_eq!(parsed.errors[0].position.line, 3);
+    }
+
+    #[test]
+    fn test_syntax_error_keeps_partial_document() {
+        let parsed = parse("name: demo\nargs: [serve\nversion: 1\n");
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].position.line, 3);
+        assert_eq!(parsed.value.unwrap()["name"], "demo");
+
+        let parsed = parse("");
+        assert_eq!(parsed.errors[0].message, "document is empty");
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let deep = format!(
+            "{}1{}",
+            "[".repeat(MAX_DEPTH + 1),
+            "]".repeat(MAX_DEPTH + 1)
+        );
+        let parsed = parse(&deep);
+        assert_eq!(parsed.errors.len(), 1, "{:?}", parsed.errors);
+        assert_eq!(
+            parsed.errors[0].message,
+            format!("values are nested more than {} levels deep", MAX_DEPTH)
+        );
+        assert!(parsed.value.is_some());
+    }
+}
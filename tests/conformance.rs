@@ -0,0 +1,56 @@
+//! The mcp-forge binary must pass its own conformance suite
+//!
+//! Runs `mcp-forge serve` over stdio through [`conformance::test_server`],
+//! which covers the parts an in-process test can't: the real stdio
+//! transport and a clean exit once stdin closes.
+
+use mcp_forge::conformance::{self, Outcome};
+use std::path::Path;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_forge_binary_conforms() {
+    let report = conformance::test_server(
+        env!("CARGO_BIN_EXE_mcp-forge"),
+        &["serve".to_string()],
+        Path::new(env!("CARGO_TARGET_TMPDIR")),
+        conformance::DEFAULT_TIMEOUT,
+    )
+    .await;
+
+    assert!(report.success(), "{}", report.summary());
+    assert_eq!(report.skipped, 0);
+    let shutdown = report.checks.last().unwrap();
+    assert_eq!(
+        (shutdown.name.as_str(), shutdown.outcome),
+        ("shutdown", Outcome::Passed)
+    );
+}
+
+#[tokio::test]
+async fn test_server_that_never_initializes() {
+    let report = conformance::test_server(
+        env!("CARGO_BIN_EXE_mcp-forge"),
+        &["no-such-command".to_string()],
+        Path::new("."),
+        Duration::from_secs(5),
+    )
+    .await;
+
+    assert!(!report.success());
+    assert_eq!(report.checks[0].name, "initialize");
+    assert!(
+        report.checks[0]
+            .message
+            .as_deref()
+            .unwrap()
+            .contains("; stderr: Usage: mcp-forge"),
+        "{}",
+        report.summary()
+    );
+    assert!(
+        report
+            .junit_xml()
+            .contains("<failure message=\"initialize failed")
+    );
+}
@@ -0,0 +1,123 @@
+//! Fuzzing `MCPForgeServer` with arguments generated from its tool schemas
+//!
+//! Every tool that doesn't launch processes is fuzzed in-process, with the
+//! workspace in a scratch directory so generated files stay out of the
+//! repository. `validate_project_name` is exercised through
+//! `generate_project` and the manifest parsers through `validate_manifest`.
+//! The binary is fuzzed once over stdio to check that it never panics, and
+//! the manifest parsers are fed the fuzzer's malformed documents directly.
+
+use mcp_forge::MCPForgeServer;
+use mcp_forge::fuzz::{self, FuzzOptions};
+use rmcp::ServiceExt;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Time all malformed documents together get to parse
+const PARSE_LIMIT: Duration = Duration::from_secs(10);
+
+/// Tools that only read and write the workspace
+const FUZZED_TOOLS: [&str; 5] = [
+    "generate_project",
+    "generate_readme",
+    "generate_resource",
+    "generate_tool",
+    "validate_manifest",
+];
+
+/// An empty directory for one test under the target directory
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn options() -> FuzzOptions {
+    FuzzOptions::default()
+        .with_seed(0x5eed)
+        .with_tools(FUZZED_TOOLS)
+        .with_fixed("verify", json!(false))
+}
+
+// Several workers, so a hung handler times out instead of stalling the test
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fuzz_forge_server() {
+    let root = scratch_dir("fuzz_forge_server");
+    let server = MCPForgeServer::default().with_workspace_root(&root);
+    let (server_transport, client_transport) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        let running = server.serve(server_transport).await?;
+        running.waiting().await?;
+        anyhow::Ok(())
+    });
+
+    let report = fuzz::run_fuzz(client_transport, "MCPForgeServer", &options()).await;
+    assert!(report.success(), "{}", report.summary());
+    assert_eq!(report.tools, FUZZED_TOOLS.len());
+    assert!(report.cases > 300, "{}", report.summary());
+
+    // Nothing escaped the workspace
+    assert!(!root.join("..").join("escape").exists());
+}
+
+#[tokio::test]
+async fn test_fuzz_binary() {
+    let root = scratch_dir("fuzz_binary");
+    let options = options()
+        .with_tools(["generate_project", "validate_manifest"])
+        .with_random_cases(5);
+    let report = fuzz::fuzz_server(
+        env!("CARGO_BIN_EXE_mcp-forge"),
+        &["serve".to_string()],
+        &root,
+        &options,
+    )
+    .await;
+    assert!(report.success(), "{}", report.summary());
+    assert!(report.panics.is_empty());
+}
+
+#[test]
+fn test_parsers_finish_on_malformed_documents() {
+    let documents = fuzz::malformed_documents();
+    let (progress, parsing) = mpsc::channel();
+    std::thread::spawn(move || {
+        for document in &documents {
+            for (syntax, parse) in [
+                ("json", mcp_forge::json_source::parse as fn(&str) -> _),
+                ("yaml", mcp_forge::yaml_source::parse),
+                ("toml", mcp_forge::toml_source::parse),
+            ] {
+                // Report the document before parsing, so a hang names it
+                progress
+                    .send(Some(format!("{} {:?}", syntax, document)))
+                    .unwrap();
+                let parsed = parse(document);
+                if syntax == "json" {
+                    assert!(!parsed.errors.is_empty(), "{:?} parsed cleanly", document);
+                }
+            }
+        }
+        progress.send(None).unwrap();
+    });
+
+    let deadline = std::time::Instant::now() + PARSE_LIMIT;
+    let mut current = String::new();
+    loop {
+        let left = deadline.saturating_duration_since(std::time::Instant::now());
+        match parsing.recv_timeout(left) {
+            Ok(None) => break,
+            Ok(Some(document)) => current = document,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                panic!(
+                    "parsing did not finish within {:?}: {}",
+                    PARSE_LIMIT, current
+                )
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => panic!("parser panicked on {}", current),
+        }
+    }
+}
@@ -0,0 +1,478 @@
+//! Every `MCPForgeServer` handler, exercised through the protocol layer
+//!
+//! Uses [`harness::TestHarness`] to send real JSON-RPC requests over an
+//! in-memory transport, covering the success paths and the error codes
+//! clients rely on.
+
+use mcp_forge::MCPForgeServer;
+use mcp_forge::harness::{self, HarnessClient, TestHarness};
+use mcp_forge::logging::{ClientLogLayer, ClientLoggers};
+use mcp_forge::tool_executor;
+use rmcp::model::{
+    ErrorCode, LoggingLevel, ProtocolVersion, RawContent, ResourceContents, SetLevelRequestParam,
+    SubscribeRequestParam, UnsubscribeRequestParam,
+};
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// An empty directory for one test under the target directory
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+async fn start() -> TestHarness {
+    TestHarness::start(MCPForgeServer::default()).await.unwrap()
+}
+
+/// Poll `condition` for up to five seconds
+async fn eventually(mut condition: impl FnMut() -> bool) -> bool {
+    for _ in 0..50 {
+        if condition() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_initialize() {
+    let harness = start().await;
+    let info = harness.peer_info().unwrap();
+    assert_eq!(info.server_info.name, "MCP Forge");
+    assert_eq!(info.protocol_version, ProtocolVersion::V_2025_06_18);
+    assert!(info.capabilities.tools.is_some());
+    assert!(info.capabilities.logging.is_some());
+    harness.shutdown().await;
+
+    let client = HarnessClient::new().with_protocol_version(ProtocolVersion::V_2024_11_05);
+    let harness = TestHarness::start_with(MCPForgeServer::default(), client)
+        .await
+        .unwrap();
+    assert_eq!(
+        harness.peer_info().unwrap().protocol_version,
+        ProtocolVersion::V_2024_11_05
+    );
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_list_tools() {
+    let harness = start().await;
+    let tools = harness.list_all_tools().await.unwrap();
+    let names: Vec<&str> = tools.iter().map(|tool| tool.name.as_ref()).collect();
+    assert_eq!(
+        names,
+        [
+            "check_project",
+            "fuzz_tools",
+            "generate_project",
+            "generate_readme",
+            "generate_resource",
+            "generate_tool",
+            "test_server",
+            "validate_manifest",
+            "verify_manifest",
+        ]
+    );
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_generate_project() {
+    let root = scratch_dir("handlers_generate_project");
+    let server = MCPForgeServer::default().with_workspace_root(&root);
+    let harness = TestHarness::start(server).await.unwrap();
+
+    let result = harness
+        .call_tool(
+            "generate_project",
+            json!({"project_name": "weather", "description": "Weather lookups"}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert!(root.join("weather/Cargo.toml").exists());
+    let RawContent::ResourceLink(link) = &result.content.last().unwrap().raw else {
+        panic!("expected a resource link: {:?}", result.content);
+    };
+    assert_eq!(link.uri, "forge://workspace/weather");
+
+    // Invalid arguments, including an existing project, are rejected
+    for arguments in [
+        json!({"project_name": "weather"}),
+        json!({"project_name": "../escape"}),
+        json!({"project_name": "two words"}),
+        json!({"project_name": "x".repeat(65)}),
+        json!({"project_name": "future", "protocol_version": "2099-01-01"}),
+    ] {
+        let error = harness
+            .call_tool("generate_project", arguments.clone())
+            .await
+            .unwrap_err();
+        let code = harness::error_code(&error);
+        assert_eq!(code, Some(ErrorCode::INVALID_PARAMS), "{}", arguments);
+    }
+    assert!(!root.join("future").exists());
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_generate_tool_resource_and_readme() {
+    let root = scratch_dir("handlers_readme");
+    let server = MCPForgeServer::default().with_workspace_root(&root);
+    let harness = TestHarness::start(server).await.unwrap();
+
+    let result = harness
+        .call_tool(
+            "generate_tool",
+            json!({"tool_name": "search", "description": "Search files"}),
+        )
+        .await
+        .unwrap();
+    let code = &result.content[0].as_text().unwrap().text;
+    assert!(code.contains("pub async fn execute_search"), "{}", code);
+
+    let result = harness
+        .call_tool(
+            "generate_resource",
+            json!({"resource_name": "settings", "type": "json"}),
+        )
+        .await
+        .unwrap();
+    assert!(
+        result.content[0]
+            .as_text()
+            .unwrap()
+            .text
+            .contains("settings")
+    );
+
+    // The README is written to `output_path` inside the workspace
+    let result = harness
+        .call_tool(
+            "generate_readme",
+            json!({"project_name": "weather", "output_path": "docs/README.md"}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert!(
+        std::fs::read_to_string(root.join("docs/README.md"))
+            .unwrap()
+            .starts_with("# weather")
+    );
+    for output_path in [
+        root.join("escape.md").to_str().unwrap(),
+        "../escape.md",
+        "docs",
+    ] {
+        let error = harness
+            .call_tool(
+                "generate_readme",
+                json!({"project_name": "weather", "output_path": output_path}),
+            )
+            .await
+            .unwrap_err();
+        let code = harness::error_code(&error);
+        assert_eq!(code, Some(ErrorCode::INVALID_PARAMS), "{}", output_path);
+    }
+
+    // Names that aren't identifiers are rejected
+    let error = harness
+        .call_tool(
+            "generate_tool",
+            json!({"tool_name": "word-count", "description": "Count words"}),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(harness::error_code(&error), Some(ErrorCode::INVALID_PARAMS));
+
+    // Without elicitation an unknown resource type is rejected
+    let error = harness
+        .call_tool(
+            "generate_resource",
+            json!({"resource_name": "settings", "type": "yaml"}),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(harness::error_code(&error), Some(ErrorCode::INVALID_PARAMS));
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_validate_manifest() {
+    let harness = start().await;
+
+    let valid = json!({"mcpServers": {"forge": {"command": "mcp-forge"}}}).to_string();
+    let result = harness
+        .call_tool("validate_manifest", json!({"manifest_content": valid}))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["valid"], true);
+
+    // An invalid manifest is a failed result, not a protocol error
+    let result = harness
+        .call_tool("validate_manifest", json!({"manifest_content": "{"}))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    let error = harness
+        .call_tool(
+            "validate_manifest",
+            json!({"manifest_content": valid, "format": "nonsense"}),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(harness::error_code(&error), Some(ErrorCode::INVALID_PARAMS));
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_verify_manifest_and_check_project_failures() {
+    let root = scratch_dir("handlers_failures");
+    let server = MCPForgeServer::default().with_workspace_root(&root);
+    let harness = TestHarness::start(server).await.unwrap();
+
+    let result = harness
+        .call_tool("verify_manifest", json!({"path": "missing.json"}))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    let result = harness
+        .call_tool("check_project", json!({"path": "missing"}))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_test_server() {
+    let arguments = json!({
+        "command": env!("CARGO_BIN_EXE_mcp-forge"),
+        "args": ["serve"],
+        "junit": true,
+    });
+    let harness = start().await;
+    let result = harness
+        .call_tool("test_server", arguments.clone())
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false), "{:?}", result.content);
+    assert_eq!(result.structured_content.unwrap()["failed"], 0);
+    let texts: Vec<&str> = result
+        .content
+        .iter()
+        .filter_map(|content| content.as_text().map(|text| text.text.as_str()))
+        .collect();
+    assert!(texts[0].starts_with("Conformance of "), "{}", texts[0]);
+    assert!(texts[1].starts_with("<?xml"), "{}", texts[1]);
+    harness.shutdown().await;
+
+    // The executor takes the same arguments
+    let output = tool_executor::execute_tool("test_server", &arguments)
+        .await
+        .unwrap();
+    let (summary, xml) = output.split_once("\n\n").unwrap();
+    assert!(summary.starts_with("Conformance of "), "{}", output);
+    assert!(xml.starts_with("<?xml"), "{}", output);
+}
+
+#[tokio::test]
+async fn test_tool_call_errors() {
+    let harness = start().await;
+
+    let error = harness
+        .call_tool("no_such_tool", json!({}))
+        .await
+        .unwrap_err();
+    assert_eq!(harness::error_code(&error), Some(ErrorCode::INVALID_PARAMS));
+
+    let error = harness
+        .call_tool("generate_tool", json!({"tool_name": "search"}))
+        .await
+        .unwrap_err();
+    assert_eq!(harness::error_code(&error), Some(ErrorCode::INVALID_PARAMS));
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_unknown_older_protocol_version() {
+    let older: ProtocolVersion = serde_json::from_str("\"2024-01-01\"").unwrap();
+    let client = HarnessClient::new().with_protocol_version(older.clone());
+    let harness = TestHarness::start_with(MCPForgeServer::default(), client)
+        .await
+        .unwrap();
+    // Tool results are adapted to the version the server answered with
+    assert_eq!(harness.peer_info().unwrap().protocol_version, older);
+    let result = harness
+        .call_tool("validate_manifest", json!({"manifest_content": "{}"}))
+        .await
+        .unwrap();
+    assert!(result.structured_content.is_none());
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_structured_output_for_older_sessions() {
+    let client = HarnessClient::new().with_protocol_version(ProtocolVersion::V_2025_03_26);
+    let harness = TestHarness::start_with(MCPForgeServer::default(), client)
+        .await
+        .unwrap();
+    let result = harness
+        .call_tool("validate_manifest", json!({"manifest_content": "{}"}))
+        .await
+        .unwrap();
+    assert!(result.structured_content.is_none());
+    let json = &result.content.last().unwrap().as_text().unwrap().text;
+    assert!(serde_json::from_str::<serde_json::Value>(json).is_ok());
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_list_and_read_resources() {
+    let root = scratch_dir("handlers_resources");
+    std::fs::write(root.join("notes.txt"), "hello").unwrap();
+    let server = MCPForgeServer::default().with_workspace_root(&root);
+    let harness = TestHarness::start(server).await.unwrap();
+
+    let resources = harness.list_all_resources().await.unwrap();
+    let uris: Vec<&str> = resources.iter().map(|r| r.uri.as_str()).collect();
+    assert!(uris.contains(&"forge://templates/main.rs"));
+    assert_eq!(uris.last(), Some(&"forge://workspace/"));
+
+    let templates = harness.list_all_resource_templates().await.unwrap();
+    assert_eq!(templates[0].uri_template, "forge://workspace/{+path}");
+
+    let result = harness
+        .read_resource("forge://templates/main.rs")
+        .await
+        .unwrap();
+    assert!(matches!(
+        &result.contents[0],
+        ResourceContents::TextResourceContents { text, .. } if text.contains("fn main")
+    ));
+
+    let result = harness
+        .read_resource("forge://assets/icon.png")
+        .await
+        .unwrap();
+    assert!(matches!(
+        &result.contents[0],
+        ResourceContents::BlobResourceContents { mime_type: Some(mime), .. } if mime == "image/png"
+    ));
+
+    let result = harness
+        .read_resource("forge://workspace/notes.txt")
+        .await
+        .unwrap();
+    assert!(matches!(
+        &result.contents[0],
+        ResourceContents::TextResourceContents { text, .. } if text == "hello"
+    ));
+
+    for (uri, code) in [
+        ("forge://templates/missing", ErrorCode::RESOURCE_NOT_FOUND),
+        (
+            "forge://workspace/missing.txt",
+            ErrorCode::RESOURCE_NOT_FOUND,
+        ),
+        ("forge://workspace/../outside", ErrorCode::INVALID_PARAMS),
+    ] {
+        let error = harness.read_resource(uri).await.unwrap_err();
+        assert_eq!(harness::error_code(&error), Some(code), "{}", uri);
+    }
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_resource_subscriptions() {
+    let templates = scratch_dir("handlers_subscriptions");
+    std::fs::write(templates.join("main.rs.template"), "fn main() {}\n").unwrap();
+    let server = MCPForgeServer::default().with_template_dir(&templates);
+    let harness = TestHarness::start(server).await.unwrap();
+
+    let uri = "forge://templates/main.rs".to_string();
+    harness
+        .subscribe(SubscribeRequestParam { uri: uri.clone() })
+        .await
+        .unwrap();
+    std::fs::write(
+        templates.join("main.rs.template"),
+        "fn main() {\n    println!(\"changed\");\n}\n",
+    )
+    .unwrap();
+    assert!(eventually(|| harness.updated_resources().contains(&uri)).await);
+
+    std::fs::write(templates.join("extra.rs.template"), "// extra\n").unwrap();
+    assert!(eventually(|| harness.resource_list_changes() > 0).await);
+
+    harness
+        .unsubscribe(UnsubscribeRequestParam { uri })
+        .await
+        .unwrap();
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_set_level_forwards_log_messages() {
+    let loggers = ClientLoggers::new();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(ClientLogLayer::new(loggers.clone())),
+    );
+    let server = MCPForgeServer::default().with_client_loggers(loggers);
+    let harness = TestHarness::start(server).await.unwrap();
+
+    harness
+        .set_level(SetLevelRequestParam {
+            level: LoggingLevel::Info,
+        })
+        .await
+        .unwrap();
+    harness
+        .call_tool(
+            "generate_tool",
+            json!({"tool_name": "search", "description": "Search files"}),
+        )
+        .await
+        .unwrap();
+    assert!(eventually(|| !harness.log_messages().is_empty()).await);
+    assert!(
+        harness
+            .log_messages()
+            .iter()
+            .all(|message| message.level != LoggingLevel::Debug)
+    );
+    harness.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_prompts() {
+    let harness = start().await;
+
+    let prompts = harness.list_all_prompts().await.unwrap();
+    assert_eq!(prompts.len(), 11);
+    assert!(prompts.windows(2).all(|pair| pair[0].name < pair[1].name));
+
+    let result = harness
+        .get_prompt("generate-tool", json!({"tool_name": "search"}))
+        .await
+        .unwrap();
+    assert!(!result.messages.is_empty());
+
+    let error = harness
+        .get_prompt("no-such-prompt", json!({}))
+        .await
+        .unwrap_err();
+    assert_eq!(harness::error_code(&error), Some(ErrorCode::INVALID_PARAMS));
+    harness.shutdown().await;
+}
@@ -0,0 +1,46 @@
+//! Golden session tests for the mcp-forge binary
+//!
+//! `tests/golden/forge-session.jsonl` was recorded with `mcp-forge record`.
+//! Replaying it against the current build catches unintended changes to
+//! listings, tool output and error codes. After an intended change, refresh
+//! it with:
+//!
+//! ```text
+//! mcp-forge replay --update tests/golden/forge-session.jsonl mcp-forge serve
+//! ```
+
+use mcp_forge::recording;
+use std::process::Stdio;
+
+const GOLDEN_SESSION: &str = include_str!("golden/forge-session.jsonl");
+
+#[tokio::test]
+async fn test_replay_golden_session() {
+    let messages = recording::parse_recording(GOLDEN_SESSION).unwrap();
+    let mut child = tokio::process::Command::new(env!("CARGO_BIN_EXE_mcp-forge"))
+        .arg("serve")
+        .current_dir(env!("CARGO_TARGET_TMPDIR"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+    let server_in = child.stdin.take().unwrap();
+    let server_out = child.stdout.take().unwrap();
+
+    let ignore = recording::DEFAULT_IGNORE.map(String::from);
+    let report = recording::replay(
+        &messages,
+        server_in,
+        server_out,
+        &ignore,
+        recording::DEFAULT_TIMEOUT,
+    )
+    .await
+    .unwrap();
+
+    assert!(report.success(), "{}", report.summary());
+    assert_eq!(report.matched, 7);
+    assert!(child.wait().await.unwrap().success());
+}
@@ -0,0 +1,138 @@
+//! Generated projects must compile
+//!
+//! Renders every built-in template into a project and runs
+//! `cargo check --offline --all-targets` on it, so template changes that
+//! break against the pinned rmcp version fail here rather than in a user's
+//! freshly generated server. The basic project's generated integration
+//! tests are run too. Dependencies come from the local cargo cache (or a
+//! vendored source configured for cargo); build artifacts are shared between
+//! runs in this crate's target directory.
+
+use mcp_forge::config::ForgeConfig;
+use mcp_forge::resources::get_resource;
+use mcp_forge::tool_executor::{
+    ExecutionContext, check_project_report, generate_project, render_tool_template,
+};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Scratch directory for generated projects and their build artifacts
+fn scratch_dir() -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join("template-build")
+}
+
+/// Text of a built-in template
+fn template(key: &str) -> String {
+    get_resource(key)
+        .and_then(|resource| resource.text().map(str::to_string))
+        .unwrap_or_else(|| panic!("missing template {}", key))
+}
+
+/// Generate a project from the built-in templates
+async fn generate(workspace: &Path, name: &str) -> PathBuf {
+    let _ = fs::remove_dir_all(workspace.join(name));
+    let args = json!({
+        "project_name": name,
+        "workspace_root": workspace,
+        "author": "Template Test <test@example.com>",
+    });
+    generate_project(&args, &ForgeConfig::default(), &ExecutionContext::default())
+        .await
+        .unwrap()
+        .path
+}
+
+/// Run `cargo check` on a generated project and fail with its diagnostics
+async fn assert_builds(workspace: &Path, name: &str) {
+    let args = json!({
+        "path": name,
+        "workspace_root": workspace,
+        "target_dir": scratch_dir().join("target"),
+    });
+    let report = check_project_report(&args, &ExecutionContext::default())
+        .await
+        .unwrap();
+    assert!(
+        report.success,
+        "{} does not build:\n{}",
+        name,
+        report.summary()
+    );
+}
+
+/// Run a generated project's integration tests, returning whether they
+/// passed and their output
+async fn run_tests(project: &Path, update_snapshots: bool) -> (bool, String) {
+    let mut command = tokio::process::Command::new(env!("CARGO"));
+    command
+        .args(["test", "--offline", "--test", "server"])
+        .current_dir(project)
+        .env("CARGO_TARGET_DIR", scratch_dir().join("target"))
+        .env_remove("UPDATE_SNAPSHOTS");
+    if update_snapshots {
+        command.env("UPDATE_SNAPSHOTS", "1");
+    }
+    let output = command.output().await.unwrap();
+    (
+        output.status.success(),
+        format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ),
+    )
+}
+
+/// Run a generated project's integration tests and fail with their output
+async fn assert_tests_pass(project: &Path, update_snapshots: bool) {
+    let (success, output) = run_tests(project, update_snapshots).await;
+    assert!(success, "{} tests failed:\n{}", project.display(), output);
+}
+
+#[tokio::test]
+async fn test_templates_build() {
+    let workspace = scratch_dir().join("projects");
+    fs::create_dir_all(&workspace).unwrap();
+
+    // The project templates as generate_project writes them
+    let project = generate(&workspace, "basic-server").await;
+    assert_builds(&workspace, "basic-server").await;
+
+    // A fresh project has no tools/list snapshot, so its tests fail until
+    // one is recorded
+    let snapshot = project.join("tests/snapshots/tools_list.json");
+    assert!(!snapshot.exists());
+    let (success, output) = run_tests(&project, false).await;
+    assert!(!success, "tests passed without a snapshot:\n{}", output);
+    assert!(
+        output.contains("run with UPDATE_SNAPSHOTS=1 to record it"),
+        "{}",
+        output
+    );
+    assert_tests_pass(&project, true).await;
+    assert!(snapshot.exists());
+    assert_tests_pass(&project, false).await;
+
+    // The advanced templates, added as modules of a generated project
+    let project = generate(&workspace, "advanced-server").await;
+    let advanced_tool = render_tool_template(&template("template/advanced-tool-rs"), "text_stats");
+    fs::write(project.join("src/advanced_tool.rs"), advanced_tool).unwrap();
+    fs::write(
+        project.join("src/advanced_prompts.rs"),
+        template("template/prompts-advanced-rs"),
+    )
+    .unwrap();
+    fs::write(
+        project.join("src/advanced_resources.rs"),
+        template("template/resources-advanced-rs"),
+    )
+    .unwrap();
+    let lib = fs::read_to_string(project.join("src/lib.rs")).unwrap();
+    fs::write(
+        project.join("src/lib.rs"),
+        lib + "pub mod advanced_prompts;\npub mod advanced_resources;\npub mod advanced_tool;\n",
+    )
+    .unwrap();
+    assert_builds(&workspace, "advanced-server").await;
+}
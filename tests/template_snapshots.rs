@@ -0,0 +1,234 @@
+//! Snapshot tests of the rendered templates
+//!
+//! Every built-in template is rendered with a fixed context and compared to
+//! the committed output in `tests/snapshots/templates`:
+//!
+//! - the project files for each supported protocol version, once with the
+//!   default options and once with a custom author, license and rmcp version
+//! - the project files with the template pack in `tests/fixtures/template-pack`
+//!   overriding some of the built-in templates
+//! - the code `generate_tool` and `generate_resource` (per resource type)
+//!   return
+//! - the advanced tool, prompts and resources templates, the tool template
+//!   filled in for a `text_stats` tool
+//!
+//! `Cargo.toml.template` is the exception: it is only served as a resource.
+//! Generated projects get their `Cargo.toml` from `generate_cargo_toml`,
+//! which the project cases cover.
+//!
+//! A snapshot holds all files of a case, each under a `==> path <==` header.
+//! After changing a template on purpose, rewrite the snapshots with
+//! `UPDATE_SNAPSHOTS=1 cargo test --test template_snapshots` and review the
+//! diff. Snapshots of cases that no longer exist are removed then too.
+
+use mcp_forge::config::ForgeConfig;
+use mcp_forge::protocol;
+use mcp_forge::resources::get_resource;
+use mcp_forge::tool_executor::{
+    RESOURCE_TYPES, execute_tool, render_project, render_tool_template,
+};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lines of context shown around the first difference
+const CONTEXT_LINES: usize = 3;
+
+fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/templates")
+}
+
+fn update_mode() -> bool {
+    std::env::var_os("UPDATE_SNAPSHOTS").is_some()
+}
+
+/// Project arguments every case starts from
+fn project_args() -> Value {
+    json!({
+        "project_name": "snapshot-server",
+        "description": "Server rendered by the template snapshot tests",
+    })
+}
+
+/// Join rendered files into one snapshot
+fn snapshot<'a>(files: impl IntoIterator<Item = (&'a str, String)>) -> String {
+    files
+        .into_iter()
+        .map(|(path, content)| {
+            let newline = if content.ends_with('\n') { "" } else { "\n" };
+            format!("==> {} <==\n{}{}", path, content, newline)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Text of a built-in template
+fn template(key: &str) -> String {
+    get_resource(key)
+        .and_then(|resource| resource.text().map(str::to_string))
+        .unwrap_or_else(|| panic!("missing template {}", key))
+}
+
+/// Render the project files with extra arguments on top of `project_args`
+fn render(extra: Value) -> String {
+    let mut args = project_args();
+    args.as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    snapshot(render_project(&args, &ForgeConfig::default()).unwrap())
+}
+
+/// Every case, by snapshot name
+async fn cases() -> BTreeMap<String, String> {
+    let mut cases = BTreeMap::new();
+    for version in protocol::SUPPORTED {
+        cases.insert(
+            format!("project-{}-defaults", version),
+            render(json!({"protocol_version": version.to_string()})),
+        );
+        cases.insert(
+            format!("project-{}-custom", version),
+            render(json!({
+                "protocol_version": version.to_string(),
+                "author": "Snapshot Test <snapshot@example.com>",
+                "license": "Apache-2.0",
+                "rmcp_version": "0.8.5",
+            })),
+        );
+    }
+
+    let pack = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/template-pack");
+    cases.insert(
+        "project-template-pack".to_string(),
+        render(json!({"template_dir": pack})),
+    );
+
+    let tool = execute_tool(
+        "generate_tool",
+        &json!({"tool_name": "word_count", "description": "Count the words in a text"}),
+    )
+    .await
+    .unwrap();
+    cases.insert("tool".to_string(), snapshot([("word_count.rs", tool)]));
+    for resource_type in RESOURCE_TYPES {
+        let resource = execute_tool(
+            "generate_resource",
+            &json!({
+                "resource_name": "user_data",
+                "resource_type": resource_type,
+                "description": "User database resource",
+            }),
+        )
+        .await
+        .unwrap();
+        cases.insert(
+            format!("resource-{}", resource_type),
+            snapshot([("user_data.rs", resource)]),
+        );
+    }
+
+    let advanced_tool = render_tool_template(&template("template/advanced-tool-rs"), "text_stats");
+    cases.insert(
+        "advanced-tool".to_string(),
+        snapshot([("src/advanced_tool.rs", advanced_tool)]),
+    );
+    cases.insert(
+        "advanced-prompts".to_string(),
+        snapshot([(
+            "src/advanced_prompts.rs",
+            template("template/prompts-advanced-rs"),
+        )]),
+    );
+    cases.insert(
+        "advanced-resources".to_string(),
+        snapshot([(
+            "src/advanced_resources.rs",
+            template("template/resources-advanced-rs"),
+        )]),
+    );
+    cases
+}
+
+/// Where `actual` first departs from `expected`, with surrounding lines
+fn first_difference(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let line = expected
+        .iter()
+        .zip(&actual)
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or(expected.len().min(actual.len()));
+    let excerpt = |lines: &[&str]| {
+        let start = line.saturating_sub(CONTEXT_LINES);
+        let end = (line + CONTEXT_LINES + 1).min(lines.len());
+        (start..end)
+            .map(|i| format!("{:>5} | {}", i + 1, lines[i]))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    format!(
+        "first difference at line {}\n--- expected\n{}\n--- actual\n{}",
+        line + 1,
+        excerpt(&expected),
+        excerpt(&actual)
+    )
+}
+
+#[tokio::test]
+async fn test_template_snapshots() {
+    let dir = snapshot_dir();
+    let cases = cases().await;
+    let mut problems = Vec::new();
+
+    for (name, actual) in &cases {
+        let path = dir.join(format!("{}.snap", name));
+        if update_mode() {
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(&path, actual).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == *actual => {}
+            Ok(expected) => problems.push(format!(
+                "{} changed, {}",
+                path.display(),
+                first_difference(&expected, actual)
+            )),
+            Err(_) => problems.push(format!("{} is missing", path.display())),
+        }
+    }
+
+    // Snapshots left behind by removed cases
+    for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        if cases.contains_key(name.as_ref()) {
+            continue;
+        }
+        if update_mode() {
+            fs::remove_file(&path).unwrap();
+        } else {
+            problems.push(format!("{} has no case", path.display()));
+        }
+    }
+
+    assert!(
+        problems.is_empty(),
+        "{}\n\nrun with UPDATE_SNAPSHOTS=1 to accept the changes",
+        problems.join("\n\n")
+    );
+}
+
+#[test]
+fn test_first_difference() {
+    let difference = first_difference("a\nb\nc\nd\n", "a\nb\nx\nd\n");
+    assert!(difference.starts_with("first difference at line 3"));
+    assert!(difference.contains("    3 | c"));
+    assert!(difference.contains("    3 | x"));
+
+    // A file that only got longer differs after its last line
+    let difference = first_difference("a\n", "a\nb\n");
+    assert!(difference.starts_with("first difference at line 2"));
+    assert!(difference.contains("    2 | b"));
+}